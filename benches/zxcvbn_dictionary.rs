@@ -0,0 +1,19 @@
+use criterion::black_box;
+use criterion::Criterion;
+use criterion::{criterion_group, criterion_main};
+
+use zxcvbn::zxcvbn;
+
+pub fn bench_zxcvbn_dictionary(c: &mut Criterion) {
+    c.bench_function("zxcvbn_dictionary", |b| {
+        b.iter(|| {
+            zxcvbn(
+                black_box("correcthorsebatterystaplemotherboardsunshineprincess"),
+                &[],
+            )
+        })
+    });
+}
+
+criterion_group!(benches, bench_zxcvbn_dictionary);
+criterion_main!(benches);
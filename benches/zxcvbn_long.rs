@@ -0,0 +1,14 @@
+use criterion::black_box;
+use criterion::Criterion;
+use criterion::{criterion_group, criterion_main};
+
+use zxcvbn::zxcvbn;
+
+pub fn bench_zxcvbn_long(c: &mut Criterion) {
+    c.bench_function("zxcvbn_long", |b| {
+        b.iter(|| zxcvbn(black_box("Tr0ub4dor&3correcthorsebatterystaple9xK!mQz#2pLwVbN7rT5yUeIoPaSdFgHjKlZxCvBnMqWeRtYuIoPlKjHgFdSaZx"), &[]))
+    });
+}
+
+criterion_group!(benches, bench_zxcvbn_long);
+criterion_main!(benches);
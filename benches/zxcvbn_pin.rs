@@ -0,0 +1,29 @@
+use criterion::black_box;
+use criterion::Criterion;
+use criterion::{criterion_group, criterion_main};
+
+use zxcvbn::matching::patterns::PatternKind;
+use zxcvbn::{zxcvbn, zxcvbn_with_options, ZxcvbnOptions};
+
+pub fn bench_zxcvbn_pin(c: &mut Criterion) {
+    c.bench_function("zxcvbn_pin", |b| {
+        b.iter(|| zxcvbn(black_box("385926"), &[]))
+    });
+
+    // without the dictionary and spatial matchers, which trades accuracy for speed: they also
+    // find common PINs like 123456 and keypad walks like 147258
+    let options = [
+        PatternKind::Dictionary,
+        PatternKind::Reverse,
+        PatternKind::L33t,
+        PatternKind::Spatial,
+    ]
+    .into_iter()
+    .fold(ZxcvbnOptions::new(), ZxcvbnOptions::disable_matcher);
+    c.bench_function("zxcvbn_pin_numeric_matchers", |b| {
+        b.iter(|| zxcvbn_with_options(black_box("385926"), &[], &options))
+    });
+}
+
+criterion_group!(benches, bench_zxcvbn_pin);
+criterion_main!(benches);
@@ -0,0 +1,15 @@
+use criterion::black_box;
+use criterion::Criterion;
+use criterion::{criterion_group, criterion_main};
+
+use zxcvbn::zxcvbn;
+
+pub fn bench_zxcvbn_saturated(c: &mut Criterion) {
+    // the guesses of this password overflow u64
+    c.bench_function("zxcvbn_saturated", |b| {
+        b.iter(|| zxcvbn(black_box("!QASW@#EDFR$%TGHY^&UJKI*(OL"), &[]))
+    });
+}
+
+criterion_group!(benches, bench_zxcvbn_saturated);
+criterion_main!(benches);
@@ -0,0 +1,18 @@
+use criterion::black_box;
+use criterion::Criterion;
+use criterion::{criterion_group, criterion_main};
+
+use zxcvbn::zxcvbn;
+
+pub fn bench_zxcvbn_serialization(c: &mut Criterion) {
+    let entropy = zxcvbn("r0sebudmaelstrom11/20/91aaaa", &[]);
+    c.bench_function("zxcvbn_serialization", |b| {
+        b.iter(|| {
+            let json = serde_json::to_string(black_box(&entropy)).unwrap();
+            serde_json::from_str::<serde_json::Value>(&json).unwrap()
+        })
+    });
+}
+
+criterion_group!(benches, bench_zxcvbn_serialization);
+criterion_main!(benches);
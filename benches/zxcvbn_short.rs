@@ -0,0 +1,14 @@
+use criterion::black_box;
+use criterion::Criterion;
+use criterion::{criterion_group, criterion_main};
+
+use zxcvbn::zxcvbn;
+
+pub fn bench_zxcvbn_short(c: &mut Criterion) {
+    c.bench_function("zxcvbn_short", |b| {
+        b.iter(|| zxcvbn(black_box("p4ssW0rd"), &[]))
+    });
+}
+
+criterion_group!(benches, bench_zxcvbn_short);
+criterion_main!(benches);
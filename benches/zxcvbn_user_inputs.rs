@@ -0,0 +1,36 @@
+use criterion::black_box;
+use criterion::Criterion;
+use criterion::{criterion_group, criterion_main};
+
+use zxcvbn::zxcvbn;
+
+pub fn bench_zxcvbn_user_inputs(c: &mut Criterion) {
+    let user_inputs = [
+        "jsmith",
+        "john",
+        "smith",
+        "john.smith@example.com",
+        "example",
+        "acme",
+        "acmecorp",
+        "1987",
+        "jsmith87",
+        "springfield",
+        "illinois",
+        "rover",
+        "mary",
+        "smithj",
+        "johnny",
+        "0415",
+        "engineering",
+        "chicago",
+        "cubs",
+        "555-0134",
+    ];
+    c.bench_function("zxcvbn_user_inputs", |b| {
+        b.iter(|| zxcvbn(black_box("JohnSmith1987!rover"), black_box(&user_inputs)))
+    });
+}
+
+criterion_group!(benches, bench_zxcvbn_user_inputs);
+criterion_main!(benches);
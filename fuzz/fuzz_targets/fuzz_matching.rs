@@ -0,0 +1,23 @@
+#![no_main]
+
+use std::collections::HashMap;
+
+use libfuzzer_sys::fuzz_target;
+
+// The password is followed by the user inputs, separated by NUL bytes.
+fuzz_target!(|data: &[u8]| {
+    let data = String::from_utf8_lossy(data);
+    let mut parts = data.split('\0');
+    let password = parts.next().unwrap_or_default();
+    let user_inputs: HashMap<String, usize> = parts
+        .enumerate()
+        .map(|(i, input)| (input.to_lowercase(), i + 1))
+        .collect();
+    // same truncation as `zxcvbn`, as the matching is quadratic in the length of the password
+    let password: String = password.chars().take(100).collect();
+    let chars: Vec<char> = password.chars().collect();
+    for m in zxcvbn::matching::omnimatch_with_graphs(&password, &user_inputs, &[]) {
+        assert!(m.i <= m.j && m.j < chars.len(), "{:?}", m);
+        assert_eq!(m.token, chars[m.i..=m.j].iter().collect::<String>());
+    }
+});
@@ -0,0 +1,51 @@
+#![no_main]
+
+use std::collections::HashMap;
+
+use libfuzzer_sys::fuzz_target;
+use zxcvbn::matching::patterns::{DictionaryPattern, MatchPattern};
+use zxcvbn::matching::Match;
+
+// The password is followed by a NUL byte and by triples of bytes, each of which describes an
+// extra match: its start, its length and the logarithm of its guesses. The extra matches are
+// scored along with the matches found in the password.
+fuzz_target!(|data: &[u8]| {
+    let (password, extra) = match data.iter().position(|&b| b == 0) {
+        Some(nul) => (&data[..nul], &data[nul + 1..]),
+        None => (data, &[][..]),
+    };
+    let password = String::from_utf8_lossy(password);
+    let chars: Vec<char> = password.chars().take(100).collect();
+    let password: String = chars.iter().collect();
+
+    let mut matches = zxcvbn::matching::omnimatch_with_graphs(&password, &HashMap::new(), &[]);
+    if !chars.is_empty() {
+        for triple in extra.chunks_exact(3) {
+            let i = usize::from(triple[0]) % chars.len();
+            let j = i + usize::from(triple[1]) % (chars.len() - i);
+            let guesses = match triple[2] {
+                255 => u64::MAX,
+                exponent => 1 << (exponent % 64),
+            };
+            matches.push(Match {
+                i,
+                j,
+                token: chars[i..=j].iter().collect(),
+                pattern: MatchPattern::Dictionary(DictionaryPattern::default()),
+                guesses: Some(guesses),
+            });
+        }
+    }
+
+    for exclude_additive in [false, true] {
+        let result =
+            zxcvbn::scoring::most_guessable_match_sequence(&password, &matches, exclude_additive);
+        // the sequence covers the whole password, without overlaps
+        let mut next = 0;
+        for m in &result.sequence {
+            assert_eq!(m.i, next, "{:?}", result.sequence);
+            next = m.j + 1;
+        }
+        assert_eq!(next, chars.len());
+    }
+});
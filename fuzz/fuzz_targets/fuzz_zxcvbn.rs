@@ -0,0 +1,13 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// The password is followed by the user inputs, separated by NUL bytes.
+// Bytes which are not valid UTF-8 are evaluated as opaque symbols.
+fuzz_target!(|data: &[u8]| {
+    let mut parts = data.split(|&b| b == 0);
+    let password = parts.next().unwrap_or_default();
+    let user_inputs: Vec<&[u8]> = parts.collect();
+    let entropy = zxcvbn::zxcvbn_bytes(password, &user_inputs);
+    assert!(u8::from(entropy.score()) <= 4);
+});
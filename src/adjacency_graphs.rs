@@ -1,7 +1,42 @@
+//! Contains the built-in keyboard layouts used for spatial matching.
+//!
+//! # Example
+//! ```rust
+//! use zxcvbn::adjacency_graphs::{graph_by_name, list_available_graphs};
+//!
+//! assert!(list_available_graphs().contains(&"qwerty"));
+//! let qwerty = graph_by_name("qwerty").unwrap();
+//! assert!(qwerty.adjacents().contains_key(&'q'));
+//! ```
+
 use std::collections::HashMap;
 
+use crate::matching::AdjacencyGraph;
+
+/// The names of the built-in graphs, as reported in the `graph` of spatial matches.
+const BUILTIN_GRAPH_NAMES: [&str; 4] = ["qwerty", "dvorak", "keypad", "mac_keypad"];
+
+/// The names of all built-in adjacency graphs, e.g. to avoid reusing one of them for the
+/// extra graphs of [`omnimatch_with_graphs`](crate::matching::omnimatch_with_graphs).
+pub fn list_available_graphs() -> &'static [&'static str] {
+    &BUILTIN_GRAPH_NAMES
+}
+
+/// Get the built-in adjacency graph with the given name, if there is one.
+pub fn graph_by_name(name: &str) -> Option<&'static AdjacencyGraph<'static>> {
+    lazy_static! {
+        static ref BUILTIN_GRAPHS: [AdjacencyGraph<'static>; 4] = [
+            AdjacencyGraph::new(BUILTIN_GRAPH_NAMES[0], QWERTY.clone()),
+            AdjacencyGraph::new(BUILTIN_GRAPH_NAMES[1], DVORAK.clone()),
+            AdjacencyGraph::new(BUILTIN_GRAPH_NAMES[2], KEYPAD.clone()),
+            AdjacencyGraph::new(BUILTIN_GRAPH_NAMES[3], MAC_KEYPAD.clone()),
+        ];
+    }
+    BUILTIN_GRAPHS.iter().find(|graph| graph.name() == name)
+}
+
 lazy_static! {
-    pub static ref QWERTY: HashMap<char, Vec<Option<&'static str>>> = {
+    pub(crate) static ref QWERTY: HashMap<char, Vec<Option<&'static str>>> = {
         let mut table = HashMap::with_capacity(94);
         table.insert(
             '!',
@@ -635,7 +670,7 @@ lazy_static! {
         table.insert('~', vec![None, None, None, Some("1!"), None, None]);
         table
     };
-    pub static ref DVORAK: HashMap<char, Vec<Option<&'static str>>> = {
+    pub(crate) static ref DVORAK: HashMap<char, Vec<Option<&'static str>>> = {
         let mut table = HashMap::with_capacity(94);
         table.insert(
             '!',
@@ -1269,7 +1304,7 @@ lazy_static! {
         table.insert('~', vec![None, None, None, Some("1!"), None, None]);
         table
     };
-    pub static ref KEYPAD: HashMap<char, Vec<Option<&'static str>>> = {
+    pub(crate) static ref KEYPAD: HashMap<char, Vec<Option<&'static str>>> = {
         let mut table = HashMap::with_capacity(15);
         table.insert(
             '*',
@@ -1468,7 +1503,7 @@ lazy_static! {
         );
         table
     };
-    pub static ref MAC_KEYPAD: HashMap<char, Vec<Option<&'static str>>> = {
+    pub(crate) static ref MAC_KEYPAD: HashMap<char, Vec<Option<&'static str>>> = {
         let mut table = HashMap::with_capacity(16);
         table.insert(
             '*',
@@ -1681,3 +1716,64 @@ lazy_static! {
         table
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_graph_by_name() {
+        for &name in list_available_graphs() {
+            assert_eq!(graph_by_name(name).unwrap().name(), name);
+        }
+        assert_eq!(graph_by_name("dvorak").unwrap().adjacents(), &*DVORAK);
+        assert_eq!(graph_by_name("azerty"), None);
+    }
+
+    /// Builds the graph of a keyboard whose rows are each indented by one more character than
+    /// the keys they are shifted by, like the build script of the original zxcvbn:
+    /// https://github.com/dropbox/zxcvbn/blob/master/data-scripts/build_keyboard_adjacency_graphs.py
+    fn slanted_graph(layout: &[&'static str]) -> HashMap<char, Vec<Option<&'static str>>> {
+        let mut positions = HashMap::new();
+        for (y, row) in layout.iter().enumerate() {
+            for token in row.split_whitespace() {
+                let column = row.find(token).unwrap() - y;
+                positions.insert(((column / 3) as i32, y as i32), token);
+            }
+        }
+        let mut graph = HashMap::new();
+        for (&(x, y), token) in &positions {
+            let neighbors = [
+                (x - 1, y),
+                (x, y - 1),
+                (x + 1, y - 1),
+                (x + 1, y),
+                (x, y + 1),
+                (x - 1, y + 1),
+            ]
+            .iter()
+            .map(|position| positions.get(position).copied())
+            .collect::<Vec<_>>();
+            for c in token.chars() {
+                graph.insert(c, neighbors.clone());
+            }
+        }
+        graph
+    }
+
+    #[test]
+    fn test_dvorak_graph_matches_layout() {
+        // the US Dvorak layout
+        let dvorak = slanted_graph(&[
+            "`~ 1! 2@ 3# 4$ 5% 6^ 7& 8* 9( 0) [{ ]}",
+            "    '\" ,< .> pP yY fF gG cC rR lL /? =+ \\|",
+            "     aA oO eE uU iI dD hH tT nN sS -_",
+            "      ;: qQ jJ kK xX bB mM wW vV zZ",
+        ]);
+        assert_eq!(*DVORAK, dvorak);
+        assert_eq!(
+            DVORAK[&'a'],
+            vec![None, Some("'\""), Some(",<"), Some("oO"), Some(";:"), None]
+        );
+    }
+}
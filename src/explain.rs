@@ -0,0 +1,103 @@
+use crate::frequency_lists::DictionaryType;
+use crate::matching::patterns::MatchPattern;
+use crate::matching::Match;
+use crate::time_estimates::ScenarioName;
+use crate::Entropy;
+
+pub(crate) fn explain(entropy: &Entropy) -> String {
+    if entropy.sequence().is_empty() {
+        return "Your password is empty.".to_string();
+    }
+    let descriptions = entropy
+        .sequence()
+        .iter()
+        .map(|m| format!("{} ('{}')", describe_match(m), m.token))
+        .collect::<Vec<String>>();
+    format!(
+        "Your password contains {}. The strongest attacker scenario cracks it in {}.",
+        join_descriptions(&descriptions),
+        entropy
+            .crack_times()
+            .crack_time(ScenarioName::OfflineFastHashing1e10PerSecond)
+    )
+}
+
+fn describe_match(m: &Match) -> String {
+    match m.pattern {
+        MatchPattern::Dictionary(ref p) => {
+            let mut description = match p.dictionary_name {
+                DictionaryType::Passwords => "a common password".to_string(),
+                DictionaryType::English => "a common English word".to_string(),
+                DictionaryType::FemaleNames | DictionaryType::MaleNames => {
+                    "a common first name".to_string()
+                }
+                DictionaryType::Surnames => "a common surname".to_string(),
+                DictionaryType::UsTvAndFilm => "a word common in TV and film".to_string(),
+                DictionaryType::UserInputs => "one of your personal details".to_string(),
+                DictionaryType::Organization => "the name of your organization".to_string(),
+                DictionaryType::Phrases => "a common phrase".to_string(),
+                DictionaryType::Diceware => "a diceware passphrase".to_string(),
+                DictionaryType::Custom(name) => format!("a word from the {} list", name),
+            };
+            if p.reversed {
+                description.push_str(" spelled backwards");
+            }
+            if p.l33t {
+                description.push_str(" with l33t substitutions");
+            }
+            description
+        }
+        MatchPattern::Spatial(ref p) if p.graph == "keypad" || p.graph == "mac_keypad" => {
+            "a keypad pattern".to_string()
+        }
+        MatchPattern::Spatial(_) => "a keyboard pattern".to_string(),
+        MatchPattern::Repeat(_) => "a repeated pattern".to_string(),
+        MatchPattern::Sequence(ref p) => match p.sequence_name {
+            "lower" | "upper" => "a letter sequence".to_string(),
+            "digits" => "a digit sequence".to_string(),
+            _ => "a character sequence".to_string(),
+        },
+        MatchPattern::Regex(ref p) if p.regex_name == "recent_year" => "a recent year".to_string(),
+        MatchPattern::Regex(_) => "a common pattern".to_string(),
+        MatchPattern::Date(_) => "a date".to_string(),
+        #[cfg(feature = "regex")]
+        MatchPattern::CustomRegex(_) => "a custom pattern".to_string(),
+        MatchPattern::BruteForce => "random characters".to_string(),
+    }
+}
+
+/// Joins descriptions into an English list, e.g. "a, b, and c".
+fn join_descriptions(descriptions: &[String]) -> String {
+    match descriptions {
+        [] => String::new(),
+        [only] => only.clone(),
+        [first, second] => format!("{} and {}", first, second),
+        [init @ .., last] => format!("{}, and {}", init.join(", "), last),
+    }
+}
+
+// the explanations quote the tokens, which the "zeroize" feature masks
+#[cfg(all(test, not(feature = "zeroize")))]
+mod tests {
+    use crate::zxcvbn;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_explain() {
+        assert_eq!(zxcvbn("", &[]).explain(), "Your password is empty.");
+        assert_eq!(
+            zxcvbn("password", &[]).explain(),
+            "Your password contains a common password ('password'). \
+             The strongest attacker scenario cracks it in less than a second."
+        );
+        assert_eq!(
+            zxcvbn("jsmith1987qwerty", &["jsmith"]).explain(),
+            "Your password contains one of your personal details ('jsmith'), \
+             a recent year ('1987'), and a common password ('qwerty'). \
+             The strongest attacker scenario cracks it in less than a second."
+        );
+    }
+}
@@ -5,13 +5,19 @@ use itertools::Itertools;
 
 use crate::matching::patterns::*;
 use crate::matching::Match;
-use crate::{frequency_lists::DictionaryType, scoring::Score};
+use crate::scoring::is_word_with_number_suffix;
+use crate::{frequency_lists::DictionaryType, scoring::Score, ZxcvbnOptions};
 use std::fmt;
 
 /// A warning explains what's wrong with the password.
-#[derive(Debug, Copy, Clone, PartialEq)]
+///
+/// Its `Display` implementation gives the same English text as [`Warning::message`];
+/// match on the variants to filter or translate warnings.
+/// More warnings may be added in minor releases.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ser", derive(serde::Serialize))]
 #[allow(missing_docs)]
+#[non_exhaustive]
 pub enum Warning {
     StraightRowsOfKeysAreEasyToGuess,
     ShortKeyboardPatternsAreEasyToGuess,
@@ -27,50 +33,174 @@ pub enum Warning {
     DatesAreOftenEasyToGuess,
     NamesAndSurnamesByThemselvesAreEasyToGuess,
     CommonNamesAndSurnamesAreEasyToGuess,
+    AWordFollowedByANumberIsEasyToGuess,
 }
 
 impl fmt::Display for Warning {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message())
+    }
+}
+
+impl Warning {
+    /// The English text of the warning, e.g. "This is a top-10 common password.".
+    pub fn message(&self) -> &'static str {
         match self {
-            Warning::StraightRowsOfKeysAreEasyToGuess => {
-                write!(f, "Straight rows of keys are easy to guess.")
-            }
+            Warning::StraightRowsOfKeysAreEasyToGuess => "Straight rows of keys are easy to guess.",
             Warning::ShortKeyboardPatternsAreEasyToGuess => {
-                write!(f, "Short keyboard patterns are easy to guess.")
-            }
-            Warning::RepeatsLikeAaaAreEasyToGuess => {
-                write!(f, "Repeats like \"aaa\" are easy to guess.")
+                "Short keyboard patterns are easy to guess."
             }
-            Warning::RepeatsLikeAbcAbcAreOnlySlightlyHarderToGuess => write!(
-                f,
+            Warning::RepeatsLikeAaaAreEasyToGuess => "Repeats like \"aaa\" are easy to guess.",
+            Warning::RepeatsLikeAbcAbcAreOnlySlightlyHarderToGuess => {
                 "Repeats like \"abcabcabc\" are only slightly harder to guess than \"abc\"."
-            ),
-            Warning::ThisIsATop10Password => write!(f, "This is a top-10 common password."),
-            Warning::ThisIsATop100Password => write!(f, "This is a top-100 common password."),
-            Warning::ThisIsACommonPassword => write!(f, "This is a very common password."),
+            }
+            Warning::ThisIsATop10Password => "This is a top-10 common password.",
+            Warning::ThisIsATop100Password => "This is a top-100 common password.",
+            Warning::ThisIsACommonPassword => "This is a very common password.",
             Warning::ThisIsSimilarToACommonlyUsedPassword => {
-                write!(f, "This is similar to a commonly used password.")
+                "This is similar to a commonly used password."
             }
             Warning::SequencesLikeAbcAreEasyToGuess => {
-                write!(f, "Sequences like abc or 6543 are easy to guess.")
+                "Sequences like abc or 6543 are easy to guess."
             }
-            Warning::RecentYearsAreEasyToGuess => write!(f, "Recent years are easy to guess."),
-            Warning::AWordByItselfIsEasyToGuess => write!(f, "A word by itself is easy to guess."),
-            Warning::DatesAreOftenEasyToGuess => write!(f, "Dates are often easy to guess."),
+            Warning::RecentYearsAreEasyToGuess => "Recent years are easy to guess.",
+            Warning::AWordByItselfIsEasyToGuess => "A word by itself is easy to guess.",
+            Warning::DatesAreOftenEasyToGuess => "Dates are often easy to guess.",
             Warning::NamesAndSurnamesByThemselvesAreEasyToGuess => {
-                write!(f, "Names and surnames by themselves are easy to guess.")
+                "Names and surnames by themselves are easy to guess."
             }
             Warning::CommonNamesAndSurnamesAreEasyToGuess => {
-                write!(f, "Common names and surnames are easy to guess.")
+                "Common names and surnames are easy to guess."
+            }
+            Warning::AWordFollowedByANumberIsEasyToGuess => {
+                "A word followed by a year or a number is easy to guess."
             }
         }
     }
+
+    /// The reason of the warning, e.g. [`Reason::CommonPassword`] for
+    /// [`Warning::ThisIsATop10Password`].
+    pub fn reason(self) -> Reason {
+        match self {
+            Warning::StraightRowsOfKeysAreEasyToGuess
+            | Warning::ShortKeyboardPatternsAreEasyToGuess => Reason::KeyboardWalk,
+            Warning::RepeatsLikeAaaAreEasyToGuess
+            | Warning::RepeatsLikeAbcAbcAreOnlySlightlyHarderToGuess => Reason::Repeat,
+            Warning::ThisIsATop10Password
+            | Warning::ThisIsATop100Password
+            | Warning::ThisIsACommonPassword => Reason::CommonPassword,
+            Warning::ThisIsSimilarToACommonlyUsedPassword => Reason::SimilarToCommonPassword,
+            Warning::SequencesLikeAbcAreEasyToGuess => Reason::Sequence,
+            Warning::RecentYearsAreEasyToGuess => Reason::RecentYear,
+            Warning::AWordByItselfIsEasyToGuess => Reason::CommonWord,
+            Warning::DatesAreOftenEasyToGuess => Reason::ContainsDate,
+            Warning::NamesAndSurnamesByThemselvesAreEasyToGuess
+            | Warning::CommonNamesAndSurnamesAreEasyToGuess => Reason::Name,
+            Warning::AWordFollowedByANumberIsEasyToGuess => Reason::WordWithNumberSuffix,
+        }
+    }
+
+    /// How severe the warning is, from 0 for the most severe warnings.
+    /// Warnings about passwords found as-is in the frequency lists are the most severe.
+    fn severity(self) -> u8 {
+        match self {
+            Warning::ThisIsATop10Password => 0,
+            Warning::ThisIsATop100Password => 1,
+            Warning::ThisIsACommonPassword => 2,
+            Warning::ThisIsSimilarToACommonlyUsedPassword => 3,
+            Warning::RepeatsLikeAaaAreEasyToGuess => 4,
+            Warning::SequencesLikeAbcAreEasyToGuess => 5,
+            Warning::StraightRowsOfKeysAreEasyToGuess => 6,
+            Warning::AWordByItselfIsEasyToGuess => 7,
+            Warning::NamesAndSurnamesByThemselvesAreEasyToGuess => 8,
+            Warning::AWordFollowedByANumberIsEasyToGuess => 9,
+            Warning::RecentYearsAreEasyToGuess => 10,
+            Warning::DatesAreOftenEasyToGuess => 11,
+            Warning::ShortKeyboardPatternsAreEasyToGuess => 12,
+            Warning::RepeatsLikeAbcAbcAreOnlySlightlyHarderToGuess => 13,
+            Warning::CommonNamesAndSurnamesAreEasyToGuess => 14,
+        }
+    }
+}
+
+/// A machine-readable reason why a password is weak, which groups the warnings and
+/// suggestions about the same weakness, e.g. all the warnings about common passwords.
+///
+/// The English text for users remains that of the [`Warning`]s and [`Suggestion`]s;
+/// [`Reason::as_str`] gives a stable code, e.g. for an API.
+/// More reasons may be added in minor releases.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+#[cfg_attr(feature = "ser", serde(rename_all = "snake_case"))]
+#[non_exhaustive]
+pub enum Reason {
+    /// The password is a common password.
+    CommonPassword,
+    /// The password is a common password with predictable variations.
+    SimilarToCommonPassword,
+    /// The password contains a common word.
+    CommonWord,
+    /// The password contains a common name or surname.
+    Name,
+    /// The password contains a row or walk of adjacent keys.
+    KeyboardWalk,
+    /// The password contains repeated characters or words.
+    Repeat,
+    /// The password contains a sequence like `abc` or `6543`.
+    Sequence,
+    /// The password contains a recent year.
+    RecentYear,
+    /// The password contains a date.
+    ContainsDate,
+    /// The password capitalizes predictably, e.g. only its first letter.
+    Capitalization,
+    /// The password contains a reversed word.
+    ReversedWord,
+    /// The password contains predictable substitutions, like `@` instead of `a`.
+    L33tSubstitution,
+    /// The password is shorter than the configured minimum length.
+    TooShort,
+    /// The password is a word followed by a year or a number, like `summer2023`.
+    WordWithNumberSuffix,
+}
+
+impl Reason {
+    /// The code of the reason in snake case, e.g. `"common_password"`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Reason::CommonPassword => "common_password",
+            Reason::SimilarToCommonPassword => "similar_to_common_password",
+            Reason::CommonWord => "common_word",
+            Reason::Name => "name",
+            Reason::KeyboardWalk => "keyboard_walk",
+            Reason::Repeat => "repeat",
+            Reason::Sequence => "sequence",
+            Reason::RecentYear => "recent_year",
+            Reason::ContainsDate => "contains_date",
+            Reason::Capitalization => "capitalization",
+            Reason::ReversedWord => "reversed_word",
+            Reason::L33tSubstitution => "l33t_substitution",
+            Reason::TooShort => "too_short",
+            Reason::WordWithNumberSuffix => "word_with_number_suffix",
+        }
+    }
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 /// A suggestion helps to choose a better password.
-#[derive(Debug, Copy, Clone, PartialEq)]
+///
+/// Its `Display` implementation gives the English text of the suggestion;
+/// match on the variants to filter or translate suggestions.
+/// More suggestions may be added in minor releases.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ser", derive(serde::Serialize))]
 #[allow(missing_docs)]
+#[non_exhaustive]
 pub enum Suggestion {
     UseAFewWordsAvoidCommonPhrases,
     NoNeedForSymbolsDigitsOrUppercaseLetters,
@@ -85,6 +215,37 @@ pub enum Suggestion {
     AvoidRecentYears,
     AvoidYearsThatAreAssociatedWithYou,
     AvoidDatesAndYearsThatAreAssociatedWithYou,
+    UseAtLeastNCharacters(usize),
+    ThisPasswordIsStrong,
+    AvoidReusingThisPassword,
+}
+
+impl Suggestion {
+    /// The reason of the suggestion, for the suggestions about a specific weakness,
+    /// e.g. [`Reason::TooShort`] for [`Suggestion::UseAtLeastNCharacters`].
+    pub fn reason(self) -> Option<Reason> {
+        match self {
+            Suggestion::CapitalizationDoesntHelpVeryMuch
+            | Suggestion::AllUppercaseIsAlmostAsEasyToGuessAsAllLowercase => {
+                Some(Reason::Capitalization)
+            }
+            Suggestion::ReversedWordsArentMuchHarderToGuess => Some(Reason::ReversedWord),
+            Suggestion::PredictableSubstitutionsDontHelpVeryMuch => Some(Reason::L33tSubstitution),
+            Suggestion::UseALongerKeyboardPatternWithMoreTurns => Some(Reason::KeyboardWalk),
+            Suggestion::AvoidRepeatedWordsAndCharacters => Some(Reason::Repeat),
+            Suggestion::AvoidSequences => Some(Reason::Sequence),
+            Suggestion::AvoidRecentYears | Suggestion::AvoidYearsThatAreAssociatedWithYou => {
+                Some(Reason::RecentYear)
+            }
+            Suggestion::AvoidDatesAndYearsThatAreAssociatedWithYou => Some(Reason::ContainsDate),
+            Suggestion::UseAtLeastNCharacters(_) => Some(Reason::TooShort),
+            Suggestion::UseAFewWordsAvoidCommonPhrases
+            | Suggestion::NoNeedForSymbolsDigitsOrUppercaseLetters
+            | Suggestion::AddAnotherWordOrTwo
+            | Suggestion::ThisPasswordIsStrong
+            | Suggestion::AvoidReusingThisPassword => None,
+        }
+    }
 }
 
 impl fmt::Display for Suggestion {
@@ -127,12 +288,17 @@ impl fmt::Display for Suggestion {
             Suggestion::AvoidDatesAndYearsThatAreAssociatedWithYou => {
                 write!(f, "Avoid dates and years that are associated with you.")
             }
+            Suggestion::UseAtLeastNCharacters(n) => write!(f, "Use at least {} characters.", n),
+            Suggestion::ThisPasswordIsStrong => write!(f, "This password is strong."),
+            Suggestion::AvoidReusingThisPassword => {
+                write!(f, "Avoid reusing this password on other sites.")
+            }
         }
     }
 }
 
 /// Verbal feedback to help choose better passwords
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "ser", derive(serde::Serialize))]
 pub struct Feedback {
     /// Explains what's wrong, e.g. "This is a top-10 common password". Not always set.
@@ -140,9 +306,27 @@ pub struct Feedback {
     /// A possibly-empty list of suggestions to help choose a less guessable password.
     /// E.g. "Add another word or two".
     suggestions: Vec<Suggestion>,
+    /// The rank of the password in the frequency list of common passwords,
+    /// when the whole password is a common password.
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+    common_password_rank: Option<usize>,
+    /// The kind of pattern the warning and suggestions are about, for weak passwords.
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+    primary_weakness: Option<PatternKind>,
 }
 
 impl Feedback {
+    /// Build a `Feedback` by hand, e.g. to unit test code that displays it.
+    #[cfg(feature = "test-util")]
+    pub fn from_parts(warning: Option<Warning>, suggestions: Vec<Suggestion>) -> Feedback {
+        Feedback {
+            warning,
+            suggestions,
+            common_password_rank: None,
+            primary_weakness: None,
+        }
+    }
+
     /// Explains what's wrong, e.g. "This is a top-10 common password". Not always set.
     pub fn warning(&self) -> Option<Warning> {
         self.warning
@@ -153,6 +337,120 @@ impl Feedback {
     pub fn suggestions(&self) -> &[Suggestion] {
         &self.suggestions
     }
+
+    /// Whether there is neither a warning nor any suggestion, i.e. nothing to display.
+    pub fn is_empty(&self) -> bool {
+        self.warning.is_none() && self.suggestions.is_empty()
+    }
+
+    /// The number of suggestions.
+    pub fn suggestion_count(&self) -> usize {
+        self.suggestions.len()
+    }
+
+    /// The reasons of the warning and of the suggestions, without duplicates,
+    /// so that they can be handled without parsing the English text.
+    ///
+    /// # Example
+    /// ```rust
+    /// use zxcvbn::feedback::Reason;
+    /// use zxcvbn::zxcvbn;
+    ///
+    /// let feedback = zxcvbn("Password", &[]).feedback().cloned().unwrap();
+    /// assert_eq!(
+    ///     feedback.reasons(),
+    ///     [Reason::CommonPassword, Reason::Capitalization]
+    /// );
+    /// ```
+    pub fn reasons(&self) -> Vec<Reason> {
+        let mut reasons = Vec::new();
+        let suggested = self.suggestions.iter().filter_map(|s| s.reason());
+        for reason in self
+            .warning
+            .map(Warning::reason)
+            .into_iter()
+            .chain(suggested)
+        {
+            if !reasons.contains(&reason) {
+                reasons.push(reason);
+            }
+        }
+        reasons
+    }
+
+    /// The rank of the password among the common passwords, where 1 is the most common,
+    /// when the warning is about the password being a common one, e.g.
+    /// [`Warning::ThisIsATop100Password`].
+    /// This allows telling users that their password is "among the 100 most common passwords".
+    pub fn common_password_rank(&self) -> Option<usize> {
+        self.common_password_rank
+    }
+
+    /// The kind of pattern which makes the password weak, i.e. the one of its longest match,
+    /// which the warning and suggestions are about. This is `None` for passwords with a score
+    /// of 3 or more, and for the feedback of an empty password.
+    ///
+    /// # Example
+    /// ```rust
+    /// use zxcvbn::matching::patterns::PatternKind;
+    /// use zxcvbn::zxcvbn;
+    ///
+    /// let feedback = zxcvbn("qwertyuiop1", &[]).feedback().cloned().unwrap();
+    /// assert_eq!(feedback.primary_weakness(), Some(PatternKind::Dictionary));
+    /// let feedback = zxcvbn("zxcvfr", &[]).feedback().cloned().unwrap();
+    /// assert_eq!(feedback.primary_weakness(), Some(PatternKind::Spatial));
+    /// ```
+    pub fn primary_weakness(&self) -> Option<PatternKind> {
+        self.primary_weakness
+    }
+
+    /// The single most relevant suggestion to fix the
+    /// [`primary_weakness`](Self::primary_weakness), e.g. to display along with the warning
+    /// when there is no room for every suggestion.
+    pub fn primary_suggestion(&self) -> Option<Suggestion> {
+        let suggestion = match self.primary_weakness? {
+            PatternKind::L33t => Suggestion::PredictableSubstitutionsDontHelpVeryMuch,
+            PatternKind::Reverse => Suggestion::ReversedWordsArentMuchHarderToGuess,
+            PatternKind::Spatial => Suggestion::UseALongerKeyboardPatternWithMoreTurns,
+            PatternKind::Repeat => Suggestion::AvoidRepeatedWordsAndCharacters,
+            PatternKind::Sequence => Suggestion::AvoidSequences,
+            PatternKind::Date => Suggestion::AvoidDatesAndYearsThatAreAssociatedWithYou,
+            // only the recent years have a suggestion among the regex matches
+            PatternKind::Regex if self.suggestions.contains(&Suggestion::AvoidRecentYears) => {
+                Suggestion::AvoidRecentYears
+            }
+            PatternKind::Dictionary | PatternKind::Regex | PatternKind::Bruteforce => {
+                Suggestion::AddAnotherWordOrTwo
+            }
+        };
+        Some(suggestion)
+    }
+
+    /// Combine the feedback of two evaluations, e.g. of the same password against
+    /// several user profiles.
+    ///
+    /// The merged feedback keeps the more severe of both warnings, with its primary weakness,
+    /// or the warning of `self` if both are equally severe. It contains the suggestions of
+    /// `self` followed by those of `other`, without duplicates.
+    pub fn merge(&self, other: &Feedback) -> Feedback {
+        let keep_other = match (self.warning, other.warning) {
+            (Some(a), Some(b)) => b.severity() < a.severity(),
+            (a, _) => a.is_none(),
+        };
+        let kept = if keep_other { other } else { self };
+        let mut suggestions: Vec<Suggestion> = Vec::new();
+        for &suggestion in self.suggestions.iter().chain(&other.suggestions) {
+            if !suggestions.contains(&suggestion) {
+                suggestions.push(suggestion);
+            }
+        }
+        Feedback {
+            warning: kept.warning,
+            suggestions,
+            common_password_rank: kept.common_password_rank,
+            primary_weakness: kept.primary_weakness,
+        }
+    }
 }
 
 impl fmt::Display for Feedback {
@@ -166,7 +464,29 @@ impl fmt::Display for Feedback {
     }
 }
 
-pub(crate) fn get_feedback(score: Score, sequence: &[Match]) -> Option<Feedback> {
+pub(crate) fn get_feedback(
+    score: Score,
+    sequence: &[Match],
+    options: &ZxcvbnOptions,
+) -> Option<Feedback> {
+    let mut feedback = if options.always_feedback && score >= Score::Three {
+        Some(get_strong_password_feedback(sequence))
+    } else {
+        get_score_feedback(score, sequence)
+    };
+    if let Some(min_length) = options.min_length {
+        let password_len = sequence.last().map_or(0, |m| m.j + 1);
+        if password_len < min_length {
+            feedback
+                .get_or_insert_with(Feedback::default)
+                .suggestions
+                .push(Suggestion::UseAtLeastNCharacters(min_length));
+        }
+    }
+    feedback
+}
+
+fn get_score_feedback(score: Score, sequence: &[Match]) -> Option<Feedback> {
     if sequence.is_empty() {
         // default feedback
         return Some(Feedback {
@@ -175,6 +495,8 @@ pub(crate) fn get_feedback(score: Score, sequence: &[Match]) -> Option<Feedback>
                 Suggestion::UseAFewWordsAvoidCommonPhrases,
                 Suggestion::NoNeedForSymbolsDigitsOrUppercaseLetters,
             ],
+            common_password_rank: None,
+            primary_weakness: None,
         });
     }
     if score >= Score::Three {
@@ -186,12 +508,31 @@ pub(crate) fn get_feedback(score: Score, sequence: &[Match]) -> Option<Feedback>
         .max_by_key(|x| x.token.chars().count())
         .unwrap();
     let mut feedback = get_match_feedback(longest_match, sequence.len() == 1);
+    if is_word_with_number_suffix(sequence) {
+        feedback.warning = Some(Warning::AWordFollowedByANumberIsEasyToGuess);
+    }
+    feedback.primary_weakness = Some(longest_match.kind());
     let extra_feedback = Suggestion::AddAnotherWordOrTwo;
 
     feedback.suggestions.insert(0, extra_feedback);
     Some(feedback)
 }
 
+fn get_strong_password_feedback(sequence: &[Match]) -> Feedback {
+    let mut suggestions = vec![Suggestion::ThisPasswordIsStrong];
+    if let Some(longest_match) = sequence.iter().max_by_key(|x| x.token.chars().count()) {
+        let match_feedback = get_match_feedback(longest_match, sequence.len() == 1);
+        suggestions.extend(match_feedback.suggestions);
+    }
+    suggestions.push(Suggestion::AvoidReusingThisPassword);
+    Feedback {
+        warning: None,
+        suggestions,
+        common_password_rank: None,
+        primary_weakness: None,
+    }
+}
+
 fn get_match_feedback(cur_match: &Match, is_sole_match: bool) -> Feedback {
     match cur_match.pattern {
         MatchPattern::Dictionary(ref pattern) => {
@@ -204,6 +545,8 @@ fn get_match_feedback(cur_match: &Match, is_sole_match: bool) -> Feedback {
                 Warning::ShortKeyboardPatternsAreEasyToGuess
             }),
             suggestions: vec![Suggestion::UseALongerKeyboardPatternWithMoreTurns],
+            common_password_rank: None,
+            primary_weakness: None,
         },
         MatchPattern::Repeat(ref pattern) => Feedback {
             warning: Some(if pattern.base_token.chars().count() == 1 {
@@ -212,10 +555,14 @@ fn get_match_feedback(cur_match: &Match, is_sole_match: bool) -> Feedback {
                 Warning::RepeatsLikeAbcAbcAreOnlySlightlyHarderToGuess
             }),
             suggestions: vec![Suggestion::AvoidRepeatedWordsAndCharacters],
+            common_password_rank: None,
+            primary_weakness: None,
         },
         MatchPattern::Sequence(_) => Feedback {
             warning: Some(Warning::SequencesLikeAbcAreEasyToGuess),
             suggestions: vec![Suggestion::AvoidSequences],
+            common_password_rank: None,
+            primary_weakness: None,
         },
         MatchPattern::Regex(ref pattern) => {
             if pattern.regex_name == "recent_year" {
@@ -225,6 +572,8 @@ fn get_match_feedback(cur_match: &Match, is_sole_match: bool) -> Feedback {
                         Suggestion::AvoidRecentYears,
                         Suggestion::AvoidYearsThatAreAssociatedWithYou,
                     ],
+                    common_password_rank: None,
+                    primary_weakness: None,
                 }
             } else {
                 Feedback::default()
@@ -233,11 +582,10 @@ fn get_match_feedback(cur_match: &Match, is_sole_match: bool) -> Feedback {
         MatchPattern::Date(_) => Feedback {
             warning: Some(Warning::DatesAreOftenEasyToGuess),
             suggestions: vec![Suggestion::AvoidDatesAndYearsThatAreAssociatedWithYou],
+            common_password_rank: None,
+            primary_weakness: None,
         },
-        _ => Feedback {
-            warning: None,
-            suggestions: vec![],
-        },
+        _ => Feedback::default(),
     }
 }
 
@@ -295,9 +643,23 @@ fn get_dictionary_match_feedback(
         suggestions.push(Suggestion::PredictableSubstitutionsDontHelpVeryMuch);
     }
 
+    let is_common_password = matches!(
+        warning,
+        Some(
+            Warning::ThisIsATop10Password
+                | Warning::ThisIsATop100Password
+                | Warning::ThisIsACommonPassword
+        )
+    );
     Feedback {
         warning,
         suggestions,
+        common_password_rank: if is_common_password {
+            Some(pattern.rank)
+        } else {
+            None
+        },
+        primary_weakness: None,
     }
 }
 
@@ -335,15 +697,310 @@ mod tests {
         );
     }
 
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_feedback_reasons() {
+        use crate::{zxcvbn, zxcvbn_with_options};
+
+        let reasons = |password: &str| zxcvbn(password, &[]).feedback.unwrap().reasons();
+        assert_eq!(reasons("password"), [Reason::CommonPassword]);
+        assert_eq!(reasons("qwertyuiop"), [Reason::CommonPassword]);
+        assert_eq!(reasons("zxcvfr"), [Reason::KeyboardWalk]);
+        assert_eq!(reasons("13/05/1987"), [Reason::ContainsDate]);
+        assert_eq!(reasons("aaaaaaaa"), [Reason::Repeat]);
+        assert_eq!(
+            reasons("drowssap"),
+            [Reason::SimilarToCommonPassword, Reason::ReversedWord]
+        );
+
+        let options = ZxcvbnOptions::new().min_length(30);
+        let feedback = zxcvbn_with_options("correcthorsebatterystaple", &[], &options)
+            .feedback
+            .unwrap();
+        assert_eq!(feedback.reasons(), [Reason::TooShort]);
+        assert_eq!(Reason::TooShort.to_string(), "too_short");
+        assert_eq!(Feedback::default().reasons(), []);
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_primary_weakness() {
+        use crate::{zxcvbn, zxcvbn_with_options};
+
+        let feedback_of = |password: &str| zxcvbn(password, &[]).feedback.unwrap();
+        for (password, kind, suggestion) in [
+            (
+                "password",
+                PatternKind::Dictionary,
+                Suggestion::AddAnotherWordOrTwo,
+            ),
+            (
+                "p4ssw0rd",
+                PatternKind::L33t,
+                Suggestion::PredictableSubstitutionsDontHelpVeryMuch,
+            ),
+            (
+                "zxcvfr",
+                PatternKind::Spatial,
+                Suggestion::UseALongerKeyboardPatternWithMoreTurns,
+            ),
+            (
+                "aaaaaaaa",
+                PatternKind::Repeat,
+                Suggestion::AvoidRepeatedWordsAndCharacters,
+            ),
+            ("abcdefg", PatternKind::Sequence, Suggestion::AvoidSequences),
+            (
+                "13/05/1987",
+                PatternKind::Date,
+                Suggestion::AvoidDatesAndYearsThatAreAssociatedWithYou,
+            ),
+            ("1987", PatternKind::Regex, Suggestion::AvoidRecentYears),
+        ] {
+            let feedback = feedback_of(password);
+            assert_eq!(feedback.primary_weakness(), Some(kind), "{}", password);
+            assert_eq!(
+                feedback.primary_suggestion(),
+                Some(suggestion),
+                "{}",
+                password
+            );
+            assert!(feedback.suggestions().contains(&suggestion), "{}", password);
+        }
+
+        // strong passwords have no weakness, even with feedback
+        let options = ZxcvbnOptions::new().always_feedback(true);
+        let strong = zxcvbn_with_options("correcthorsebatterystaple", &[], &options);
+        assert!(u8::from(strong.score()) >= 3);
+        let feedback = strong.feedback.unwrap();
+        assert_eq!(feedback.primary_weakness(), None);
+        assert_eq!(feedback.primary_suggestion(), None);
+        assert_eq!(zxcvbn("", &[]).feedback.unwrap().primary_weakness(), None);
+
+        let merged = Feedback::default().merge(&feedback_of("zxcvfr"));
+        assert_eq!(merged.primary_weakness(), Some(PatternKind::Spatial));
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_word_with_number_suffix_feedback() {
+        use crate::zxcvbn;
+
+        for password in ["summer2023", "john1985", "dragon007", "Summer2023"] {
+            let feedback = zxcvbn(password, &[]).feedback.unwrap();
+            assert_eq!(
+                feedback.warning(),
+                Some(Warning::AWordFollowedByANumberIsEasyToGuess),
+                "{}",
+                password
+            );
+            assert_eq!(feedback.reasons()[0], Reason::WordWithNumberSuffix);
+        }
+        // more digits, digits first, or a common password ending with digits
+        for password in ["summer20235", "1985john", "monkey12"] {
+            let feedback = zxcvbn(password, &[]).feedback.unwrap();
+            assert_ne!(
+                feedback.warning(),
+                Some(Warning::AWordFollowedByANumberIsEasyToGuess),
+                "{}",
+                password
+            );
+        }
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_common_password_rank() {
+        use crate::frequency_lists::rank_in_list;
+        use crate::zxcvbn;
+
+        let feedback = zxcvbn("password", &[]).feedback.unwrap();
+        assert_eq!(feedback.common_password_rank(), Some(2));
+
+        let feedback = zxcvbn("test", &[]).feedback.unwrap();
+        assert_eq!(feedback.warning(), Some(Warning::ThisIsATop100Password));
+        assert_eq!(
+            feedback.common_password_rank(),
+            rank_in_list("test", "passwords")
+        );
+
+        // only set when the whole password is a common password
+        assert_eq!(
+            zxcvbn("p4ssw0rd", &[])
+                .feedback
+                .unwrap()
+                .common_password_rank(),
+            None
+        );
+        assert_eq!(
+            zxcvbn("password1983", &[])
+                .feedback
+                .unwrap()
+                .common_password_rank(),
+            None
+        );
+
+        let merged = Feedback::default().merge(&zxcvbn("password", &[]).feedback.unwrap());
+        assert_eq!(merged.common_password_rank(), Some(2));
+    }
+
+    #[test]
+    fn test_is_empty_and_suggestion_count() {
+        let none = Feedback::default();
+        assert!(none.is_empty());
+        assert_eq!(none.suggestion_count(), 0);
+
+        let warning_only = Feedback {
+            warning: Some(Warning::DatesAreOftenEasyToGuess),
+            ..Feedback::default()
+        };
+        assert!(!warning_only.is_empty());
+        assert_eq!(warning_only.suggestion_count(), 0);
+
+        let suggestions_only = Feedback {
+            suggestions: vec![
+                Suggestion::AddAnotherWordOrTwo,
+                Suggestion::CapitalizationDoesntHelpVeryMuch,
+            ],
+            ..Feedback::default()
+        };
+        assert!(!suggestions_only.is_empty());
+        assert_eq!(suggestions_only.suggestion_count(), 2);
+    }
+
+    #[test]
+    fn test_merge_feedback() {
+        let none = Feedback::default();
+        let common = Feedback {
+            warning: Some(Warning::ThisIsACommonPassword),
+            suggestions: vec![
+                Suggestion::AddAnotherWordOrTwo,
+                Suggestion::CapitalizationDoesntHelpVeryMuch,
+            ],
+            common_password_rank: None,
+            primary_weakness: None,
+        };
+        let date = Feedback {
+            warning: Some(Warning::DatesAreOftenEasyToGuess),
+            suggestions: vec![
+                Suggestion::AddAnotherWordOrTwo,
+                Suggestion::AvoidDatesAndYearsThatAreAssociatedWithYou,
+            ],
+            common_password_rank: None,
+            primary_weakness: None,
+        };
+
+        let merged = none.merge(&none);
+        assert_eq!(merged.warning, None);
+        assert!(merged.suggestions.is_empty());
+
+        let merged = none.merge(&date);
+        assert_eq!(merged.warning, Some(Warning::DatesAreOftenEasyToGuess));
+        assert_eq!(merged.suggestions, date.suggestions);
+        let merged = date.merge(&none);
+        assert_eq!(merged.warning, Some(Warning::DatesAreOftenEasyToGuess));
+        assert_eq!(merged.suggestions, date.suggestions);
+
+        let expected_suggestions = vec![
+            Suggestion::AddAnotherWordOrTwo,
+            Suggestion::AvoidDatesAndYearsThatAreAssociatedWithYou,
+            Suggestion::CapitalizationDoesntHelpVeryMuch,
+        ];
+        let merged = date.merge(&common);
+        assert_eq!(merged.warning, Some(Warning::ThisIsACommonPassword));
+        assert_eq!(merged.suggestions, expected_suggestions);
+        let merged = common.merge(&date);
+        assert_eq!(merged.warning, Some(Warning::ThisIsACommonPassword));
+        assert_eq!(
+            merged.suggestions,
+            vec![
+                Suggestion::AddAnotherWordOrTwo,
+                Suggestion::CapitalizationDoesntHelpVeryMuch,
+                Suggestion::AvoidDatesAndYearsThatAreAssociatedWithYou,
+            ]
+        );
+
+        let merged = common.merge(&common);
+        assert_eq!(merged.warning, common.warning);
+        assert_eq!(merged.suggestions, common.suggestions);
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_min_length_feedback() {
+        use crate::{zxcvbn, zxcvbn_with_options};
+
+        let password = "TestMeNow!";
+        assert!(zxcvbn(password, &[]).feedback.is_none());
+
+        let options = ZxcvbnOptions::new().min_length(12);
+        let entropy = zxcvbn_with_options(password, &[], &options);
+        assert_eq!(entropy.score, Score::Three);
+        let feedback = entropy.feedback.unwrap();
+        assert_eq!(feedback.warning, None);
+        assert_eq!(
+            feedback.suggestions,
+            vec![Suggestion::UseAtLeastNCharacters(12)]
+        );
+
+        let entropy = zxcvbn_with_options("password", &[], &options);
+        let feedback = entropy.feedback.unwrap();
+        assert_eq!(feedback.warning, Some(Warning::ThisIsATop10Password));
+        assert_eq!(
+            feedback.suggestions.last(),
+            Some(&Suggestion::UseAtLeastNCharacters(12))
+        );
+
+        let options = ZxcvbnOptions::new().min_length(10);
+        let entropy = zxcvbn_with_options(password, &[], &options);
+        assert!(entropy.feedback.is_none());
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_always_feedback() {
+        use crate::zxcvbn_with_options;
+
+        let options = ZxcvbnOptions::new().always_feedback(true);
+        let entropy = zxcvbn_with_options("r0sebudmaelstrom11/20/91aaaa", &[], &options);
+        assert_eq!(entropy.score, Score::Four);
+        let feedback = entropy.feedback.unwrap();
+        assert_eq!(feedback.warning, None);
+        assert_eq!(
+            feedback.suggestions.first(),
+            Some(&Suggestion::ThisPasswordIsStrong)
+        );
+        assert_eq!(
+            feedback.suggestions.last(),
+            Some(&Suggestion::AvoidReusingThisPassword)
+        );
+
+        // weak passwords receive the regular feedback
+        let entropy = zxcvbn_with_options("password", &[], &options);
+        assert_eq!(
+            entropy.feedback.unwrap().warning,
+            Some(Warning::ThisIsATop10Password)
+        );
+    }
+
     #[test]
     fn test_feedback_display() {
         let feedback = Feedback {
             warning: Some(Warning::ThisIsATop10Password),
             suggestions: vec![Suggestion::UseAFewWordsAvoidCommonPhrases],
+            common_password_rank: None,
+            primary_weakness: None,
         };
         assert_eq!(
             format!("{}", feedback),
             "This is a top-10 common password. Use a few words, avoid common phrases."
         );
     }
+
+    #[test]
+    fn test_warning_message() {
+        let warning = Warning::RepeatsLikeAaaAreEasyToGuess;
+        assert_eq!(warning.message(), "Repeats like \"aaa\" are easy to guess.");
+        assert_eq!(warning.to_string(), warning.message());
+    }
 }
@@ -0,0 +1,101 @@
+//! A C interface to `zxcvbn`, for use from C, Python (`ctypes`) and other languages.
+//!
+//! The declarations are available in `zxcvbn.h` at the root of the crate.
+//! Build a shared or static library with the `ffi` feature, e.g. with
+//! `cargo rustc --release --features ffi --crate-type cdylib`.
+//!
+//! # Example
+//! ```c
+//! #include "zxcvbn.h"
+//!
+//! const char *inputs[] = {"jsmith"};
+//! ZxcvbnResult *result = zxcvbn_ffi("correcthorsebatterystaple", inputs, 1);
+//! if (result != NULL) {
+//!     printf("score: %u\n", result->score);
+//!     zxcvbn_free(result);
+//! }
+//! ```
+
+use std::ffi::CStr;
+use std::os::raw::c_char;
+use std::panic;
+
+use crate::zxcvbn;
+
+/// The result of evaluating a password through [`zxcvbn_ffi`].
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ZxcvbnResult {
+    /// Overall strength score from 0-4.
+    pub score: u8,
+    /// Estimated guesses needed to crack the password.
+    pub guesses: u64,
+    /// Seconds needed to crack the password in an online attack on a service
+    /// that doesn't rate-limit, at 10 guesses per second.
+    pub crack_time_online_seconds: f64,
+}
+
+/// Evaluates the NUL-terminated UTF-8 `password`, with the `n_inputs` NUL-terminated UTF-8
+/// strings of `user_inputs` as user-supplied inputs.
+///
+/// Returns a result which must be freed with [`zxcvbn_free`], or NULL if `password`,
+/// `user_inputs` or one of the inputs is NULL or not valid UTF-8.
+/// `user_inputs` may be NULL if `n_inputs` is 0.
+///
+/// # Safety
+/// `password` and the first `n_inputs` pointers of `user_inputs` must be NULL or point to
+/// NUL-terminated strings, and `user_inputs` must be NULL or point to `n_inputs` pointers.
+#[no_mangle]
+pub unsafe extern "C" fn zxcvbn_ffi(
+    password: *const c_char,
+    user_inputs: *const *const c_char,
+    n_inputs: usize,
+) -> *mut ZxcvbnResult {
+    let password = match to_str(password) {
+        Some(password) => password,
+        None => return std::ptr::null_mut(),
+    };
+    let mut inputs = Vec::with_capacity(n_inputs);
+    if n_inputs > 0 {
+        if user_inputs.is_null() {
+            return std::ptr::null_mut();
+        }
+        for input in std::slice::from_raw_parts(user_inputs, n_inputs) {
+            match to_str(*input) {
+                Some(input) => inputs.push(input),
+                None => return std::ptr::null_mut(),
+            }
+        }
+    }
+    // unwinding into C is undefined behavior
+    let entropy = match panic::catch_unwind(|| zxcvbn(password, &inputs)) {
+        Ok(entropy) => entropy,
+        Err(_) => return std::ptr::null_mut(),
+    };
+    Box::into_raw(Box::new(ZxcvbnResult {
+        score: entropy.score().into(),
+        guesses: entropy.guesses(),
+        crack_time_online_seconds: entropy
+            .crack_times()
+            .online_no_throttling_10_per_second()
+            .as_secs_f64(),
+    }))
+}
+
+/// Frees a result returned by [`zxcvbn_ffi`]. Does nothing if `result` is NULL.
+///
+/// # Safety
+/// `result` must be NULL or a result returned by `zxcvbn_ffi` which has not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn zxcvbn_free(result: *mut ZxcvbnResult) {
+    if !result.is_null() {
+        drop(Box::from_raw(result));
+    }
+}
+
+unsafe fn to_str<'a>(s: *const c_char) -> Option<&'a str> {
+    if s.is_null() {
+        return None;
+    }
+    CStr::from_ptr(s).to_str().ok()
+}
@@ -0,0 +1,166 @@
+//! Contains structs and methods for generating random passwords
+//! that are guaranteed to reach a minimum strength score.
+//!
+//! # Example
+//! ```rust
+//! # use std::error::Error;
+//! #
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use zxcvbn::generator::{Charset, PasswordGenerator};
+//! use zxcvbn::{zxcvbn, Score};
+//!
+//! let generator = PasswordGenerator::new(Score::Four, Charset::Alphanumeric);
+//! let password = generator.generate()?;
+//! assert!(zxcvbn(&password, &[]).score() >= Score::Four);
+//! #
+//! #     Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+
+use rand::rand_core::UnwrapErr;
+use rand::rngs::SysRng;
+use rand::RngExt;
+
+use crate::{zxcvbn, Score};
+
+/// The maximum number of passwords generated before giving up on reaching the minimum score.
+const MAX_RETRIES: usize = 1000;
+const DEFAULT_LENGTH: usize = 12;
+
+const LOWERCASE_ALPHA: &str = "abcdefghijklmnopqrstuvwxyz";
+const ALPHANUMERIC: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+const ALPHANUMERIC_SYMBOL: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789\
+                                   !\"#$%&'()*+,-./:;<=>?@[\\]^_`{|}~";
+
+/// The set of characters a generated password is made of.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Charset {
+    /// Lowercase latin letters.
+    LowercaseAlpha,
+    /// Lowercase and uppercase latin letters and digits.
+    Alphanumeric,
+    /// Lowercase and uppercase latin letters, digits and ASCII symbols.
+    AlphanumericSymbol,
+    /// A custom set of characters.
+    Custom(String),
+}
+
+impl Charset {
+    fn chars(&self) -> Vec<char> {
+        match self {
+            Charset::LowercaseAlpha => LOWERCASE_ALPHA.chars().collect(),
+            Charset::Alphanumeric => ALPHANUMERIC.chars().collect(),
+            Charset::AlphanumericSymbol => ALPHANUMERIC_SYMBOL.chars().collect(),
+            Charset::Custom(chars) => chars.chars().collect(),
+        }
+    }
+}
+
+/// An error that occurred while generating a password.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeneratorError {
+    /// The charset does not contain any characters.
+    EmptyCharset,
+    /// No password reaching the minimum score was generated within the retry limit.
+    TooManyRetries,
+}
+
+impl fmt::Display for GeneratorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GeneratorError::EmptyCharset => {
+                write!(f, "The charset does not contain any characters")
+            }
+            GeneratorError::TooManyRetries => write!(
+                f,
+                "No password reaching the minimum score was generated after {} attempts",
+                MAX_RETRIES
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GeneratorError {}
+
+/// Generates random passwords that reach a minimum `Score`.
+///
+/// Passwords are generated using the operating system's random number generator.
+#[derive(Debug, Clone)]
+pub struct PasswordGenerator {
+    min_score: Score,
+    charset: Charset,
+    length: usize,
+}
+
+impl PasswordGenerator {
+    /// Create a generator for passwords of 12 characters from `charset`
+    /// which reach at least `min_score`.
+    pub fn new(min_score: Score, charset: Charset) -> Self {
+        PasswordGenerator {
+            min_score,
+            charset,
+            length: DEFAULT_LENGTH,
+        }
+    }
+
+    /// Set the number of characters of the generated passwords.
+    pub fn length(mut self, length: usize) -> Self {
+        self.length = length;
+        self
+    }
+
+    /// Generate a random password, retrying until it reaches the minimum score.
+    ///
+    /// Returns `GeneratorError::TooManyRetries` if no password reached the minimum score
+    /// after 1000 attempts, e.g. because the length or the charset is too small.
+    pub fn generate(&self) -> Result<String, GeneratorError> {
+        let chars = self.charset.chars();
+        if chars.is_empty() {
+            return Err(GeneratorError::EmptyCharset);
+        }
+        let mut rng = UnwrapErr(SysRng);
+        for _ in 0..MAX_RETRIES {
+            let password = (0..self.length)
+                .map(|_| chars[rng.random_range(0..chars.len())])
+                .collect::<String>();
+            if zxcvbn(&password, &[]).score() >= self.min_score {
+                return Ok(password);
+            }
+        }
+        Err(GeneratorError::TooManyRetries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_reaches_min_score() {
+        let generator = PasswordGenerator::new(Score::Four, Charset::AlphanumericSymbol);
+        let password = generator.generate().unwrap();
+        assert_eq!(password.chars().count(), DEFAULT_LENGTH);
+        assert!(password.chars().all(|c| ALPHANUMERIC_SYMBOL.contains(c)));
+        assert!(zxcvbn(&password, &[]).score() >= Score::Four);
+    }
+
+    #[test]
+    fn test_generate_custom_charset_and_length() {
+        let generator =
+            PasswordGenerator::new(Score::Three, Charset::Custom("xyz123".to_string())).length(20);
+        let password = generator.generate().unwrap();
+        assert_eq!(password.chars().count(), 20);
+        assert!(password.chars().all(|c| "xyz123".contains(c)));
+    }
+
+    #[test]
+    fn test_generate_errors() {
+        let generator = PasswordGenerator::new(Score::Four, Charset::Custom("a".to_string()));
+        assert_eq!(generator.generate(), Err(GeneratorError::TooManyRetries));
+
+        let generator = PasswordGenerator::new(Score::Zero, Charset::Custom(String::new()));
+        assert_eq!(generator.generate(), Err(GeneratorError::EmptyCharset));
+    }
+}
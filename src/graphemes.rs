@@ -0,0 +1,143 @@
+//! Truncation of passwords which doesn't split grapheme clusters, i.e. what users see as
+//! a single character, like a letter and its combining accents or an emoji family.
+//!
+//! Only the clusters made of combining marks, variation selectors, emoji modifiers and
+//! zero-width joiners, flags, and Devanagari conjuncts are recognized, rather than every
+//! rule of Unicode text segmentation.
+
+const ZERO_WIDTH_JOINER: char = '\u{200D}';
+const DEVANAGARI_VIRAMA: char = '\u{094D}';
+/// The most characters a cluster is extended by, so that a run of combining marks can't
+/// defeat the truncation.
+const MAX_CLUSTER_EXTENSION: usize = 32;
+
+/// The characters which extend the cluster of the character before them.
+const EXTENDING_RANGES: &[(char, char)] = &[
+    // combining diacritical marks
+    ('\u{0300}', '\u{036F}'),
+    // Devanagari vowel signs, nukta and virama
+    ('\u{0900}', '\u{0903}'),
+    ('\u{093A}', '\u{093C}'),
+    ('\u{093E}', '\u{094F}'),
+    ('\u{0951}', '\u{0957}'),
+    ('\u{0962}', '\u{0963}'),
+    // combining diacritical marks extended, supplement and for symbols
+    ('\u{1AB0}', '\u{1AFF}'),
+    ('\u{1DC0}', '\u{1DFF}'),
+    ('\u{20D0}', '\u{20FF}'),
+    // zero-width non-joiner and joiner
+    ('\u{200C}', '\u{200D}'),
+    // variation selectors
+    ('\u{FE00}', '\u{FE0F}'),
+    // combining half marks
+    ('\u{FE20}', '\u{FE2F}'),
+    // emoji skin tone modifiers
+    ('\u{1F3FB}', '\u{1F3FF}'),
+    // tags of subdivision flags
+    ('\u{E0020}', '\u{E007F}'),
+    // variation selectors supplement
+    ('\u{E0100}', '\u{E01EF}'),
+];
+
+/// The prefix of `password` made of its first `max_chars` characters, extended to the end
+/// of the grapheme cluster of the last of them.
+pub(crate) fn truncate(password: &str, max_chars: usize) -> &str {
+    let mut previous = None;
+    let mut regional_indicators = 0;
+    for (count, (index, c)) in password.char_indices().enumerate() {
+        if count >= max_chars
+            && (count >= max_chars + MAX_CLUSTER_EXTENSION
+                || !continues_cluster(previous, c, regional_indicators))
+        {
+            return &password[..index];
+        }
+        regional_indicators = if is_regional_indicator(c) {
+            regional_indicators + 1
+        } else {
+            0
+        };
+        previous = Some(c);
+    }
+    password
+}
+
+/// Whether `c` belongs to the cluster of `previous`, which ends a run of
+/// `regional_indicators` regional indicators.
+fn continues_cluster(previous: Option<char>, c: char, regional_indicators: usize) -> bool {
+    let previous = match previous {
+        Some(previous) => previous,
+        None => return false,
+    };
+    is_extending(c)
+        // emoji joined by a zero-width joiner, e.g. the members of a family
+        || previous == ZERO_WIDTH_JOINER
+        // consonants joined by a virama into a conjunct
+        || (previous == DEVANAGARI_VIRAMA && is_devanagari_consonant(c))
+        // flags are pairs of regional indicators
+        || (is_regional_indicator(c) && regional_indicators % 2 == 1)
+}
+
+fn is_extending(c: char) -> bool {
+    EXTENDING_RANGES
+        .iter()
+        .any(|&(first, last)| first <= c && c <= last)
+}
+
+fn is_devanagari_consonant(c: char) -> bool {
+    matches!(c, '\u{0915}'..='\u{0939}' | '\u{0958}'..='\u{095F}' | '\u{0978}'..='\u{097F}')
+}
+
+fn is_regional_indicator(c: char) -> bool {
+    ('\u{1F1E6}'..='\u{1F1FF}').contains(&c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_ascii() {
+        assert_eq!(truncate("password", 4), "pass");
+        assert_eq!(truncate("password", 8), "password");
+        assert_eq!(truncate("password", 100), "password");
+        assert_eq!(truncate("", 100), "");
+    }
+
+    #[test]
+    fn test_truncate_combining_marks() {
+        // "e" followed by a combining acute accent
+        assert_eq!(truncate("cafe\u{301}s", 4), "cafe\u{301}");
+        assert_eq!(truncate("a\u{301}\u{323}b", 1), "a\u{301}\u{323}");
+    }
+
+    #[test]
+    fn test_truncate_emoji() {
+        let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}\u{200D}\u{1F466}";
+        assert_eq!(truncate(&format!("{}{}", family, family), 1), family);
+        let waving = "\u{1F44B}\u{1F3FD}";
+        assert_eq!(truncate(&format!("{}!", waving), 1), waving);
+        let flags = "\u{1F1EB}\u{1F1F7}\u{1F1EF}\u{1F1F5}";
+        assert_eq!(truncate(flags, 1), "\u{1F1EB}\u{1F1F7}");
+        assert_eq!(truncate(flags, 3), flags);
+    }
+
+    #[test]
+    fn test_truncate_devanagari() {
+        // "kshatriya": the conjuncts क्ष and त्रि
+        let word = "\u{915}\u{94D}\u{937}\u{924}\u{94D}\u{930}\u{93F}\u{92F}";
+        assert_eq!(truncate(word, 1), "\u{915}\u{94D}\u{937}");
+        assert_eq!(
+            truncate(word, 4),
+            "\u{915}\u{94D}\u{937}\u{924}\u{94D}\u{930}\u{93F}"
+        );
+    }
+
+    #[test]
+    fn test_truncate_bounds_the_extension() {
+        let password = format!("a{}", "\u{301}".repeat(100));
+        assert_eq!(
+            truncate(&password, 1).chars().count(),
+            1 + MAX_CLUSTER_EXTENSION
+        );
+    }
+}
@@ -1,7 +1,7 @@
 #![doc = include_str!("../README.md")]
 #![recursion_limit = "128"]
 #![warn(missing_docs)]
-#![forbid(unsafe_code)]
+#![deny(unsafe_code)]
 
 #[macro_use]
 #[cfg(feature = "builder")]
@@ -10,6 +10,10 @@ extern crate derive_builder;
 #[macro_use]
 extern crate lazy_static;
 
+use std::borrow::Cow;
+use std::cmp;
+use std::collections::HashMap;
+use std::str::Utf8Error;
 use std::time::Duration;
 
 #[cfg(test)]
@@ -22,14 +26,31 @@ use time_estimates::CrackTimes;
 use wasm_bindgen::prelude::wasm_bindgen;
 
 pub use crate::matching::Match;
+pub use crate::nist::zxcvbn_nist;
+pub use crate::options::{ByteDecoding, ZxcvbnOptions};
 
-mod adjacency_graphs;
+use crate::frequency_lists::RankedDictionary;
+
+pub mod adjacency_graphs;
+mod explain;
 pub mod feedback;
-mod frequency_lists;
+#[cfg(feature = "ffi")]
+#[allow(unsafe_code)]
+pub mod ffi;
+pub mod frequency_lists;
+#[cfg(feature = "generator")]
+pub mod generator;
+mod graphemes;
 /// Defines structures for matches found in a password
 pub mod matching;
-mod scoring;
+pub mod nist;
+mod options;
+pub mod password_policy;
+pub mod scoring;
 pub mod time_estimates;
+#[cfg(feature = "zeroize")]
+#[allow(unsafe_code)]
+mod zeroizing;
 
 #[cfg(not(target_arch = "wasm32"))]
 fn time_scoped<F, R>(f: F) -> (R, Duration)
@@ -62,7 +83,11 @@ where
 }
 
 /// Contains the results of an entropy calculation
-#[derive(Debug, Clone)]
+///
+/// Comparing entropies with `==` also compares their calculation times, which differ between
+/// runs; use [`Entropy::eq_ignoring_calc_time`] or [`Entropy::with_calc_time_zeroed`]
+/// to compare the results themselves.
+#[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "ser", derive(serde::Serialize))]
 pub struct Entropy {
     /// Estimated guesses needed to crack the password
@@ -74,86 +99,771 @@ pub struct Entropy {
     /// Overall strength score from 0-4.
     /// Any score less than 3 should be considered too weak.
     score: Score,
-    /// Verbal feedback to help choose better passwords. Set when `score` <= 2.
+    /// Verbal feedback to help choose better passwords. Set when `score` <= 2,
+    /// or when requested through `ZxcvbnOptions`.
     feedback: Option<feedback::Feedback>,
     /// The list of patterns the guess calculation was based on
     sequence: Vec<Match>,
     /// How long it took to calculate the answer.
     calc_time: Duration,
+    /// The number of characters of the password that was evaluated.
+    password_length: usize,
+    /// The password that was evaluated, truncated like the evaluation itself.
+    #[cfg(feature = "store_password")]
+    #[cfg_attr(feature = "ser", serde(skip))]
+    password: String,
 }
 
 impl Entropy {
+    /// Build an `Entropy` by hand, e.g. to unit test code that consumes evaluation results
+    /// without running the full evaluation.
+    ///
+    /// `guesses_log10` and the crack times are derived from `guesses`, and the calculation
+    /// time is zero. The password length is the end of the last match of `sequence`.
+    /// With the `store_password` feature, the stored password is empty.
+    ///
+    /// # Example
+    /// ```rust
+    /// use zxcvbn::{Entropy, Score};
+    ///
+    /// let entropy = Entropy::from_parts(1_000, Score::Zero, None, Vec::new());
+    /// assert_eq!(entropy.guesses_log10(), 3.0);
+    /// ```
+    #[cfg(feature = "test-util")]
+    pub fn from_parts(
+        guesses: u64,
+        score: Score,
+        feedback: Option<feedback::Feedback>,
+        sequence: Vec<Match>,
+    ) -> Entropy {
+        Entropy {
+            guesses,
+            guesses_log10: (guesses as f64).log10(),
+            crack_times: CrackTimes::new(guesses),
+            score,
+            feedback,
+            password_length: sequence.last().map_or(0, |m| m.j + 1),
+            sequence,
+            calc_time: Duration::from_secs(0),
+            #[cfg(feature = "store_password")]
+            password: String::new(),
+        }
+    }
+
     /// The estimated number of guesses needed to crack the password.
+    ///
+    /// This is the exact result of the model, not rounded: the guesses of the matches are
+    /// multiplied and added as integers, saturating at `u64::MAX`. Only the guesses of
+    /// bruteforce matches with [`ZxcvbnOptions::allowed_character_classes`] or
+    /// [`ZxcvbnOptions::unicode_bruteforce_cardinality`] are computed
+    /// as floating-point numbers, rounded to the nearest integer. Since the model is an
+    /// approximation, prefer [`guesses_rounded`](Self::guesses_rounded) for display.
     pub fn guesses(&self) -> u64 {
         self.guesses
     }
 
+    /// The estimated number of guesses, rounded half up to `significant_figures`
+    /// significant figures, e.g. 370,000,000 instead of 372,010,000 with 2 significant figures.
+    /// This saturates at `u64::MAX`, and 0 significant figures are treated as 1.
+    ///
+    /// # Example
+    /// ```rust
+    /// use zxcvbn::zxcvbn;
+    ///
+    /// let entropy = zxcvbn("TestMeNow!", &[]);
+    /// assert_eq!(entropy.guesses(), 372_010_000);
+    /// assert_eq!(entropy.guesses_rounded(2), 370_000_000);
+    /// ```
+    pub fn guesses_rounded(&self, significant_figures: u32) -> u64 {
+        round_to_significant_figures(self.guesses, significant_figures)
+    }
+
     /// The order of magnitude of `guesses`.
     pub fn guesses_log10(&self) -> f64 {
         self.guesses_log10
     }
 
+    /// The entropy of the password in bits, i.e. the base-2 logarithm of `guesses`.
+    /// Like `guesses_log10`, this is negative infinity for an empty password.
+    pub fn bits(&self) -> f64 {
+        (self.guesses as f64).log2()
+    }
+
+    /// Whether the password takes more than 10 times as many guesses as the one of `other`,
+    /// e.g. to require that a new password be meaningfully stronger than the old one.
+    ///
+    /// # Example
+    /// ```rust
+    /// use zxcvbn::zxcvbn;
+    ///
+    /// let old = zxcvbn("password1", &[]);
+    /// assert!(zxcvbn("correct horse battery staple", &[]).is_stronger_than(&old));
+    /// assert!(!zxcvbn("Password1", &[]).is_stronger_than(&old));
+    /// ```
+    pub fn is_stronger_than(&self, other: &Entropy) -> bool {
+        self.guesses > other.guesses.saturating_mul(10)
+    }
+
+    /// How many orders of magnitude more guesses the password takes than the one of `other`,
+    /// or 0 if it takes fewer guesses.
+    pub fn orders_of_magnitude_stronger_than(&self, other: &Entropy) -> f64 {
+        // the NaN of two empty passwords is also mapped to 0
+        (self.guesses_log10 - other.guesses_log10).max(0.0)
+    }
+
     /// List of back-of-the-envelope crack time estimations based on a few scenarios.
     pub fn crack_times(&self) -> time_estimates::CrackTimes {
         self.crack_times
     }
 
+    /// The time needed to crack the password in `scenario`, truncated to whole seconds.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::time::Duration;
+    /// use zxcvbn::time_estimates::ScenarioName;
+    /// use zxcvbn::zxcvbn;
+    ///
+    /// let entropy = zxcvbn("password123", &[]);
+    /// let time = entropy.crack_time_for_scenario(ScenarioName::OnlineThrottling100PerHour);
+    /// assert_eq!(time, Duration::from_secs(36 * entropy.guesses()));
+    /// ```
+    pub fn crack_time_for_scenario(&self, scenario: time_estimates::ScenarioName) -> Duration {
+        self.crack_times.for_scenario(scenario)
+    }
+
     /// Overall strength score from 0-4.
     /// Any score less than 3 should be considered too weak.
     pub fn score(&self) -> Score {
         self.score
     }
 
-    /// Feedback to help choose better passwords. Set when `score` <= 2.
+    /// Feedback to help choose better passwords. Set when `score` <= 2,
+    /// or when requested through `ZxcvbnOptions`.
     pub fn feedback(&self) -> Option<&feedback::Feedback> {
         self.feedback.as_ref()
     }
 
+    /// Re-derives the feedback from the current `score` and `sequence`.
+    ///
+    /// The feedback is generated with the default options, since the `ZxcvbnOptions`
+    /// of the evaluation are not kept in the `Entropy`.
+    pub fn recompute_feedback(&mut self) {
+        self.feedback = self.feedback_for_score(self.score);
+    }
+
+    /// The feedback the password would receive if it had the given `score`,
+    /// with the default options. E.g. a password with a score of 3 gets no feedback,
+    /// but `feedback_for_score(Score::Two)` gives the advice for making it stronger.
+    pub fn feedback_for_score(&self, score: Score) -> Option<feedback::Feedback> {
+        feedback::get_feedback(score, &self.sequence, &ZxcvbnOptions::default())
+    }
+
     /// The list of patterns the guess calculation was based on
+    ///
+    /// With the "zeroize" feature, the tokens and the matched words of the matches are masked
+    /// like [`Match::display_token`], so they don't keep the password. The feedback is derived
+    /// before the masking, but [`Entropy::recompute_feedback`] only sees the masked tokens.
     pub fn sequence(&self) -> &[Match] {
         &self.sequence
     }
 
+    /// Whether the whole password is a single pattern, e.g. a single common word or keyboard
+    /// walk, i.e. `sequence` is one match spanning the whole password. Characters guessed by
+    /// bruteforce aren't a pattern, so this is `false` for random passwords.
+    ///
+    /// # Example
+    /// ```rust
+    /// use zxcvbn::zxcvbn;
+    ///
+    /// assert!(zxcvbn("qwertyuiop", &[]).is_single_pattern());
+    /// assert!(!zxcvbn("qwertyuiop1", &[]).is_single_pattern());
+    /// ```
+    pub fn is_single_pattern(&self) -> bool {
+        match self.sequence.as_slice() {
+            [m] => {
+                m.i == 0
+                    && m.j + 1 == self.password_length
+                    && m.kind() != matching::patterns::PatternKind::Bruteforce
+            }
+            _ => false,
+        }
+    }
+
+    /// The number of matches of `sequence` of each kind of pattern, including the bruteforce
+    /// matches filling the gaps between patterns, e.g. to report how many users have a
+    /// dictionary word in their password. Kinds which don't occur are left out.
+    ///
+    /// # Example
+    /// ```rust
+    /// use zxcvbn::matching::patterns::PatternKind;
+    /// use zxcvbn::zxcvbn;
+    ///
+    /// let summary = zxcvbn("p4ssw0rd1987", &[]).sequence_type_summary();
+    /// assert_eq!(summary[&PatternKind::L33t], 1);
+    /// assert_eq!(summary[&PatternKind::Regex], 1);
+    /// assert!(!summary.contains_key(&PatternKind::Spatial));
+    /// ```
+    pub fn sequence_type_summary(&self) -> HashMap<matching::patterns::PatternKind, usize> {
+        let mut summary = HashMap::new();
+        for m in &self.sequence {
+            *summary.entry(m.kind()).or_insert(0) += 1;
+        }
+        summary
+    }
+
+    /// Each match of `sequence` with its share of the password's strength, from `0.0` to `1.0`.
+    ///
+    /// Since the guesses of the matches multiply, the share of a match is its part
+    /// of the sum of the `log10` of the guesses of all matches. The shares sum to `1.0`,
+    /// including the bruteforce matches filling the gaps between patterns.
+    pub fn match_contributions(&self) -> Vec<(&Match, f64)> {
+        let guesses_log10 = self
+            .sequence
+            .iter()
+            .map(|m| (m.guesses.unwrap_or(1) as f64).log10())
+            .collect::<Vec<f64>>();
+        let total: f64 = guesses_log10.iter().sum();
+        self.sequence
+            .iter()
+            .zip(guesses_log10)
+            .map(|(m, log10)| {
+                // every match is a single guess, so none is weaker than another
+                let share = if total > 0. {
+                    log10 / total
+                } else {
+                    1. / self.sequence.len() as f64
+                };
+                (m, share)
+            })
+            .collect()
+    }
+
+    /// A plain-English description of what was found in the password and how fast it can
+    /// be cracked, e.g. "Your password contains a common password ('password'). The strongest
+    /// attacker scenario cracks it in less than a second."
+    ///
+    /// Unlike `feedback`, which gives advice, this describes the matched patterns.
+    /// It quotes the matched parts of the password, so it should be shown to the user
+    /// but not logged.
+    pub fn explain(&self) -> String {
+        explain::explain(self)
+    }
+
     /// How long it took to calculate the answer.
     pub fn calculation_time(&self) -> Duration {
         self.calc_time
     }
+
+    /// Get a copy of this entropy with a calculation time of zero, which makes it
+    /// deterministic, e.g. for snapshot tests.
+    pub fn with_calc_time_zeroed(&self) -> Entropy {
+        Entropy {
+            calc_time: Duration::ZERO,
+            ..self.clone()
+        }
+    }
+
+    /// Compare two entropies, except for their calculation times.
+    ///
+    /// # Example
+    /// ```rust
+    /// use zxcvbn::zxcvbn;
+    ///
+    /// assert!(zxcvbn("password", &[]).eq_ignoring_calc_time(&zxcvbn("password", &[])));
+    /// ```
+    pub fn eq_ignoring_calc_time(&self, other: &Entropy) -> bool {
+        Entropy {
+            calc_time: other.calc_time,
+            ..self.clone()
+        } == *other
+    }
+
+    /// The number of characters of the password that was evaluated, e.g. for telling users
+    /// how long their password is without keeping it. Unless `ZxcvbnOptions::analyze_full_length`
+    /// is set, only the first 100 characters are evaluated, along with the rest of the grapheme
+    /// cluster of the last one, e.g. its combining accents.
+    pub fn password_length(&self) -> usize {
+        self.password_length
+    }
+
+    /// The password that was evaluated. Unless `ZxcvbnOptions::analyze_full_length` is set,
+    /// only the first 100 characters are evaluated and kept.
+    ///
+    /// # Privacy
+    /// With the `store_password` feature enabled, every `Entropy` keeps a plaintext copy
+    /// of the evaluated password for as long as it is alive. Avoid logging, caching or
+    /// persisting `Entropy` values when this feature is enabled.
+    /// The password is never included when serializing with the `ser` feature.
+    #[cfg(feature = "store_password")]
+    pub fn password(&self) -> &str {
+        &self.password
+    }
+
+    /// Whether this password is too similar to a `previous` one, e.g. in a password change
+    /// flow where `Password1!` should not be accepted as a replacement for `Password2!`.
+    ///
+    /// The similarity is `1 - d / n`, where `d` is the edit distance between both passwords
+    /// and `n` is the length of the longer one, so it ranges from `0.0` (nothing in common)
+    /// to `1.0` (identical). Returns `true` if the similarity is at least `threshold`.
+    ///
+    /// Requires the `store_password` feature; see [`Entropy::password`] for its privacy implications.
+    #[cfg(feature = "store_password")]
+    pub fn similar_to_previous(&self, previous: &Entropy, threshold: f64) -> bool {
+        let current = self.password.chars().collect::<Vec<char>>();
+        let previous = previous.password.chars().collect::<Vec<char>>();
+        let max_len = cmp::max(current.len(), previous.len());
+        if max_len == 0 {
+            return true;
+        }
+        let similarity = 1.0 - edit_distance(&current, &previous) as f64 / max_len as f64;
+        similarity >= threshold
+    }
+}
+
+/// Rounds `value` half up to `significant_figures` significant figures, at least 1.
+fn round_to_significant_figures(value: u64, significant_figures: u32) -> u64 {
+    let digits = value.to_string().len() as u32;
+    let significant_figures = significant_figures.max(1);
+    if digits <= significant_figures {
+        return value;
+    }
+    let unit = 10u64.pow(digits - significant_figures);
+    let rounded = value / unit + u64::from(value % unit >= unit / 2);
+    rounded.saturating_mul(unit)
+}
+
+/// Levenshtein distance between two sequences of characters.
+#[cfg(feature = "store_password")]
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut previous_row = (0..=b.len()).collect::<Vec<usize>>();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current_row = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous_row[j] + usize::from(ca != cb);
+            current_row[j + 1] = cmp::min(
+                substitution,
+                cmp::min(previous_row[j + 1] + 1, current_row[j] + 1),
+            );
+        }
+        previous_row = current_row;
+    }
+    previous_row[b.len()]
 }
 
 /// Takes a password string and optionally a list of user-supplied inputs
 /// (e.g. username, email, first name) and calculates the strength of the password
 /// based on entropy, using a number of different factors.
+///
+/// User inputs made of several words, e.g. a full name, are also matched word by word.
 pub fn zxcvbn(password: &str, user_inputs: &[&str]) -> Entropy {
+    zxcvbn_with_options(password, user_inputs, &ZxcvbnOptions::default())
+}
+
+/// Same as [`zxcvbn`], but allows customizing the evaluation through [`ZxcvbnOptions`].
+pub fn zxcvbn_with_options(
+    password: &str,
+    user_inputs: &[&str],
+    options: &ZxcvbnOptions,
+) -> Entropy {
+    let sanitized_inputs = sanitize_inputs(user_inputs);
+    evaluate(
+        password,
+        &matching::user_inputs_dictionary(&sanitized_inputs),
+        options,
+    )
+}
+
+/// Evaluates each of `passwords` with the same `user_inputs`, e.g. to audit a list of
+/// credentials. The results are the same as calling [`zxcvbn`] on each password, but the
+/// user inputs are only sanitized and indexed for the dictionary matching once for the whole
+/// batch.
+pub fn zxcvbn_batch(passwords: &[&str], user_inputs: &[&str]) -> Vec<Entropy> {
+    zxcvbn_batch_with_options(passwords, user_inputs, &ZxcvbnOptions::default())
+}
+
+/// Same as [`zxcvbn_batch`], but allows customizing the evaluation through [`ZxcvbnOptions`].
+/// The results are the same as calling [`zxcvbn_with_options`] on each password.
+pub fn zxcvbn_batch_with_options(
+    passwords: &[&str],
+    user_inputs: &[&str],
+    options: &ZxcvbnOptions,
+) -> Vec<Entropy> {
+    let sanitized_inputs = sanitize_inputs(user_inputs);
+    let user_inputs = matching::user_inputs_dictionary(&sanitized_inputs);
+    passwords
+        .iter()
+        .map(|password| evaluate(password, &user_inputs, options))
+        .collect()
+}
+
+/// The change in strength from appending a character to a password,
+/// as computed by [`zxcvbn_score_delta`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+pub struct ScoreDelta {
+    /// Score of the password before appending the character.
+    previous_score: Score,
+    /// Guesses of the password before appending the character.
+    previous_guesses: u64,
+    /// Score of the extended password.
+    score: Score,
+    /// Guesses of the extended password.
+    guesses: u64,
+}
+
+impl ScoreDelta {
+    /// Score of the password before appending the character.
+    pub fn previous_score(&self) -> Score {
+        self.previous_score
+    }
+
+    /// Guesses of the password before appending the character.
+    pub fn previous_guesses(&self) -> u64 {
+        self.previous_guesses
+    }
+
+    /// Score of the extended password.
+    pub fn score(&self) -> Score {
+        self.score
+    }
+
+    /// Guesses of the extended password.
+    pub fn guesses(&self) -> u64 {
+        self.guesses
+    }
+
+    /// How much the score changed, from -4 to 4.
+    pub fn score_change(&self) -> i8 {
+        u8::from(self.score) as i8 - u8::from(self.previous_score) as i8
+    }
+
+    /// Whether the extended password takes more guesses than the original one.
+    /// Appending a character can make a password weaker, e.g. `passwor` + `d`.
+    pub fn improved(&self) -> bool {
+        self.guesses > self.previous_guesses
+    }
+}
+
+/// Evaluates `password` before and after appending `next`, e.g. for a strength meter
+/// which tells whether the character just typed made the password stronger.
+///
+/// Both passwords are fully evaluated, so the results are exactly those of `zxcvbn`:
+/// a single character can change every match, e.g. by completing a dictionary word or
+/// extending a repeat, so the matches of the original password can't be reused.
+///
+/// # Example
+/// ```rust
+/// use zxcvbn::zxcvbn_score_delta;
+///
+/// let delta = zxcvbn_score_delta("passwor", 'd', &[]);
+/// assert!(!delta.improved());
+/// assert_eq!(delta.guesses(), zxcvbn::zxcvbn("password", &[]).guesses());
+/// ```
+pub fn zxcvbn_score_delta(password: &str, next: char, user_inputs: &[&str]) -> ScoreDelta {
+    zxcvbn_score_delta_with_options(password, next, user_inputs, &ZxcvbnOptions::default())
+}
+
+/// Same as [`zxcvbn_score_delta`], but allows customizing the evaluation through
+/// [`ZxcvbnOptions`]. Both passwords are evaluated like with [`zxcvbn_with_options`].
+pub fn zxcvbn_score_delta_with_options(
+    password: &str,
+    next: char,
+    user_inputs: &[&str],
+    options: &ZxcvbnOptions,
+) -> ScoreDelta {
+    let sanitized_inputs = sanitize_inputs(user_inputs);
+    let user_inputs = matching::user_inputs_dictionary(&sanitized_inputs);
+    let previous = evaluate(password, &user_inputs, options);
+    let mut extended = String::with_capacity(password.len() + next.len_utf8());
+    extended.push_str(password);
+    extended.push(next);
+    let current = evaluate(&extended, &user_inputs, options);
+    ScoreDelta {
+        previous_score: previous.score,
+        previous_guesses: previous.guesses,
+        score: current.score,
+        guesses: current.guesses,
+    }
+}
+
+/// Same as [`zxcvbn`], but with an explicit rank for each of the user inputs, instead of
+/// ranking them by their position.
+///
+/// As in the frequency lists, a rank of 1 means the input is treated as the most common word.
+/// A rank of 0 is treated as 1. If an input occurs multiple times, its last rank is used.
+///
+/// # Example
+/// ```rust
+/// use zxcvbn::{zxcvbn, zxcvbn_with_ranked_inputs};
+///
+/// let positional = zxcvbn("bluefalcon", &["acmecorp", "payroll", "bluefalcon"]);
+/// let ranked = zxcvbn_with_ranked_inputs("bluefalcon", &[("acmecorp", 2), ("bluefalcon", 1)]);
+/// assert!(ranked.guesses() < positional.guesses());
+/// ```
+pub fn zxcvbn_with_ranked_inputs(password: &str, user_inputs: &[(&str, u32)]) -> Entropy {
+    zxcvbn_with_ranked_inputs_and_options(password, user_inputs, &ZxcvbnOptions::default())
+}
+
+/// Same as [`zxcvbn_with_ranked_inputs`], but allows customizing the evaluation through
+/// [`ZxcvbnOptions`].
+pub fn zxcvbn_with_ranked_inputs_and_options(
+    password: &str,
+    user_inputs: &[(&str, u32)],
+    options: &ZxcvbnOptions,
+) -> Entropy {
+    let sanitized_inputs = user_inputs
+        .iter()
+        .map(|&(x, rank)| (x.to_lowercase(), cmp::max(rank, 1) as usize))
+        .collect();
+    evaluate(
+        password,
+        &matching::user_inputs_dictionary(&sanitized_inputs),
+        options,
+    )
+}
+
+/// Same as [`zxcvbn`], but for a password and user inputs held as bytes, e.g. in a buffer
+/// from secure memory or produced by an encoding that isn't UTF-8.
+///
+/// Valid UTF-8 passwords are evaluated like with `zxcvbn`, without being copied.
+/// Bytes which are not part of valid UTF-8 sequences are opaque symbols: they never match
+/// dictionary words, and are guessed by bruteforce with a cardinality of 256.
+/// See [`zxcvbn_bytes_with_options`] for decoding them differently.
+///
+/// # Example
+/// ```rust
+/// use zxcvbn::zxcvbn_bytes;
+///
+/// let entropy = zxcvbn_bytes(b"password\xff\x00\x9c", &[b"jsmith"]);
+/// # #[cfg(not(feature = "zeroize"))]
+/// assert_eq!(entropy.sequence()[0].token, "password");
+/// ```
+pub fn zxcvbn_bytes(password: &[u8], user_inputs: &[&[u8]]) -> Entropy {
+    let options = ZxcvbnOptions::default().byte_decoding(ByteDecoding::Opaque);
+    zxcvbn_bytes_with_options(password, user_inputs, &options)
+        .expect("opaque bytes are never rejected")
+}
+
+/// Same as [`zxcvbn_bytes`], but allows customizing the evaluation through [`ZxcvbnOptions`],
+/// including how bytes which are not valid UTF-8 are decoded with
+/// [`ZxcvbnOptions::byte_decoding`].
+///
+/// Unlike `zxcvbn_bytes`, passwords and user inputs which are not valid UTF-8 are rejected
+/// unless another `ByteDecoding` than the default `ByteDecoding::Strict` is set.
+///
+/// # Example
+/// ```rust
+/// use zxcvbn::{zxcvbn, zxcvbn_bytes_with_options, ByteDecoding, ZxcvbnOptions};
+///
+/// let options = ZxcvbnOptions::new().byte_decoding(ByteDecoding::Latin1);
+/// let entropy = zxcvbn_bytes_with_options(b"caf\xe9latte", &[], &options).unwrap();
+/// assert_eq!(entropy.guesses(), zxcvbn("caf\u{e9}latte", &[]).guesses());
+/// ```
+pub fn zxcvbn_bytes_with_options(
+    password: &[u8],
+    user_inputs: &[&[u8]],
+    options: &ZxcvbnOptions,
+) -> Result<Entropy, Utf8Error> {
+    let (password, opaque_bytes) = decode_bytes(password, options.byte_decoding)?;
+    let user_inputs = user_inputs
+        .iter()
+        .map(|input| decode_bytes(input, options.byte_decoding).map(|(input, _)| input))
+        .collect::<Result<Vec<Cow<str>>, Utf8Error>>()?;
+    let user_inputs = user_inputs.iter().map(AsRef::as_ref).collect::<Vec<&str>>();
+    let sanitized_inputs = sanitize_inputs(&user_inputs);
+    Ok(evaluate_with_opaque_bytes(
+        &password,
+        &matching::user_inputs_dictionary(&sanitized_inputs),
+        options,
+        &opaque_bytes,
+    ))
+}
+
+/// Decodes `bytes` as UTF-8, or as specified by `decoding` if they are not valid UTF-8.
+/// Only `ByteDecoding::Strict` returns errors.
+///
+/// Also returns the sorted indices of the characters standing for opaque bytes, which are
+/// only produced by `ByteDecoding::Opaque`.
+fn decode_bytes(
+    bytes: &[u8],
+    decoding: ByteDecoding,
+) -> Result<(Cow<'_, str>, Vec<usize>), Utf8Error> {
+    let error = match std::str::from_utf8(bytes) {
+        Ok(s) => return Ok((Cow::Borrowed(s), Vec::new())),
+        Err(e) => e,
+    };
+    let mut opaque_bytes = Vec::new();
+    let decoded = match decoding {
+        ByteDecoding::Strict => return Err(error),
+        ByteDecoding::Lossy => String::from_utf8_lossy(bytes),
+        ByteDecoding::Latin1 => Cow::Owned(bytes.iter().map(|&b| b as char).collect()),
+        ByteDecoding::Opaque => {
+            let mut decoded = String::with_capacity(bytes.len() * 4);
+            let mut char_count = 0;
+            let mut rest = bytes;
+            while !rest.is_empty() {
+                match std::str::from_utf8(rest) {
+                    Ok(valid) => {
+                        decoded.push_str(valid);
+                        break;
+                    }
+                    Err(e) => {
+                        let (valid, invalid) = rest.split_at(e.valid_up_to());
+                        let valid = std::str::from_utf8(valid).unwrap();
+                        decoded.push_str(valid);
+                        char_count += valid.chars().count();
+                        // an incomplete sequence at the end has no error length
+                        let invalid_len = e.error_len().unwrap_or(invalid.len());
+                        for &b in &invalid[..invalid_len] {
+                            decoded.push(scoring::opaque_byte(b));
+                            opaque_bytes.push(char_count);
+                            char_count += 1;
+                        }
+                        rest = &invalid[invalid_len..];
+                    }
+                }
+            }
+            Cow::Owned(decoded)
+        }
+    };
+    Ok((decoded, opaque_bytes))
+}
+
+fn evaluate(password: &str, user_inputs: &RankedDictionary, options: &ZxcvbnOptions) -> Entropy {
+    evaluate_with_opaque_bytes(password, user_inputs, options, &[])
+}
+
+/// Same as `evaluate`, but the characters at the sorted indices `opaque_bytes` stand for bytes
+/// of invalid UTF-8 sequences.
+fn evaluate_with_opaque_bytes(
+    password: &str,
+    user_inputs: &RankedDictionary,
+    options: &ZxcvbnOptions,
+    opaque_bytes: &[usize],
+) -> Entropy {
+    const COMPLETES: &str = "evaluations without a deadline complete";
+    // only the evaluation of passwords which would otherwise be truncated can be abandoned
+    let deadline = options
+        .deadline
+        .filter(|_| options.analyze_full_length && password.chars().nth(100).is_some());
+    let deadline = match deadline {
+        Some(deadline) => deadline,
+        None => {
+            let truncate = !options.analyze_full_length;
+            return evaluate_before_deadline(
+                password,
+                user_inputs,
+                options,
+                opaque_bytes,
+                truncate,
+                None,
+            )
+            .expect(COMPLETES);
+        }
+    };
+    let (entropy, elapsed) = time_scoped(|| {
+        evaluate_before_deadline(
+            password,
+            user_inputs,
+            options,
+            opaque_bytes,
+            false,
+            Some(deadline),
+        )
+    });
+    entropy.unwrap_or_else(|| {
+        let mut entropy =
+            evaluate_before_deadline(password, user_inputs, options, opaque_bytes, true, None)
+                .expect(COMPLETES);
+        entropy.calc_time += elapsed;
+        entropy
+    })
+}
+
+/// Evaluates `password`, or only its first 100 characters with `truncate`, or returns `None`
+/// if `deadline` passes first.
+fn evaluate_before_deadline(
+    password: &str,
+    user_inputs: &RankedDictionary,
+    options: &ZxcvbnOptions,
+    opaque_bytes: &[usize],
+    truncate: bool,
+    deadline: Option<std::time::Instant>,
+) -> Option<Entropy> {
     if password.is_empty() {
-        return Entropy {
+        return Some(Entropy {
             guesses: 0,
             guesses_log10: f64::NEG_INFINITY,
             crack_times: CrackTimes::new(0),
             score: Score::Zero,
-            feedback: feedback::get_feedback(Score::Zero, &[]),
+            feedback: feedback::get_feedback(Score::Zero, &[], options),
             sequence: Vec::default(),
             calc_time: Duration::from_secs(0),
-        };
+            password_length: 0,
+            #[cfg(feature = "store_password")]
+            password: String::new(),
+        });
     }
 
-    let (result, calc_time) = time_scoped(|| {
-        // Only evaluate the first 100 characters of the input.
-        // This prevents potential DoS attacks from sending extremely long input strings.
-        let password = password.chars().take(100).collect::<String>();
-
-        let sanitized_inputs = user_inputs
-            .iter()
-            .enumerate()
-            .map(|(i, x)| (x.to_lowercase(), i + 1))
-            .collect();
+    // Only evaluate the first 100 characters of the input, unless explicitly requested.
+    // This prevents potential DoS attacks from sending extremely long input strings.
+    // The truncation doesn't split the grapheme cluster of the last character.
+    let truncated;
+    let password = if !truncate {
+        password
+    } else {
+        // The capacity fits the truncated password, so the copy is never reallocated.
+        let mut copy = String::with_capacity(password.len());
+        copy.push_str(graphemes::truncate(password, 100));
+        #[cfg(feature = "zeroize")]
+        let copy = zeroizing::ZeroizingString(copy);
+        truncated = copy;
+        &*truncated
+    };
 
-        let matches = matching::omnimatch(&password, &sanitized_inputs);
-        scoring::most_guessable_match_sequence(&password, &matches, false)
+    let (result, calc_time) = time_scoped(|| {
+        let matches = match deadline {
+            Some(deadline) => {
+                let matches =
+                    matching::omnimatch_before_deadline(password, user_inputs, options, deadline)?;
+                if std::time::Instant::now() >= deadline {
+                    return None;
+                }
+                matches
+            }
+            None => matching::omnimatch_with_options(password, user_inputs, options),
+        };
+        let result = scoring::most_guessable_match_sequence_with_options(
+            password,
+            &matches,
+            false,
+            options,
+            opaque_bytes,
+        );
+        #[cfg(feature = "zeroize")]
+        for mut m in matches {
+            zeroizing::mask_match(&mut m);
+        }
+        Some(result)
     });
-    let (crack_times, score) = time_estimates::estimate_attack_times(result.guesses);
-    let feedback = feedback::get_feedback(score, &result.sequence);
+    let result = result?;
+    let (crack_times, score, feedback) = if options.strict_saturation && result.guesses == u64::MAX
+    {
+        // the guesses can't be represented, so the password is strong beyond any estimate
+        (CrackTimes::new(u64::MAX), Score::Four, None)
+    } else {
+        let (crack_times, score) = time_estimates::estimate_attack_times(result.guesses);
+        let feedback = feedback::get_feedback(score, &result.sequence, options);
+        (crack_times, score, feedback)
+    };
+    // the feedback is based on the tokens, so they are only masked once it is computed
+    #[cfg(feature = "zeroize")]
+    let mut result = result;
+    #[cfg(feature = "zeroize")]
+    result.sequence.iter_mut().for_each(zeroizing::mask_match);
 
-    Entropy {
+    Some(Entropy {
         guesses: result.guesses,
         guesses_log10: result.guesses_log10,
         crack_times,
@@ -161,7 +871,135 @@ pub fn zxcvbn(password: &str, user_inputs: &[&str]) -> Entropy {
         feedback,
         sequence: result.sequence,
         calc_time,
+        password_length: password.chars().count(),
+        #[cfg(feature = "store_password")]
+        password: password.to_string(),
+    })
+}
+
+/// Returns every match found in `password`, before the optimal sequence of matches is chosen.
+///
+/// Unlike [`Entropy::sequence`], the matches may overlap, and their `guesses` are not estimated yet.
+/// This is useful for analyzing why a particular pattern wasn't selected.
+pub fn zxcvbn_all_matches(password: &str, user_inputs: &[&str]) -> Vec<Match> {
+    zxcvbn_all_matches_with_options(password, user_inputs, &ZxcvbnOptions::default())
+}
+
+/// Same as [`zxcvbn_all_matches`], but with the dictionaries, matchers and other options of
+/// [`ZxcvbnOptions`], so that the matches are those [`zxcvbn_with_options`] chooses from.
+/// The matching always completes, even past the deadline of [`ZxcvbnOptions::with_deadline`].
+///
+/// # Example
+/// ```rust
+/// use zxcvbn::{zxcvbn_all_matches, zxcvbn_all_matches_with_options, ZxcvbnOptions};
+///
+/// static PRODUCTS: &[&str] = &["frobnicator"];
+/// let options = ZxcvbnOptions::new().with_extra_dictionary("products", PRODUCTS);
+/// let has_product = |matches: &[zxcvbn::Match]| matches.iter().any(|m| m.token == "frobnicator");
+/// assert!(has_product(&zxcvbn_all_matches_with_options("frobnicator", &[], &options)));
+/// assert!(!has_product(&zxcvbn_all_matches("frobnicator", &[])));
+/// ```
+pub fn zxcvbn_all_matches_with_options(
+    password: &str,
+    user_inputs: &[&str],
+    options: &ZxcvbnOptions,
+) -> Vec<Match> {
+    // Same truncation as `zxcvbn_with_options`, so the matches correspond to what is evaluated.
+    let password = if options.analyze_full_length {
+        password
+    } else {
+        graphemes::truncate(password, 100)
+    };
+    let sanitized_inputs = sanitize_inputs(user_inputs);
+    matching::omnimatch_with_options(
+        password,
+        &matching::user_inputs_dictionary(&sanitized_inputs),
+        options,
+    )
+}
+
+/// Characters on which user inputs are split into words, e.g. a full name into first name and
+/// surname.
+const USER_INPUT_SEPARATORS: [char; 4] = [' ', '_', '-', '.'];
+/// The minimum number of characters of the words of user inputs ranked on their own, as shorter
+/// fragments like initials would match almost every password.
+const MIN_USER_INPUT_WORD_LEN: usize = 3;
+
+/// Ranks the lowercased user inputs by their order.
+///
+/// The inputs made of several words are also ranked with their words joined by each of the
+/// separators, or by none, since `John Smith` may appear as `john_smith` or `johnsmith` in a
+/// password. They are followed by the words of at least `MIN_USER_INPUT_WORD_LEN` characters,
+/// so that each word matches on its own, at a slightly higher rank than the inputs.
+fn sanitize_inputs(user_inputs: &[&str]) -> HashMap<String, usize> {
+    let mut sanitized: HashMap<String, usize> = user_inputs
+        .iter()
+        .enumerate()
+        .map(|(i, x)| (x.to_lowercase(), i + 1))
+        .collect();
+    let mut words = Vec::new();
+    for (i, input) in user_inputs.iter().enumerate() {
+        let input_words = input
+            .split(&USER_INPUT_SEPARATORS[..])
+            .filter(|word| !word.is_empty())
+            .map(str::to_lowercase)
+            .collect::<Vec<String>>();
+        if input_words.len() > 1 {
+            for separator in USER_INPUT_SEPARATORS
+                .iter()
+                .map(char::to_string)
+                .chain([String::new()])
+            {
+                sanitized
+                    .entry(input_words.join(&separator))
+                    .or_insert(i + 1);
+            }
+        }
+        words.extend(input_words);
+    }
+    let mut rank = user_inputs.len();
+    for word in words
+        .into_iter()
+        .filter(|word| word.chars().count() >= MIN_USER_INPUT_WORD_LEN)
+    {
+        sanitized.entry(word).or_insert_with(|| {
+            rank += 1;
+            rank
+        });
+    }
+    sanitized
+}
+
+/// Same as [`zxcvbn`], but only evaluates the characters of `password` from index `start`
+/// (inclusive) to `end` (exclusive), counted in `char`s.
+///
+/// The positions of the matches in the resulting sequence are relative to the whole `password`,
+/// so they can be related back to the original input.
+///
+/// # Panics
+///
+/// Panics if `start` is greater than `end`, or if `end` is greater than the number of
+/// characters of `password`.
+pub fn zxcvbn_substring(password: &str, start: usize, end: usize, user_inputs: &[&str]) -> Entropy {
+    let len = password.chars().count();
+    assert!(
+        start <= end && end <= len,
+        "substring {}..{} out of bounds of a password of {} characters",
+        start,
+        end,
+        len
+    );
+    let substring = password
+        .chars()
+        .skip(start)
+        .take(end - start)
+        .collect::<String>();
+    let mut entropy = zxcvbn(&substring, user_inputs);
+    for m in &mut entropy.sequence {
+        m.i += start;
+        m.j += start;
     }
+    entropy
 }
 
 #[cfg(test)]
@@ -194,10 +1032,21 @@ mod tests {
         let password = "r0sebudmaelstrom11/20/91aaaa";
         let entropy = zxcvbn(password, &[]);
         assert_eq!(entropy.guesses_log10 as u16, 14);
+        assert!((entropy.bits() - entropy.guesses_log10 * 10f64.log2()).abs() < 1e-9);
         assert_eq!(entropy.score, Score::Four);
         assert!(!entropy.sequence.is_empty());
         assert!(entropy.feedback.is_none());
         assert!(entropy.calc_time.as_nanos() > 0);
+        assert_eq!(entropy.password_length(), 28);
+    }
+
+    #[cfg(feature = "ser")]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_password_length_serialization() {
+        let entropy = zxcvbn("p\u{e4}ssw\u{f6}rd", &[]);
+        let json = serde_json::to_value(&entropy).unwrap();
+        assert_eq!(json["password_length"], 8);
     }
 
     #[cfg_attr(not(target_arch = "wasm32"), test)]
@@ -208,8 +1057,325 @@ mod tests {
         assert_eq!(entropy.score, Score::Zero);
         assert_eq!(entropy.guesses, 0);
         assert_eq!(entropy.guesses_log10, f64::NEG_INFINITY);
+        assert_eq!(entropy.bits(), f64::NEG_INFINITY);
         assert_eq!(entropy.crack_times, CrackTimes::new(0));
         assert_eq!(entropy.sequence, Vec::default());
+        assert_eq!(entropy.password_length(), 0);
+    }
+
+    #[cfg(feature = "test-util")]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_entropy_from_parts() {
+        let evaluated = zxcvbn("password", &[]);
+        let feedback = feedback::Feedback::from_parts(
+            evaluated.feedback().unwrap().warning(),
+            evaluated.feedback().unwrap().suggestions().to_vec(),
+        );
+        let entropy = Entropy::from_parts(
+            evaluated.guesses(),
+            evaluated.score(),
+            Some(feedback),
+            evaluated.sequence().to_vec(),
+        );
+        assert_eq!(entropy.guesses_log10(), evaluated.guesses_log10());
+        assert_eq!(entropy.crack_times(), evaluated.crack_times());
+        assert_eq!(entropy.score(), evaluated.score());
+        assert_eq!(
+            entropy.feedback().unwrap().to_string(),
+            evaluated.feedback().unwrap().to_string()
+        );
+        assert_eq!(entropy.sequence(), evaluated.sequence());
+        assert_eq!(entropy.calculation_time(), Duration::from_secs(0));
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_sequence_type_summary() {
+        use crate::matching::patterns::PatternKind;
+
+        let summary = zxcvbn("correcthorse8$=q", &[]).sequence_type_summary();
+        assert_eq!(summary.len(), 2);
+        assert_eq!(summary[&PatternKind::Dictionary], 2);
+        assert_eq!(summary[&PatternKind::Bruteforce], 1);
+
+        let entropy = zxcvbn("qwerty%Kx9asdfgh1987", &[]);
+        let summary = entropy.sequence_type_summary();
+        assert_eq!(summary.values().sum::<usize>(), entropy.sequence().len());
+        assert_eq!(summary[&PatternKind::Bruteforce], 1);
+
+        assert!(zxcvbn("", &[]).sequence_type_summary().is_empty());
+    }
+
+    #[cfg(not(feature = "zeroize"))]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_match_contributions() {
+        let entropy = zxcvbn("correcthorse8$=q", &[]);
+        let contributions = entropy.match_contributions();
+        assert_eq!(contributions.len(), entropy.sequence.len());
+        let total: f64 = contributions.iter().map(|(_, share)| share).sum();
+        assert!((total - 1.).abs() < 1e-9);
+        assert!(contributions
+            .iter()
+            .any(|(m, _)| m.pattern == matching::patterns::MatchPattern::BruteForce));
+        let (weakest, _) = contributions
+            .iter()
+            .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+            .unwrap();
+        assert_eq!(weakest.token, "horse");
+
+        assert!(zxcvbn("", &[]).match_contributions().is_empty());
+        assert_eq!(zxcvbn("123456", &[]).match_contributions()[0].1, 1.);
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_zxcvbn_bytes() {
+        let entropy = zxcvbn_bytes(b"correcthorsebatterystaple", &[b"jsmith"]);
+        assert_eq!(
+            entropy.guesses,
+            zxcvbn("correcthorsebatterystaple", &["jsmith"]).guesses
+        );
+
+        // NUL bytes are valid UTF-8
+        let entropy = zxcvbn_bytes(b"pass\x00word", &[]);
+        assert_eq!(entropy.guesses, zxcvbn("pass\0word", &[]).guesses);
+
+        // opaque bytes are bruteforced with a cardinality of 256
+        let entropy = zxcvbn_bytes(b"\x00\xff\x00\xfe", &[]);
+        let bruteforce = entropy
+            .sequence
+            .iter()
+            .find(|m| m.pattern == matching::patterns::MatchPattern::BruteForce)
+            .unwrap();
+        assert_eq!(bruteforce.token.chars().count(), 4);
+        assert_eq!(bruteforce.guesses, Some(10 * 256 * 10 * 256));
+        // the characters standing for opaque bytes are plain characters in valid passwords
+        let entropy = zxcvbn("\0\u{10FFFF}\0\u{10FFFE}", &[]);
+        assert_eq!(entropy.sequence[0].guesses, Some(10_000));
+        let bytes = "\0\u{10FFFF}\0\u{10FFFE}".as_bytes();
+        assert_eq!(zxcvbn_bytes(bytes, &[]).guesses, entropy.guesses);
+
+        let entropy = zxcvbn_bytes(b"\xc3jsmith\x80", &[b"jsmith\x80"]);
+        assert!(entropy.sequence.iter().any(|m| matches!(
+            m.pattern,
+            matching::patterns::MatchPattern::Dictionary(ref p)
+                if p.dictionary_name == frequency_lists::DictionaryType::UserInputs
+        )));
+
+        let password = b"p\xe4ssw\xf6rd";
+        let options = ZxcvbnOptions::new().byte_decoding(ByteDecoding::Strict);
+        assert!(zxcvbn_bytes_with_options(password, &[], &options).is_err());
+        assert!(zxcvbn_bytes_with_options(b"password", &[b"\xff"], &options).is_err());
+
+        let options = ZxcvbnOptions::new().byte_decoding(ByteDecoding::Lossy);
+        let entropy = zxcvbn_bytes_with_options(password, &[], &options).unwrap();
+        assert_eq!(
+            entropy.guesses,
+            zxcvbn("p\u{fffd}ssw\u{fffd}rd", &[]).guesses
+        );
+
+        let options = ZxcvbnOptions::new().byte_decoding(ByteDecoding::Latin1);
+        let entropy = zxcvbn_bytes_with_options(password, &[], &options).unwrap();
+        assert_eq!(entropy.guesses, zxcvbn("p\u{e4}ssw\u{f6}rd", &[]).guesses);
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_zxcvbn_batch() {
+        let passwords = ["password", "", "jsmith8634!xQ", "correcthorsebatterystaple"];
+        let user_inputs = ["jsmith", "jsmith@example.com"];
+        let entropies = zxcvbn_batch(&passwords, &user_inputs);
+        assert_eq!(entropies.len(), passwords.len());
+        for (password, entropy) in passwords.iter().zip(&entropies) {
+            let expected = zxcvbn(password, &user_inputs);
+            assert_eq!(entropy.guesses, expected.guesses);
+            assert_eq!(entropy.score, expected.score);
+            assert_eq!(entropy.sequence, expected.sequence);
+            assert_eq!(
+                entropy.feedback.as_ref().map(ToString::to_string),
+                expected.feedback.as_ref().map(ToString::to_string)
+            );
+        }
+
+        let options = ZxcvbnOptions::new().with_extra_dictionary("birds", &["nightjar"]);
+        let entropies = zxcvbn_batch_with_options(&["nightjar", "password"], &[], &options);
+        assert_eq!(
+            entropies[0].guesses,
+            zxcvbn_with_options("nightjar", &[], &options).guesses
+        );
+        assert!(entropies[0].guesses < zxcvbn("nightjar", &[]).guesses);
+        assert_eq!(entropies[1].guesses, zxcvbn("password", &[]).guesses);
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_truncation_keeps_grapheme_clusters() {
+        for cluster in [
+            "e\u{301}",
+            "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}\u{200D}\u{1F466}",
+            "\u{915}\u{94D}\u{937}",
+        ] {
+            let password = format!("{}{}x", "a".repeat(99), cluster);
+            let entropy = zxcvbn(&password, &[]);
+            assert_eq!(
+                entropy.password_length(),
+                99 + cluster.chars().count(),
+                "{}",
+                cluster
+            );
+            assert!(zxcvbn_all_matches(&password, &[])
+                .iter()
+                .all(|m| m.j < entropy.password_length()));
+        }
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_analyze_full_length() {
+        let password = "correct horse battery staple ".repeat(5);
+        let truncated = zxcvbn(&password, &[]);
+        assert_eq!(truncated.sequence.last().unwrap().j, 99);
+        assert_eq!(truncated.password_length(), 100);
+
+        let options = ZxcvbnOptions::new().analyze_full_length(true);
+        let entropy = zxcvbn_with_options(&password, &[], &options);
+        assert_eq!(entropy.sequence.last().unwrap().j, password.len() - 1);
+        assert_eq!(entropy.password_length(), password.len());
+        assert!(entropy.guesses >= truncated.guesses);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_deadline() {
+        use std::time::{Duration, Instant};
+
+        let password = "correcthorse".repeat(12);
+        let full_length = ZxcvbnOptions::new().analyze_full_length(true);
+        let full = zxcvbn_with_options(&password, &[], &full_length);
+        let truncated = zxcvbn(&password, &[]);
+
+        let options = full_length
+            .clone()
+            .with_deadline(Instant::now() + Duration::from_secs(3600));
+        let entropy = zxcvbn_with_options(&password, &[], &options);
+        assert!(entropy.eq_ignoring_calc_time(&full));
+
+        // a passed deadline falls back to the first 100 characters
+        let options = full_length.clone().with_deadline(Instant::now());
+        let entropy = zxcvbn_with_options(&password, &[], &options);
+        assert!(entropy.eq_ignoring_calc_time(&truncated));
+        assert_eq!(entropy.password_length(), 100);
+        assert!(!entropy.eq_ignoring_calc_time(&full));
+
+        // shorter passwords, and truncated evaluations, are always complete
+        let entropy = zxcvbn_with_options("correct horse", &[], &options);
+        assert!(entropy.eq_ignoring_calc_time(&zxcvbn("correct horse", &[])));
+        let options = ZxcvbnOptions::new().with_deadline(Instant::now());
+        assert!(zxcvbn_with_options(&password, &[], &options).eq_ignoring_calc_time(&truncated));
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_zxcvbn_substring() {
+        let password = "8472korrektpferd!!";
+        let entropy = zxcvbn_substring(password, 4, 16, &[]);
+        assert_eq!(entropy.guesses, zxcvbn("korrektpferd", &[]).guesses);
+        assert_eq!(entropy.sequence.first().unwrap().i, 4);
+        assert_eq!(entropy.sequence.last().unwrap().j, 15);
+
+        let entropy = zxcvbn_substring("märz2010", 1, 1, &[]);
+        assert_eq!(entropy.score, Score::Zero);
+        assert!(entropy.sequence.is_empty());
+    }
+
+    #[cfg(not(feature = "zeroize"))]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_zxcvbn_all_matches() {
+        let password = "r0sebudmaelstrom11/20/91aaaa";
+        let matches = zxcvbn_all_matches(password, &[]);
+        let entropy = zxcvbn(password, &[]);
+        assert!(matches.len() > entropy.sequence.len());
+        // every match of the optimal sequence, apart from bruteforce ones, is a candidate
+        for m in entropy
+            .sequence
+            .iter()
+            .filter(|m| m.pattern != matching::patterns::MatchPattern::BruteForce)
+        {
+            assert!(matches.iter().any(|candidate| candidate.i == m.i
+                && candidate.j == m.j
+                && candidate.token == m.token));
+        }
+        assert!(matches.iter().any(|m| m.token == "r0se"));
+
+        assert!(zxcvbn_all_matches("", &[]).is_empty());
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_zxcvbn_all_matches_with_options() {
+        let password = "qwertyfrobnicator";
+        let options = ZxcvbnOptions::new()
+            .with_extra_dictionary("products", &["frobnicator"])
+            .disable_matcher(matching::patterns::PatternKind::Spatial);
+        let matches = zxcvbn_all_matches_with_options(password, &[], &options);
+        assert!(matches.iter().any(|m| m.token == "frobnicator"));
+        assert!(!matches
+            .iter()
+            .any(|m| m.kind() == matching::patterns::PatternKind::Spatial));
+        let default_matches = zxcvbn_all_matches(password, &[]);
+        assert!(!default_matches.iter().any(|m| m.token == "frobnicator"));
+        assert!(default_matches
+            .iter()
+            .any(|m| m.kind() == matching::patterns::PatternKind::Spatial));
+
+        let password = "x".repeat(150);
+        let full_length = ZxcvbnOptions::new().analyze_full_length(true);
+        let longest = |matches: Vec<Match>| matches.iter().map(|m| m.j).max();
+        assert_eq!(longest(zxcvbn_all_matches(&password, &[])), Some(99));
+        assert_eq!(
+            longest(zxcvbn_all_matches_with_options(
+                &password,
+                &[],
+                &full_length
+            )),
+            Some(149)
+        );
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_zxcvbn_with_ranked_inputs() {
+        let password = "Nightjar";
+        let common = zxcvbn_with_ranked_inputs(password, &[("nightjar", 1)]);
+        let rare = zxcvbn_with_ranked_inputs(password, &[("nightjar", 5000)]);
+        assert!(common.guesses < rare.guesses);
+        assert_eq!(
+            common.guesses,
+            zxcvbn_with_ranked_inputs(password, &[("NIGHTJAR", 0)]).guesses
+        );
+        assert_eq!(
+            common.guesses,
+            zxcvbn_with_ranked_inputs(password, &[("nightjar", 5000), ("nightjar", 1)]).guesses
+        );
+        assert_eq!(
+            zxcvbn_with_ranked_inputs(password, &[("acme", 1), ("nightjar", 2)]).guesses,
+            zxcvbn(password, &["acme", "nightjar"]).guesses
+        );
+
+        let options = ZxcvbnOptions::new().with_extra_dictionary("birds", &["nightjar"]);
+        assert!(
+            zxcvbn_with_ranked_inputs_and_options(password, &[("acme", 1)], &options).guesses
+                < zxcvbn_with_ranked_inputs(password, &[("acme", 1)]).guesses
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_zxcvbn_substring_out_of_bounds() {
+        zxcvbn_substring("märz", 2, 5, &[]);
     }
 
     #[cfg_attr(not(target_arch = "wasm32"), test)]
@@ -246,6 +1412,77 @@ mod tests {
         assert_eq!(entropy.score, Score::Three);
     }
 
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_is_single_pattern() {
+        for password in ["password", "Tr0ub4dour", "zxcvbnm", "aaaaaaa", "13/05/1987"] {
+            assert!(zxcvbn(password, &[]).is_single_pattern(), "{}", password);
+        }
+        for password in ["", "jd8h3lq", "summer2023", "correcthorse"] {
+            assert!(!zxcvbn(password, &[]).is_single_pattern(), "{}", password);
+        }
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_is_stronger_than() {
+        let weak = zxcvbn("password1", &[]);
+        let strong = zxcvbn("TestMeNow!", &[]);
+        assert!(strong.is_stronger_than(&weak));
+        assert!(!weak.is_stronger_than(&strong));
+        assert!(!strong.is_stronger_than(&strong));
+        assert!(!strong.is_stronger_than(&zxcvbn("TestMeNow!", &[])));
+        assert_eq!(strong.orders_of_magnitude_stronger_than(&strong), 0.0);
+        assert_eq!(weak.orders_of_magnitude_stronger_than(&strong), 0.0);
+        let orders = strong.orders_of_magnitude_stronger_than(&weak);
+        assert!((orders - (strong.guesses_log10 - weak.guesses_log10)).abs() < f64::EPSILON);
+        assert!(orders > 1.0);
+
+        // exactly 10 times as many guesses is not enough
+        let mut ten_times = weak.clone();
+        ten_times.guesses = weak.guesses * 10;
+        ten_times.guesses_log10 = weak.guesses_log10 + 1.0;
+        assert!(!ten_times.is_stronger_than(&weak));
+        ten_times.guesses += 1;
+        assert!(ten_times.is_stronger_than(&weak));
+
+        let empty = zxcvbn("", &[]);
+        assert!(weak.is_stronger_than(&empty));
+        assert!(!empty.is_stronger_than(&empty));
+        assert_eq!(empty.orders_of_magnitude_stronger_than(&empty), 0.0);
+        assert_eq!(
+            weak.orders_of_magnitude_stronger_than(&empty),
+            f64::INFINITY
+        );
+
+        let mut saturated = strong.clone();
+        saturated.guesses = u64::MAX;
+        assert!(!saturated.is_stronger_than(&saturated));
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_guesses_rounded() {
+        let entropy = zxcvbn("TestMeNow!", &[]);
+        assert_eq!(entropy.guesses_rounded(1), 400_000_000);
+        assert_eq!(entropy.guesses_rounded(2), 370_000_000);
+        assert_eq!(entropy.guesses_rounded(4), 372_000_000);
+        assert_eq!(entropy.guesses_rounded(5), 372_010_000);
+        assert_eq!(entropy.guesses_rounded(20), 372_010_000);
+        assert_eq!(entropy.guesses_rounded(0), 400_000_000);
+
+        assert_eq!(super::round_to_significant_figures(0, 2), 0);
+        assert_eq!(super::round_to_significant_figures(7, 2), 7);
+        assert_eq!(super::round_to_significant_figures(1_250, 2), 1_300);
+        assert_eq!(super::round_to_significant_figures(1_249, 2), 1_200);
+        assert_eq!(super::round_to_significant_figures(999, 2), 1_000);
+        assert_eq!(
+            super::round_to_significant_figures(u64::MAX, 2),
+            18_000_000_000_000_000_000
+        );
+        assert_eq!(super::round_to_significant_figures(u64::MAX, 1), u64::MAX);
+    }
+
     #[cfg_attr(not(target_arch = "wasm32"), test)]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn test_issue_15_example_2() {
@@ -261,8 +1498,148 @@ mod tests {
     fn test_overflow_safety() {
         let password = "!QASW@#EDFR$%TGHY^&UJKI*(OL";
         let entropy = zxcvbn(password, &[]);
-        assert_eq!(entropy.guesses, u64::max_value());
+        assert_eq!(entropy.guesses, u64::MAX);
+        assert_eq!(entropy.score, Score::Four);
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_zxcvbn_score_delta() {
+        for &(password, next) in &[
+            ("", 'a'),
+            ("passwor", 'd'),
+            ("correcthorse", 'b'),
+            ("jsmit", 'h'),
+        ] {
+            let delta = zxcvbn_score_delta(password, next, &["jsmith"]);
+            let previous = zxcvbn(password, &["jsmith"]);
+            let current = zxcvbn(&format!("{}{}", password, next), &["jsmith"]);
+            assert_eq!(delta.previous_score(), previous.score());
+            assert_eq!(delta.previous_guesses(), previous.guesses());
+            assert_eq!(delta.score(), current.score());
+            assert_eq!(delta.guesses(), current.guesses());
+        }
+
+        let delta = zxcvbn_score_delta("correcthorsebattery", 's', &[]);
+        assert!(delta.improved());
+        assert!(delta.score_change() >= 0);
+        let delta = zxcvbn_score_delta("passwor", 'd', &[]);
+        assert!(!delta.improved());
+        assert!(delta.score_change() <= 0);
+
+        let options = ZxcvbnOptions::new().with_extra_dictionary("birds", &["nightjar"]);
+        let delta = zxcvbn_score_delta_with_options("nightja", 'r', &[], &options);
+        assert!(!delta.improved());
+        assert_eq!(
+            delta.guesses(),
+            zxcvbn_with_options("nightjar", &[], &options).guesses()
+        );
+        assert!(delta.guesses() < zxcvbn_score_delta("nightja", 'r', &[]).guesses());
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_compare_ignoring_calc_time() {
+        let mut first = zxcvbn("Tr0ub4dour&3", &[]);
+        let mut second = zxcvbn("Tr0ub4dour&3", &[]);
+        first.calc_time = Duration::from_millis(3);
+        second.calc_time = Duration::from_millis(5);
+        assert_ne!(first, second);
+        assert!(first.eq_ignoring_calc_time(&second));
+        assert_eq!(
+            first.with_calc_time_zeroed(),
+            second.with_calc_time_zeroed()
+        );
+        assert_eq!(
+            first.with_calc_time_zeroed().calculation_time(),
+            Duration::ZERO
+        );
+        assert!(!first.eq_ignoring_calc_time(&zxcvbn("Tr0ub4dour&4", &[])));
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_feedback_for_score() {
+        let entropy = zxcvbn("password", &[]);
+        assert_eq!(
+            entropy.feedback_for_score(entropy.score()).as_ref(),
+            entropy.feedback()
+        );
+        assert!(entropy.feedback_for_score(Score::Four).is_none());
+
+        let mut entropy = zxcvbn("correcthorsebatterystaple", &[]);
+        assert!(entropy.feedback().is_none());
+        let feedback = entropy.feedback_for_score(Score::Two).unwrap();
+        assert!(!feedback.suggestions().is_empty());
+
+        entropy.score = Score::One;
+        entropy.recompute_feedback();
+        assert_eq!(
+            entropy.feedback(),
+            entropy.feedback_for_score(Score::One).as_ref()
+        );
+        assert!(entropy.feedback().is_some());
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_strict_saturation() {
+        let password = "!QASW@#EDFR$%TGHY^&UJKI*(OL";
+        let options = ZxcvbnOptions::new()
+            .strict_saturation(true)
+            .always_feedback(true);
+        let entropy = zxcvbn_with_options(password, &[], &options);
+        assert_eq!(entropy.guesses, u64::MAX);
         assert_eq!(entropy.score, Score::Four);
+        assert!(entropy.feedback.is_none());
+        assert!(zxcvbn_with_options("password", &[], &options)
+            .feedback
+            .is_some());
+    }
+
+    #[cfg(feature = "store_password")]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_similar_to_previous() {
+        let previous = zxcvbn("Password1!", &[]);
+        assert_eq!(previous.password(), "Password1!");
+        assert!(zxcvbn("Password2!", &[]).similar_to_previous(&previous, 0.8));
+        assert!(zxcvbn("Password1!", &[]).similar_to_previous(&previous, 1.0));
+        assert!(!zxcvbn("correcthorse", &[]).similar_to_previous(&previous, 0.5));
+        assert!(zxcvbn("", &[]).similar_to_previous(&zxcvbn("", &[]), 1.0));
+        assert!(!zxcvbn("", &[]).similar_to_previous(&previous, 0.1));
+
+        assert_eq!(edit_distance(&['a', 'b', 'c'], &['a', 'b', 'c']), 0);
+        assert_eq!(edit_distance(&['k', 'i', 't'], &['s', 'i', 't', 's']), 2);
+        assert_eq!(edit_distance(&[], &['a', 'b']), 2);
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_user_inputs_are_split_into_words() {
+        let sanitized = sanitize_inputs(&["John Smith", "smith", "j.r-r_tolkien"]);
+        assert_eq!(sanitized["john smith"], 1);
+        assert_eq!(sanitized["smith"], 2);
+        assert_eq!(sanitized["j.r-r_tolkien"], 3);
+        assert_eq!(sanitized["john_smith"], 1);
+        assert_eq!(sanitized["johnsmith"], 1);
+        assert_eq!(sanitized["j r r tolkien"], 3);
+        assert_eq!(sanitized["jrrtolkien"], 3);
+        assert_eq!(sanitized["john"], 4);
+        assert_eq!(sanitized["tolkien"], 5);
+        // initials are too short to be matched on their own
+        assert!(!sanitized.contains_key("j"));
+        assert!(!sanitized.contains_key("r"));
+        assert_eq!(sanitized.len(), 14);
+
+        let with_inputs = zxcvbn("john_smith_2024", &["John Smith"]);
+        let without_inputs = zxcvbn("john_smith_2024", &[]);
+        assert!(with_inputs.guesses() < without_inputs.guesses());
+        assert!(with_inputs.score() < without_inputs.score());
+        assert!(
+            zxcvbn("2024qorvath", &["Jane Qorvath"]).guesses()
+                < zxcvbn("2024qorvath", &["Jane"]).guesses()
+        );
     }
 
     #[cfg_attr(not(target_arch = "wasm32"), test)]
@@ -3,24 +3,49 @@ mod char_indexing;
 pub mod patterns;
 
 use self::patterns::*;
-use crate::frequency_lists::DictionaryType;
+use crate::frequency_lists::{DictionaryType, RankedDictionary};
+use crate::options::ZxcvbnOptions;
 use char_indexing::{CharIndexable, CharIndexableStr};
 use fancy_regex::Regex as FancyRegex;
 use itertools::Itertools;
 use regex::Regex;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::ops::Range;
+use std::time::Instant;
 
 /// A match of a predictable pattern in the password.
+///
+/// With the `builder` feature, which is enabled by default, matches can be constructed with
+/// `MatchBuilder`, e.g. to test custom scoring. Unset fields keep their defaults:
+/// an empty token, zero indices and a brute-force pattern.
+///
+/// # Example
+/// ```rust
+/// # #[cfg(feature = "builder")] {
+/// use zxcvbn::matching::patterns::MatchPattern;
+/// use zxcvbn::matching::MatchBuilder;
+///
+/// let m = MatchBuilder::default()
+///     .i(3)
+///     .j(6)
+///     .token("1987".to_string())
+///     .build()
+///     .unwrap();
+/// assert_eq!(m.pattern, MatchPattern::BruteForce);
+/// assert_eq!(m.guesses, None);
+/// # }
+/// ```
 #[derive(Debug, Clone, PartialEq, Default)]
 #[cfg_attr(feature = "builder", derive(Builder))]
 #[cfg_attr(feature = "builder", builder(default))]
 #[cfg_attr(feature = "ser", derive(serde::Serialize))]
 pub struct Match {
-    /// Beginning of the match.
+    /// Beginning of the match, as the index of its first `char` in the password.
     pub i: usize,
-    /// End of the match.
+    /// End of the match, as the index of its last `char` in the password.
     pub j: usize,
-    /// Token that has been matched.
+    /// Token that has been matched, i.e. the `char`s `i..=j` of the password.
     pub token: String,
     /// Pattern type and details used to detect this match.
     #[cfg_attr(feature = "ser", serde(flatten))]
@@ -30,18 +55,379 @@ pub struct Match {
 }
 
 impl Match {
+    /// Get the part of the password that has been matched.
+    ///
+    /// Since `i` and `j` count `char`s rather than bytes, prefer this over slicing the
+    /// password, which panics or gives the wrong text for non-ASCII passwords.
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    /// Get the kind of pattern of the match, e.g. to tally which patterns make passwords weak.
+    ///
+    /// # Example
+    /// ```rust
+    /// use zxcvbn::matching::patterns::PatternKind;
+    /// use zxcvbn::zxcvbn;
+    ///
+    /// let entropy = zxcvbn("p4ssw0rd1987", &[]);
+    /// let kinds: Vec<PatternKind> = entropy.sequence().iter().map(|m| m.kind()).collect();
+    /// assert_eq!(kinds, [PatternKind::L33t, PatternKind::Regex]);
+    /// ```
+    pub fn kind(&self) -> PatternKind {
+        match self.pattern {
+            MatchPattern::Dictionary(ref p) if p.l33t => PatternKind::L33t,
+            MatchPattern::Dictionary(ref p) if p.reversed => PatternKind::Reverse,
+            MatchPattern::Dictionary(_) => PatternKind::Dictionary,
+            MatchPattern::Spatial(_) => PatternKind::Spatial,
+            MatchPattern::Repeat(_) => PatternKind::Repeat,
+            MatchPattern::Sequence(_) => PatternKind::Sequence,
+            MatchPattern::Regex(_) => PatternKind::Regex,
+            MatchPattern::Date(_) => PatternKind::Date,
+            #[cfg(feature = "regex")]
+            MatchPattern::CustomRegex(_) => PatternKind::Regex,
+            MatchPattern::BruteForce => PatternKind::Bruteforce,
+        }
+    }
+
     /// Get the range of the index of the chars that are included in the match.
     pub fn range_inclusive(&self) -> std::ops::RangeInclusive<usize> {
         self.i..=self.j
     }
+
+    /// Get the matched token with its middle characters masked with `*`,
+    /// e.g. `"password"` becomes `"p******d"`.
+    /// Tokens of one or two characters are masked entirely.
+    pub fn display_token(&self) -> String {
+        mask_token(&self.token)
+    }
+
+    /// Get a wrapper around this match whose `Debug` output doesn't leak the matched token,
+    /// e.g. for logging with `dbg!(m.masked())`.
+    pub fn masked(&self) -> MaskedMatch<'_> {
+        MaskedMatch(self)
+    }
+}
+
+/// Mask the middle characters of `token` with `*`, see [`Match::display_token`].
+pub(crate) fn mask_token(token: &str) -> String {
+    let len = token.chars().count();
+    if len <= 2 {
+        return "*".repeat(len);
+    }
+    token
+        .chars()
+        .enumerate()
+        .map(|(k, c)| if k == 0 || k == len - 1 { c } else { '*' })
+        .collect()
+}
+
+/// A match whose `Debug` output masks the matched token using [`Match::display_token`].
+///
+/// Only the kind of pattern is shown, as the pattern details may contain the matched word.
+#[derive(Clone, Copy)]
+pub struct MaskedMatch<'a>(pub &'a Match);
+
+impl fmt::Debug for MaskedMatch<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Match")
+            .field("i", &self.0.i)
+            .field("j", &self.0.j)
+            .field("token", &self.0.display_token())
+            .field("pattern", &self.0.pattern.variant())
+            .field("guesses", &self.0.guesses)
+            .finish()
+    }
+}
+
+/// A keyboard layout used for spatial matching, mapping each key to its adjacent keys.
+///
+/// Each character maps to its neighbors, clockwise, starting with the key to the left.
+/// A neighbor is `None` if there is no key in that direction, and otherwise contains
+/// the unshifted and shifted characters of the key, e.g. `Some("4$")`.
+///
+/// # Example
+/// ```rust
+/// use std::collections::HashMap;
+/// use zxcvbn::matching::AdjacencyGraph;
+///
+/// let mut adjacents = HashMap::new();
+/// adjacents.insert('a', vec![None, Some("zZ"), Some("eE"), Some("qQ"), None, None]);
+/// let azerty = AdjacencyGraph::new("azerty", adjacents);
+/// assert_eq!(azerty.name(), "azerty");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdjacencyGraph<'a> {
+    name: &'a str,
+    adjacents: HashMap<char, Vec<Option<&'a str>>>,
+}
+
+impl<'a> AdjacencyGraph<'a> {
+    /// Create a graph from the adjacent keys of each key.
+    ///
+    /// The `name` is reported in the `graph` of the spatial matches found with this graph.
+    pub fn new(name: &'a str, adjacents: HashMap<char, Vec<Option<&'a str>>>) -> Self {
+        AdjacencyGraph { name, adjacents }
+    }
+
+    /// Name of the graph.
+    pub fn name(&self) -> &str {
+        self.name
+    }
+
+    /// The adjacent keys of each key.
+    pub fn adjacents(&self) -> &HashMap<char, Vec<Option<&'a str>>> {
+        &self.adjacents
+    }
 }
 
+/// A custom matcher, which finds the matches of a pattern in a password.
+/// See [`ZxcvbnOptions::add_matcher`](crate::ZxcvbnOptions::add_matcher).
+pub type MatcherFn = Box<dyn Fn(&str) -> Vec<Match> + Send + Sync>;
+
+#[cfg(test)]
 #[allow(clippy::implicit_hasher)]
 pub(crate) fn omnimatch(password: &str, user_inputs: &HashMap<String, usize>) -> Vec<Match> {
-    let mut matches: Vec<Match> = MATCHERS
+    omnimatch_with_options(
+        password,
+        &user_inputs_dictionary(user_inputs),
+        &ZxcvbnOptions::default(),
+    )
+}
+
+/// Indexes the sanitized user inputs for the dictionary matchers, which is done once for all the
+/// matchers, including those matching the l33t substitutions of the password.
+#[allow(clippy::implicit_hasher)]
+pub(crate) fn user_inputs_dictionary(user_inputs: &HashMap<String, usize>) -> RankedDictionary<'_> {
+    RankedDictionary::from_ranked(user_inputs.iter().map(|(x, &i)| (x.as_str(), i)))
+}
+
+pub(crate) fn omnimatch_with_options(
+    password: &str,
+    user_inputs: &RankedDictionary,
+    options: &ZxcvbnOptions,
+) -> Vec<Match> {
+    // without a deadline, the matching always completes
+    find_matches(password, user_inputs, &[], options, None).unwrap_or_default()
+}
+
+/// Same as `omnimatch_with_options`, but returns `None` if `deadline` passes before all matchers
+/// have run.
+pub(crate) fn omnimatch_before_deadline(
+    password: &str,
+    user_inputs: &RankedDictionary,
+    options: &ZxcvbnOptions,
+    deadline: Instant,
+) -> Option<Vec<Match>> {
+    find_matches(password, user_inputs, &[], options, Some(deadline))
+}
+
+/// Find all matches in `password`, using `extra_graphs` for spatial matching
+/// in addition to the built-in keyboards and keypads, including in the base tokens of repeats.
+///
+/// The spatial matches of the extra graphs are estimated like those of a keyboard.
+#[allow(clippy::implicit_hasher)]
+pub fn omnimatch_with_graphs(
+    password: &str,
+    user_inputs: &HashMap<String, usize>,
+    extra_graphs: &[AdjacencyGraph],
+) -> Vec<Match> {
+    find_matches(
+        password,
+        &user_inputs_dictionary(user_inputs),
+        extra_graphs,
+        &ZxcvbnOptions::default(),
+        None,
+    )
+    .unwrap_or_default()
+}
+
+/// Find all matches in `password`, or `None` if `deadline` passes before the matching completes.
+fn find_matches(
+    password: &str,
+    user_inputs: &RankedDictionary,
+    extra_graphs: &[AdjacencyGraph],
+    options: &ZxcvbnOptions,
+    deadline: Option<Instant>,
+) -> Option<Vec<Match>> {
+    let check_deadline = || match deadline {
+        Some(deadline) if Instant::now() >= deadline => None,
+        _ => Some(()),
+    };
+    let enabled = |kind: PatternKind| !options.disabled_matchers.contains(&kind);
+    // With the `parallel` feature, the matchers run on the rayon thread pool.
+    // Collecting preserves the matcher order, so the output is the same as the sequential path.
+    #[cfg(feature = "parallel")]
+    let mut matches: Vec<Match> = {
+        use rayon::prelude::*;
+        MATCHERS
+            .par_iter()
+            .filter(|(kind, _)| enabled(*kind))
+            .map(|(_, x)| {
+                check_deadline()?;
+                Some(x.get_matches_with_options(password, user_inputs, extra_graphs, options))
+            })
+            .collect::<Option<Vec<Vec<Match>>>>()?
+            .into_iter()
+            .flatten()
+            .collect()
+    };
+    #[cfg(not(feature = "parallel"))]
+    let mut matches: Vec<Match> = {
+        let mut matches = Vec::new();
+        for (_, matcher) in MATCHERS.iter().filter(|(kind, _)| enabled(*kind)) {
+            check_deadline()?;
+            matches.extend(matcher.get_matches_with_options(
+                password,
+                user_inputs,
+                extra_graphs,
+                options,
+            ));
+        }
+        matches
+    };
+    check_deadline()?;
+    let password_len = password.chars().count();
+    for matcher in &options.matchers {
+        matches.extend(
+            (matcher.0)(password)
+                .into_iter()
+                .filter(|m| m.i <= m.j && m.j < password_len),
+        );
+    }
+    if enabled(PatternKind::Regex) {
+        #[cfg(feature = "regex")]
+        matches.extend(regex_match(password, &options.regexes));
+        if options.common_affixes {
+            matches.extend(common_affix_match_helper(password));
+        }
+    }
+    check_deadline()?;
+    if enabled(PatternKind::Dictionary) {
+        if let Some(ref phrases) = options.phrases {
+            let phrase_matches = phrase_match_helper(password, &matches, phrases);
+            matches.extend(phrase_matches);
+        }
+        if let Some(ref wordlist) = options.diceware {
+            matches.extend(diceware_match_helper(password, wordlist));
+        }
+    }
+    sort_matches(&mut matches);
+    Some(matches)
+}
+
+/// The separators between the words of diceware passphrases.
+const DICEWARE_SEPARATORS: [char; 4] = [' ', '-', '_', '.'];
+
+/// Matches runs of at least two words of the diceware `wordlist`, all joined by the same separator.
+fn diceware_match_helper(password: &str, wordlist: &RankedDictionary) -> Vec<Match> {
+    let chars: Vec<char> = password.chars().collect();
+    let mut matches = Vec::new();
+    for &separator in &DICEWARE_SEPARATORS {
+        if !chars.contains(&separator) {
+            continue;
+        }
+        // the segments between separators as (i, j, rank), or None if they aren't words of the list
+        let mut words: Vec<Option<(usize, usize, usize)>> = Vec::new();
+        let mut start = 0;
+        for end in 0..=chars.len() {
+            if end < chars.len() && chars[end] != separator {
+                continue;
+            }
+            let segment: String = chars[start..end]
+                .iter()
+                .flat_map(|c| c.to_lowercase())
+                .collect();
+            let rank = Some(&segment)
+                .filter(|segment| !segment.is_empty())
+                .and_then(|segment| wordlist.get(segment));
+            words.push(rank.map(|rank| (start, end - 1, rank)));
+            start = end + 1;
+        }
+        for (first, &word) in words.iter().enumerate() {
+            let (i, rank) = match word {
+                Some((i, _, rank)) => (i, rank),
+                None => continue,
+            };
+            for (count, &word) in words[first..].iter().enumerate().skip(1) {
+                let j = match word {
+                    Some((_, j, _)) => j,
+                    None => break,
+                };
+                let token: String = chars[i..=j].iter().collect();
+                // every passphrase of as many words of the list is as likely
+                let passphrases = (wordlist.len() as u64).saturating_pow(count as u32 + 1);
+                matches.push(Match {
+                    pattern: MatchPattern::Dictionary(DictionaryPattern {
+                        matched_word: token.to_lowercase(),
+                        rank,
+                        dictionary_name: DictionaryType::Diceware,
+                        ..DictionaryPattern::default()
+                    }),
+                    i,
+                    j,
+                    token,
+                    guesses: Some(passphrases),
+                });
+            }
+        }
+    }
+    matches
+}
+
+/// Maximum number of adjacent dictionary words which are joined into a phrase.
+const MAX_PHRASE_WORDS: usize = 4;
+
+/// Matches runs of adjacent dictionary matches which together form one of `phrases`.
+fn phrase_match_helper(
+    password: &str,
+    matches: &[Match],
+    phrases: &RankedDictionary,
+) -> Vec<Match> {
+    let words: Vec<&Match> = matches
         .iter()
-        .flat_map(|x| x.get_matches(password, user_inputs))
+        .filter(|m| matches!(m.pattern, MatchPattern::Dictionary(_)))
         .collect();
+    let password_lower_string = password.to_lowercase();
+    #[cfg(feature = "zeroize")]
+    let password_lower_string = crate::zeroizing::ZeroizingString(password_lower_string);
+    let password_lower = CharIndexableStr::from(&*password_lower_string);
+
+    let mut phrase_matches = Vec::new();
+    let mut found = HashSet::new();
+    // runs of words as (i, j, number of words)
+    let mut runs: Vec<(usize, usize, usize)> = words.iter().map(|m| (m.i, m.j, 1)).collect();
+    while let Some((i, j, word_count)) = runs.pop() {
+        if word_count >= 2 && found.insert((i, j)) {
+            let phrase = password_lower.char_index(i..j + 1);
+            if let Some(rank) = phrases.get(phrase) {
+                phrase_matches.push(Match {
+                    pattern: MatchPattern::Dictionary(DictionaryPattern {
+                        matched_word: phrase.to_string(),
+                        rank,
+                        dictionary_name: DictionaryType::Phrases,
+                        ..DictionaryPattern::default()
+                    }),
+                    i,
+                    j,
+                    token: password.chars().take(j + 1).skip(i).collect(),
+                    ..Match::default()
+                });
+            }
+        }
+        if word_count < MAX_PHRASE_WORDS {
+            runs.extend(
+                words
+                    .iter()
+                    .filter(|m| m.i == j + 1)
+                    .map(|m| (i, m.j, word_count + 1)),
+            );
+        }
+    }
+    phrase_matches
+}
+
+fn sort_matches(matches: &mut [Match]) {
     matches.sort_unstable_by(|a, b| {
         let range1 = a.range_inclusive();
         let range2 = b.range_inclusive();
@@ -50,7 +436,6 @@ pub(crate) fn omnimatch(password: &str, user_inputs: &HashMap<String, usize>) ->
             .cmp(range2.start())
             .then_with(|| range1.end().cmp(range2.end()))
     });
-    matches
 }
 
 lazy_static! {
@@ -81,38 +466,64 @@ lazy_static! {
 }
 
 trait Matcher: Send + Sync {
-    fn get_matches(&self, password: &str, user_inputs: &HashMap<String, usize>) -> Vec<Match>;
+    fn get_matches_with_options(
+        &self,
+        password: &str,
+        user_inputs: &RankedDictionary,
+        extra_graphs: &[AdjacencyGraph],
+        options: &ZxcvbnOptions,
+    ) -> Vec<Match>;
+
+    #[cfg(test)]
+    fn get_matches(&self, password: &str, user_inputs: &HashMap<String, usize>) -> Vec<Match> {
+        self.get_matches_with_options(
+            password,
+            &user_inputs_dictionary(user_inputs),
+            &[],
+            &ZxcvbnOptions::default(),
+        )
+    }
 }
 
 lazy_static! {
-    static ref MATCHERS: [Box<dyn Matcher>; 8] = [
-        Box::new(DictionaryMatch {}),
-        Box::new(ReverseDictionaryMatch {}),
-        Box::new(L33tMatch {}),
-        Box::new(SpatialMatch {}),
-        Box::new(RepeatMatch {}),
-        Box::new(SequenceMatch {}),
-        Box::new(RegexMatch {}),
-        Box::new(DateMatch {}),
+    static ref MATCHERS: [(PatternKind, Box<dyn Matcher>); 8] = [
+        (PatternKind::Dictionary, Box::new(DictionaryMatch {})),
+        (PatternKind::Reverse, Box::new(ReverseDictionaryMatch {})),
+        (PatternKind::L33t, Box::new(L33tMatch {})),
+        (PatternKind::Spatial, Box::new(SpatialMatch {})),
+        (PatternKind::Repeat, Box::new(RepeatMatch {})),
+        (PatternKind::Sequence, Box::new(SequenceMatch {})),
+        (PatternKind::Regex, Box::new(RegexMatch {})),
+        (PatternKind::Date, Box::new(DateMatch {})),
     ];
 }
 
 struct DictionaryMatch {}
 
 impl Matcher for DictionaryMatch {
-    fn get_matches(&self, password: &str, user_inputs: &HashMap<String, usize>) -> Vec<Match> {
+    fn get_matches_with_options(
+        &self,
+        password: &str,
+        user_inputs: &RankedDictionary,
+        _extra_graphs: &[AdjacencyGraph],
+        options: &ZxcvbnOptions,
+    ) -> Vec<Match> {
         let password_lower_string = password.to_lowercase();
-        let password_lower = CharIndexableStr::from(password_lower_string.as_str());
+        #[cfg(feature = "zeroize")]
+        let password_lower_string = crate::zeroizing::ZeroizingString(password_lower_string);
+        let password_lower = CharIndexableStr::from(&*password_lower_string);
 
         let do_trials = move |matches: &mut Vec<Match>,
                               password: &str,
                               dictionary_name: DictionaryType,
-                              ranked_dict: &HashMap<&str, usize>| {
+                              ranked_dict: &RankedDictionary| {
             let len = password.chars().count();
             for i in 0..len {
+                let mut candidates = ranked_dict.starting_with(password_lower.char_index(i..i + 1));
                 for j in i..len {
                     let word = password_lower.char_index(i..j + 1);
-                    if let Some(rank) = ranked_dict.get(word).cloned() {
+                    let (rank, longer_words) = ranked_dict.find(word, candidates);
+                    if let Some(rank) = rank {
                         let pattern = MatchPattern::Dictionary(DictionaryPattern {
                             matched_word: word.to_string(),
                             rank,
@@ -127,33 +538,83 @@ impl Matcher for DictionaryMatch {
                             ..Match::default()
                         });
                     }
+                    if longer_words.is_empty() {
+                        // no longer word starting at i is in the dictionary
+                        break;
+                    }
+                    candidates = longer_words;
                 }
             }
         };
 
         let mut matches = Vec::new();
 
-        for (dictionary_name, ranked_dict) in super::frequency_lists::RANKED_DICTIONARIES.iter() {
-            do_trials(&mut matches, password, *dictionary_name, ranked_dict);
+        for (dictionary_name, ranked_dict) in builtin_dictionaries(options) {
+            do_trials(&mut matches, password, dictionary_name, ranked_dict);
+        }
+        for (name, ranked_dict) in &options.extra_dictionaries {
+            do_trials(
+                &mut matches,
+                password,
+                DictionaryType::Custom(name),
+                ranked_dict,
+            );
         }
+        super::frequency_lists::with_organization_words(|ranked_dict| {
+            do_trials(
+                &mut matches,
+                password,
+                DictionaryType::Organization,
+                ranked_dict,
+            )
+        });
         do_trials(
             &mut matches,
             password,
             DictionaryType::UserInputs,
-            &user_inputs.iter().map(|(x, &i)| (x.as_str(), i)).collect(),
+            user_inputs,
         );
 
         matches
     }
 }
 
+/// The built-in dictionaries which are not disabled in `options`, with the passwords
+/// replaced by the list of `options` if it has one.
+pub(crate) fn builtin_dictionaries(
+    options: &ZxcvbnOptions,
+) -> impl Iterator<Item = (DictionaryType, &RankedDictionary<'static>)> + '_ {
+    super::frequency_lists::RANKED_DICTIONARIES
+        .iter()
+        .filter(move |(dictionary_name, _)| {
+            !options.disabled_dictionaries.contains(dictionary_name)
+        })
+        .map(
+            move |(&dictionary_name, ranked_dict)| match options.password_list {
+                Some(ref password_list) if dictionary_name == DictionaryType::Passwords => {
+                    (dictionary_name, password_list)
+                }
+                _ => (dictionary_name, ranked_dict),
+            },
+        )
+}
+
 struct ReverseDictionaryMatch {}
 
 impl Matcher for ReverseDictionaryMatch {
-    fn get_matches(&self, password: &str, user_inputs: &HashMap<String, usize>) -> Vec<Match> {
-        let reversed_password = password.chars().rev().collect::<String>();
+    fn get_matches_with_options(
+        &self,
+        password: &str,
+        user_inputs: &RankedDictionary,
+        _extra_graphs: &[AdjacencyGraph],
+        options: &ZxcvbnOptions,
+    ) -> Vec<Match> {
+        let mut reversed_password = String::with_capacity(password.len());
+        reversed_password.extend(password.chars().rev());
+        #[cfg(feature = "zeroize")]
+        let reversed_password = crate::zeroizing::ZeroizingString(reversed_password);
         (DictionaryMatch {})
-            .get_matches(&reversed_password, user_inputs)
+            .get_matches_with_options(&reversed_password, user_inputs, &[], options)
             .into_iter()
             .map(|mut m| {
                 // Reverse token back
@@ -173,14 +634,32 @@ impl Matcher for ReverseDictionaryMatch {
 struct L33tMatch {}
 
 impl Matcher for L33tMatch {
-    fn get_matches(&self, password: &str, user_inputs: &HashMap<String, usize>) -> Vec<Match> {
+    fn get_matches_with_options(
+        &self,
+        password: &str,
+        user_inputs: &RankedDictionary,
+        _extra_graphs: &[AdjacencyGraph],
+        options: &ZxcvbnOptions,
+    ) -> Vec<Match> {
+        let subtable = relevant_l33t_subtable(password);
+        if subtable.is_empty() {
+            // None of the characters of the password can be un-l33ted
+            return Vec::new();
+        }
         let mut matches = Vec::new();
-        for sub in enumerate_l33t_replacements(&relevant_l33t_subtable(password)) {
+        for sub in enumerate_l33t_replacements(&subtable) {
             if sub.is_empty() {
                 break;
             }
             let subbed_password = translate(password, &sub);
-            for mut m4tch in (DictionaryMatch {}).get_matches(&subbed_password, user_inputs) {
+            #[cfg(feature = "zeroize")]
+            let subbed_password = crate::zeroizing::ZeroizingString(subbed_password);
+            for mut m4tch in (DictionaryMatch {}).get_matches_with_options(
+                &subbed_password,
+                user_inputs,
+                &[],
+                options,
+            ) {
                 let token = password
                     .chars()
                     .take(m4tch.j + 1)
@@ -206,6 +685,7 @@ impl Matcher for L33tMatch {
                     pattern.sub_display = Some(
                         match_sub
                             .iter()
+                            .sorted()
                             .map(|(k, v)| format!("{} -> {}", k, v))
                             .join(", "),
                     );
@@ -214,6 +694,7 @@ impl Matcher for L33tMatch {
                 matches.push(m4tch);
             }
         }
+        matches.extend(mixed_l33t_matches(password, user_inputs, options));
         matches
             .into_iter()
             .filter(|x| !x.token.is_empty())
@@ -221,13 +702,181 @@ impl Matcher for L33tMatch {
     }
 }
 
-fn translate(string: &str, chr_map: &HashMap<char, char>) -> String {
-    string
-        .chars()
-        .map(|c| *chr_map.get(&c).unwrap_or(&c))
+/// Returns the letters which `chr` is a l33t substitution for, in alphabetical order.
+fn l33t_letters(chr: char) -> Vec<char> {
+    L33T_TABLE
+        .iter()
+        .filter(|(_, subs)| subs.contains(&chr))
+        .map(|(&letter, _)| letter)
+        .sorted()
         .collect()
 }
 
+/// Matches tokens in which a character substitutes different letters, e.g. `s1mp1e`,
+/// where `1` is both an `i` and an `l`. The substitutions enumerated for the whole password
+/// map each character to a single letter, so they can't un-l33t these tokens.
+fn mixed_l33t_matches(
+    password: &str,
+    user_inputs: &RankedDictionary,
+    options: &ZxcvbnOptions,
+) -> Vec<Match> {
+    let chars: Vec<char> = password.chars().collect();
+    let letters: Vec<Vec<char>> = chars.iter().map(|&c| l33t_letters(c)).collect();
+    if !(0..chars.len())
+        .tuple_combinations()
+        .any(|(a, b)| chars[a] == chars[b] && letters[a].len() > 1)
+    {
+        // no character occurs twice with several letters to stand for
+        return Vec::new();
+    }
+    let mut dictionaries: Vec<(DictionaryType, &RankedDictionary)> =
+        builtin_dictionaries(options).collect();
+    for (name, ranked_dict) in &options.extra_dictionaries {
+        dictionaries.push((DictionaryType::Custom(name), ranked_dict));
+    }
+    dictionaries.push((DictionaryType::UserInputs, user_inputs));
+
+    let search = |dictionaries: &[(DictionaryType, &RankedDictionary)]| {
+        let mut search = MixedL33tSearch {
+            chars: &chars,
+            letters: &letters,
+            dictionaries,
+            matches: Vec::new(),
+        };
+        let ranges: Vec<_> = dictionaries.iter().map(|(_, d)| d.all()).collect();
+        // lowercasing at most doubles the length in bytes, so the token is never reallocated
+        let mut subbed = String::with_capacity(2 * password.len());
+        for i in 0..chars.len() {
+            search.extend(i, i, &mut subbed, &mut Vec::new(), &ranges);
+        }
+        #[cfg(feature = "zeroize")]
+        drop(crate::zeroizing::ZeroizingString(subbed));
+        search.matches
+    };
+    super::frequency_lists::with_organization_words(|ranked_dict| {
+        let mut dictionaries = dictionaries.clone();
+        dictionaries.push((DictionaryType::Organization, ranked_dict));
+        search(&dictionaries)
+    })
+    .unwrap_or_else(|| search(&dictionaries))
+}
+
+/// Depth-first search of the un-l33ted tokens starting at a position of the password,
+/// which extends a token only while some dictionary has words starting with it.
+struct MixedL33tSearch<'a, 'b> {
+    chars: &'a [char],
+    letters: &'a [Vec<char>],
+    dictionaries: &'a [(DictionaryType, &'b RankedDictionary<'b>)],
+    matches: Vec<Match>,
+}
+
+impl MixedL33tSearch<'_, '_> {
+    /// Tries each way of un-l33ting the character at `k`, for the tokens starting at `i`.
+    /// `ranges` are the entries of each dictionary which start with `subbed`.
+    fn extend(
+        &mut self,
+        i: usize,
+        k: usize,
+        subbed: &mut String,
+        sub: &mut Vec<(char, char)>,
+        ranges: &[Range<usize>],
+    ) {
+        if k == self.chars.len() {
+            return;
+        }
+        let chr = self.chars[k];
+        let candidates: Vec<Option<char>> = if self.letters[k].is_empty() {
+            vec![None]
+        } else {
+            self.letters[k].iter().map(|&letter| Some(letter)).collect()
+        };
+        for letter in candidates {
+            let subbed_len = subbed.len();
+            match letter {
+                Some(letter) => {
+                    subbed.push(letter);
+                    sub.push((chr, letter));
+                }
+                None => subbed.extend(chr.to_lowercase()),
+            }
+            let mut next_ranges = Vec::with_capacity(ranges.len());
+            let mut any_longer = false;
+            for (&(dictionary_name, ranked_dict), range) in self.dictionaries.iter().zip(ranges) {
+                if range.is_empty() {
+                    next_ranges.push(range.clone());
+                    continue;
+                }
+                let (rank, longer_words) = ranked_dict.find(subbed, range.clone());
+                if let Some(rank) = rank {
+                    if k > i && is_mixed_sub(sub) {
+                        self.push_match(i, k, subbed, sub, dictionary_name, rank);
+                    }
+                }
+                any_longer |= !longer_words.is_empty();
+                next_ranges.push(longer_words);
+            }
+            if any_longer {
+                self.extend(i, k + 1, subbed, sub, &next_ranges);
+            }
+            subbed.truncate(subbed_len);
+            if letter.is_some() {
+                sub.pop();
+            }
+        }
+    }
+
+    fn push_match(
+        &mut self,
+        i: usize,
+        j: usize,
+        subbed: &str,
+        sub: &[(char, char)],
+        dictionary_name: DictionaryType,
+        rank: usize,
+    ) {
+        let sub: Vec<(char, char)> = sub.iter().cloned().unique().sorted().collect();
+        let sub_display = sub
+            .iter()
+            .map(|(k, v)| format!("{} -> {}", k, v))
+            .join(", ");
+        // the scoring takes a single letter per character: keep the first one
+        let mut match_sub = HashMap::new();
+        for &(chr, letter) in &sub {
+            match_sub.entry(chr).or_insert(letter);
+        }
+        self.matches.push(Match {
+            i,
+            j,
+            token: self.chars[i..=j].iter().collect(),
+            pattern: MatchPattern::Dictionary(DictionaryPattern {
+                matched_word: subbed.to_string(),
+                rank,
+                dictionary_name,
+                l33t: true,
+                sub: Some(match_sub),
+                sub_display: Some(sub_display),
+                ..DictionaryPattern::default()
+            }),
+            ..Match::default()
+        });
+    }
+}
+
+/// Whether a character substitutes different letters in `sub`.
+/// The other substitutions are already tried by the substitutions of the whole password.
+fn is_mixed_sub(sub: &[(char, char)]) -> bool {
+    sub.iter()
+        .tuple_combinations()
+        .any(|(a, b)| a.0 == b.0 && a.1 != b.1)
+}
+
+fn translate(string: &str, chr_map: &HashMap<char, char>) -> String {
+    // the substitutions are ASCII, so the translation is never reallocated
+    let mut translated = String::with_capacity(string.len());
+    translated.extend(string.chars().map(|c| *chr_map.get(&c).unwrap_or(&c)));
+    translated
+}
+
 fn relevant_l33t_subtable(password: &str) -> HashMap<char, Vec<char>> {
     let password_chars: Vec<char> = password.chars().collect();
     let mut subtable: HashMap<char, Vec<char>> = HashMap::new();
@@ -301,10 +950,21 @@ fn enumerate_l33t_replacements(table: &HashMap<char, Vec<char>>) -> Vec<HashMap<
 struct SpatialMatch {}
 
 impl Matcher for SpatialMatch {
-    fn get_matches(&self, password: &str, _user_inputs: &HashMap<String, usize>) -> Vec<Match> {
+    fn get_matches_with_options(
+        &self,
+        password: &str,
+        _user_inputs: &RankedDictionary,
+        extra_graphs: &[AdjacencyGraph],
+        _options: &ZxcvbnOptions,
+    ) -> Vec<Match> {
         GRAPHS
             .iter()
             .flat_map(|(graph_name, graph)| spatial_match_helper(password, graph, graph_name))
+            .chain(
+                extra_graphs.iter().flat_map(|graph| {
+                    spatial_match_helper(password, graph.adjacents(), graph.name())
+                }),
+            )
             .collect()
     }
 }
@@ -330,13 +990,10 @@ fn spatial_match_helper(
         let mut j = i + 1;
         let mut last_direction = None;
         let mut turns = 0;
-        let mut shifted_count = if ["qwerty", "dvorak"].contains(&graph_name)
-            && SHIFTED_CHARS.contains(&password.chars().nth(i).unwrap())
-        {
-            1
-        } else {
-            0
-        };
+        let mut last_shifted = !["keypad", "mac_keypad"].contains(&graph_name)
+            && SHIFTED_CHARS.contains(&password.chars().nth(i).unwrap());
+        let mut shifted_count = usize::from(last_shifted);
+        let mut alternating_shift = true;
         loop {
             let prev_char = password.chars().nth(j - 1).unwrap();
             let mut found = false;
@@ -352,13 +1009,16 @@ fn spatial_match_helper(
                         if let Some(adj_position) = adj.find(cur_char) {
                             found = true;
                             found_direction = cur_direction;
-                            if adj_position == 1 {
-                                // index 1 in the adjacency means the key is shifted,
-                                // 0 means unshifted: A vs a, % vs 5, etc.
-                                // for example, 'q' is adjacent to the entry '2@'.
-                                // @ is shifted w/ index 1, 2 is unshifted.
+                            // index 1 in the adjacency means the key is shifted,
+                            // 0 means unshifted: A vs a, % vs 5, etc.
+                            // for example, 'q' is adjacent to the entry '2@'.
+                            // @ is shifted w/ index 1, 2 is unshifted.
+                            let shifted = adj_position == 1;
+                            if shifted {
                                 shifted_count += 1;
                             }
+                            alternating_shift &= shifted != last_shifted;
+                            last_shifted = shifted;
                             if last_direction != Some(found_direction) {
                                 // adding a turn is correct even in the initial case when last_direction is null:
                                 // every spatial pattern starts with a turn.
@@ -381,6 +1041,7 @@ fn spatial_match_helper(
                         graph: graph_name.to_string(),
                         turns,
                         shifted_count,
+                        alternating_shift,
                     });
                     matches.push(Match {
                         pattern,
@@ -401,7 +1062,13 @@ fn spatial_match_helper(
 struct RepeatMatch {}
 
 impl Matcher for RepeatMatch {
-    fn get_matches(&self, password: &str, user_inputs: &HashMap<String, usize>) -> Vec<Match> {
+    fn get_matches_with_options(
+        &self,
+        password: &str,
+        user_inputs: &RankedDictionary,
+        extra_graphs: &[AdjacencyGraph],
+        options: &ZxcvbnOptions,
+    ) -> Vec<Match> {
         lazy_static! {
             static ref GREEDY_REGEX: FancyRegex = FancyRegex::new(r"(.+)\1+").unwrap();
             static ref LAZY_REGEX: FancyRegex = FancyRegex::new(r"(.+?)\1+").unwrap();
@@ -454,21 +1121,13 @@ impl Matcher for RepeatMatch {
                 last_index + token[..m.start()].chars().count(),
                 last_index + token[..m.end()].chars().count() - 1,
             );
-            // recursively match and score the base string
-            let base_analysis = super::scoring::most_guessable_match_sequence(
+            let pattern = MatchPattern::Repeat(best_repeat_pattern(
+                m.as_str(),
                 &base_token,
-                &omnimatch(&base_token, user_inputs),
-                false,
-            );
-            let base_matches = base_analysis.sequence;
-            let base_guesses = base_analysis.guesses;
-            let pattern = MatchPattern::Repeat(RepeatPattern {
-                repeat_count: m4tch.get(0).unwrap().as_str().chars().count()
-                    / base_token.chars().count(),
-                base_token,
-                base_guesses,
-                base_matches,
-            });
+                user_inputs,
+                extra_graphs,
+                options,
+            ));
             matches.push(Match {
                 pattern,
                 i,
@@ -482,6 +1141,54 @@ impl Matcher for RepeatMatch {
     }
 }
 
+/// Scores every base token which `repeated` is a repetition of, i.e. the repetitions
+/// of `shortest_base` which tile it, and returns the pattern with the fewest guesses.
+///
+/// The shortest base token usually wins, but a longer one is cheaper when it is itself
+/// a common password, e.g. `123123` in `123123123123`.
+fn best_repeat_pattern(
+    repeated: &str,
+    shortest_base: &str,
+    user_inputs: &RankedDictionary,
+    extra_graphs: &[AdjacencyGraph],
+    options: &ZxcvbnOptions,
+) -> RepeatPattern {
+    let repeated_len = repeated.chars().count();
+    let shortest_len = shortest_base.chars().count();
+    let mut best: Option<RepeatPattern> = None;
+    for base_len in (shortest_len..repeated_len).step_by(shortest_len) {
+        if repeated_len % base_len != 0 {
+            continue;
+        }
+        let base_token: String = repeated.chars().take(base_len).collect();
+        // recursively match and score the base string
+        let base_matches =
+            find_matches(&base_token, user_inputs, extra_graphs, options, None).unwrap_or_default();
+        let base_analysis = super::scoring::most_guessable_match_sequence_with_options(
+            &base_token,
+            &base_matches,
+            false,
+            options,
+            &[],
+        );
+        let candidate = RepeatPattern {
+            repeat_count: repeated_len / base_len,
+            base_token,
+            base_guesses: base_analysis.guesses,
+            base_matches: base_analysis.sequence,
+        };
+        let guesses = |p: &RepeatPattern| p.base_guesses.saturating_mul(p.repeat_count as u64);
+        // on ties, keep the shorter base token
+        if best
+            .as_ref()
+            .map_or(true, |b| guesses(&candidate) < guesses(b))
+        {
+            best = Some(candidate);
+        }
+    }
+    best.unwrap()
+}
+
 const MAX_DELTA: i32 = 5;
 
 /// Identifies sequences by looking for repeated differences in unicode codepoint.
@@ -499,7 +1206,13 @@ const MAX_DELTA: i32 = 5;
 struct SequenceMatch {}
 
 impl Matcher for SequenceMatch {
-    fn get_matches(&self, password: &str, _user_inputs: &HashMap<String, usize>) -> Vec<Match> {
+    fn get_matches_with_options(
+        &self,
+        password: &str,
+        _user_inputs: &RankedDictionary,
+        _extra_graphs: &[AdjacencyGraph],
+        options: &ZxcvbnOptions,
+    ) -> Vec<Match> {
         fn update(i: usize, j: usize, delta: i32, password: &str, matches: &mut Vec<Match>) {
             let delta_abs = delta.abs();
             if (j - i > 1 || delta_abs == 1) && (0 < delta_abs && delta_abs <= MAX_DELTA) {
@@ -520,6 +1233,7 @@ impl Matcher for SequenceMatch {
                     sequence_name,
                     sequence_space,
                     ascending: delta > 0,
+                    element_count: None,
                 });
                 matches.push(Match {
                     pattern,
@@ -532,6 +1246,9 @@ impl Matcher for SequenceMatch {
         }
 
         let mut matches = Vec::new();
+        for &(name, elements) in &options.sequences {
+            matches.extend(custom_sequence_match_helper(password, name, elements));
+        }
 
         let password_len = password.chars().count();
         if password_len <= 1 {
@@ -561,10 +1278,93 @@ impl Matcher for SequenceMatch {
     }
 }
 
+/// Minimum number of consecutive elements of a custom sequence to match.
+const MIN_CUSTOM_SEQUENCE_LEN: usize = 3;
+
+/// Matches the maximal runs of consecutive `elements` of a custom sequence in `password`.
+fn custom_sequence_match_helper(
+    password: &str,
+    sequence_name: &'static str,
+    elements: &[&str],
+) -> Vec<Match> {
+    let chars: Vec<char> = password.chars().collect();
+    let elements: Vec<Vec<char>> = elements.iter().map(|e| e.chars().collect()).collect();
+    // the end of element `e` if it occurs at `pos`
+    let element_at = |pos: usize, e: usize| -> Option<usize> {
+        let element = &elements[e];
+        if !element.is_empty() && chars[pos..].starts_with(element) {
+            Some(pos + element.len())
+        } else {
+            None
+        }
+    };
+    let next_element = |e: usize, ascending: bool| -> Option<usize> {
+        if ascending {
+            Some(e + 1).filter(|&next| next < elements.len())
+        } else {
+            e.checked_sub(1)
+        }
+    };
+
+    let mut matches = Vec::new();
+    let mut found = HashSet::new();
+    for i in 0..chars.len() {
+        for start in 0..elements.len() {
+            let first_end = match element_at(i, start) {
+                Some(end) => end,
+                None => continue,
+            };
+            for &ascending in &[true, false] {
+                // only keep maximal runs, which don't continue an element ending at i
+                let previous = next_element(start, !ascending);
+                let continues_previous = previous.map_or(false, |p| {
+                    let len = elements[p].len();
+                    len > 0 && i >= len && chars[i - len..i] == elements[p][..]
+                });
+                if continues_previous {
+                    continue;
+                }
+                let (mut e, mut end, mut count) = (start, first_end, 1);
+                while let Some(next) = next_element(e, ascending) {
+                    match element_at(end, next) {
+                        Some(next_end) => {
+                            e = next;
+                            end = next_end;
+                            count += 1;
+                        }
+                        None => break,
+                    }
+                }
+                if count >= MIN_CUSTOM_SEQUENCE_LEN && found.insert((i, end, ascending)) {
+                    matches.push(Match {
+                        pattern: MatchPattern::Sequence(SequencePattern {
+                            sequence_name,
+                            sequence_space: u8::try_from(elements.len()).unwrap_or(u8::MAX),
+                            ascending,
+                            element_count: Some(count),
+                        }),
+                        i,
+                        j: end - 1,
+                        token: chars[i..end].iter().collect(),
+                        ..Match::default()
+                    });
+                }
+            }
+        }
+    }
+    matches
+}
+
 struct RegexMatch {}
 
 impl Matcher for RegexMatch {
-    fn get_matches(&self, password: &str, _user_inputs: &HashMap<String, usize>) -> Vec<Match> {
+    fn get_matches_with_options(
+        &self,
+        password: &str,
+        _user_inputs: &RankedDictionary,
+        _extra_graphs: &[AdjacencyGraph],
+        _options: &ZxcvbnOptions,
+    ) -> Vec<Match> {
         let mut matches = Vec::new();
         for (&name, regex) in REGEXES.iter() {
             for capture in regex.captures_iter(password) {
@@ -593,6 +1393,103 @@ impl Matcher for RegexMatch {
     }
 }
 
+/// Find the non-overlapping matches of each regular expression of `patterns` in `password`,
+/// e.g. to detect employee IDs or internal project codes. The matches are
+/// [`MatchPattern::CustomRegex`] matches with the source of their regular expression, and
+/// are guessed with the number of guesses associated with their regular expression.
+///
+/// Regular expressions added with [`ZxcvbnOptions::with_regex`] are matched along with the
+/// built-in patterns.
+///
+/// # Example
+/// ```rust
+/// use regex::Regex;
+/// use zxcvbn::matching::patterns::MatchPattern;
+/// use zxcvbn::matching::regex_match;
+///
+/// let employee_id = Regex::new(r"EMP\d{6}").unwrap();
+/// let matches = regex_match("EMP123456!", &[(employee_id, 1000)]);
+/// assert_eq!(matches[0].token, "EMP123456");
+/// assert_eq!(matches[0].guesses, Some(1000));
+/// assert!(matches!(matches[0].pattern, MatchPattern::CustomRegex(ref p) if p.pattern_str == r"EMP\d{6}"));
+/// ```
+#[cfg(feature = "regex")]
+pub fn regex_match(password: &str, patterns: &[(Regex, u64)]) -> Vec<Match> {
+    let mut matches = Vec::new();
+    for (regex, guesses) in patterns {
+        for m in regex.find_iter(password) {
+            if m.as_str().is_empty() {
+                continue;
+            }
+            let pattern = MatchPattern::CustomRegex(CustomRegexPattern {
+                pattern_str: regex.as_str().to_string(),
+            });
+            let (i, j) = (
+                password[..m.start()].chars().count(),
+                password[..m.end()].chars().count() - 1,
+            );
+            matches.push(Match {
+                pattern,
+                token: m.as_str().to_string(),
+                i,
+                j,
+                guesses: Some(*guesses),
+            });
+        }
+    }
+    matches
+}
+
+/// The `regex_name` of the matches of common affixes, see
+/// [`ZxcvbnOptions::common_affixes`].
+pub const COMMON_AFFIX_REGEX_NAME: &str = "common_affix";
+
+/// Strings which are commonly added before or after a word, from the most to the least common.
+/// Years aren't listed, as they are matched as recent years.
+const COMMON_AFFIXES: &[&str] = &[
+    "1", "123", "12", "!", "2", "1234", "13", "11", "01", "7", "69", "22", "21", "3", "007", "00",
+    "99", "!!", "1!", "123!", "!@#", "@", "#1", "*",
+];
+
+/// Matches the common affixes at the start and at the end of `password`, but not a whole
+/// password. An attacker tries each affix at either end, so an affix takes twice its rank
+/// in `COMMON_AFFIXES` guesses, but no fewer than the other matches of part of a password.
+fn common_affix_match_helper(password: &str) -> Vec<Match> {
+    let password_len = password.chars().count();
+    let mut matches = Vec::new();
+    for (rank, &affix) in (1..).zip(COMMON_AFFIXES) {
+        let affix_len = affix.chars().count();
+        if affix_len >= password_len {
+            continue;
+        }
+        let min_guesses = if affix_len == 1 {
+            super::scoring::MIN_SUBMATCH_GUESSES_SINGLE_CHAR
+        } else {
+            super::scoring::MIN_SUBMATCH_GUESSES_MULTI_CHAR
+        };
+        let mut positions = Vec::with_capacity(2);
+        if password.starts_with(affix) {
+            positions.push(0);
+        }
+        if password.ends_with(affix) {
+            positions.push(password_len - affix_len);
+        }
+        for i in positions {
+            matches.push(Match {
+                pattern: MatchPattern::Regex(RegexPattern {
+                    regex_name: COMMON_AFFIX_REGEX_NAME,
+                    regex_match: vec![affix.to_string()],
+                }),
+                token: affix.to_string(),
+                i,
+                j: i + affix_len - 1,
+                guesses: Some((2 * rank).max(min_guesses)),
+            });
+        }
+    }
+    matches
+}
+
 lazy_static! {
     static ref REGEXES: HashMap<&'static str, Regex> = {
         let mut table = HashMap::with_capacity(1);
@@ -622,9 +1519,18 @@ lazy_static! {
 struct DateMatch {}
 
 impl Matcher for DateMatch {
-    fn get_matches(&self, password: &str, _user_inputs: &HashMap<String, usize>) -> Vec<Match> {
+    fn get_matches_with_options(
+        &self,
+        password: &str,
+        _user_inputs: &RankedDictionary,
+        _extra_graphs: &[AdjacencyGraph],
+        options: &ZxcvbnOptions,
+    ) -> Vec<Match> {
         let mut matches = Vec::new();
         let char_indexable = CharIndexableStr::from(password);
+        let pivot = options
+            .two_digit_year_pivot
+            .unwrap_or(DEFAULT_TWO_DIGIT_YEAR_PIVOT);
 
         let password_len = password.chars().count();
         // dates without separators are between length 4 '1191' and 8 '11111991'
@@ -647,6 +1553,7 @@ impl Matcher for DateMatch {
                         token.char_index(0..k).parse().unwrap(),
                         token.char_index(k..l).parse().unwrap(),
                         token.char_index(l..j + 1).parse().unwrap(),
+                        pivot,
                     );
                     if let Some(ymd) = ymd {
                         candidates.push(ymd);
@@ -661,22 +1568,21 @@ impl Matcher for DateMatch {
                 //
                 // ie, considering '111504', prefer 11-15-04 to 1-1-1504
                 // (interpreting '04' as 2004)
-                let metric = |candidate: &(i32, i8, i8)| {
-                    (candidate.0 - *super::scoring::REFERENCE_YEAR).abs()
-                };
+                let metric =
+                    |candidate: &Ymd| (candidate.year - *super::scoring::REFERENCE_YEAR).abs();
                 let best_candidate = candidates.iter().min_by_key(|&c| metric(c)).unwrap();
                 let pattern = MatchPattern::Date(DatePattern {
                     separator: String::new(),
-                    year: best_candidate.0,
-                    month: best_candidate.1,
-                    day: best_candidate.2,
+                    year: best_candidate.year,
+                    month: best_candidate.month,
+                    day: best_candidate.day,
                 });
                 matches.push(Match {
                     pattern,
                     token: token_str.to_string(),
                     i,
                     j,
-                    ..Match::default()
+                    guesses: best_candidate.two_digit_guesses(""),
                 });
             }
         }
@@ -700,28 +1606,33 @@ impl Matcher for DateMatch {
                             // Need to manually test that group 2 and 4 are the same
                             continue;
                         }
+                        if !is_date_separator(captures[2].chars().next().unwrap(), options) {
+                            continue;
+                        }
                         (
                             map_ints_to_ymd(
                                 captures[1].parse().unwrap(),
                                 captures[3].parse().unwrap(),
                                 captures[5].parse().unwrap(),
+                                pivot,
                             ),
                             captures[2].to_string(),
                         )
                     };
                     if let Some(ymd) = ymd {
+                        let guesses = ymd.two_digit_guesses(&separator);
                         let pattern = MatchPattern::Date(DatePattern {
                             separator,
-                            year: ymd.0,
-                            month: ymd.1,
-                            day: ymd.2,
+                            year: ymd.year,
+                            month: ymd.month,
+                            day: ymd.day,
                         });
                         matches.push(Match {
                             pattern,
                             token: token.to_string(),
                             i,
                             j,
-                            ..Match::default()
+                            guesses,
                         });
                     }
                 }
@@ -736,9 +1647,37 @@ impl Matcher for DateMatch {
     }
 }
 
-/// Takes three ints and returns them in a (y, m, d) tuple
-fn map_ints_to_ymd(first: u16, second: u16, third: u16) -> Option<(i32, i8, i8)> {
-    // given a 3-tuple, discard if:
+/// Whether `separator` separates the parts of a date, either one of the separators set in
+/// `options`, or by default whitespace or one of `/\_.-`.
+fn is_date_separator(separator: char, options: &ZxcvbnOptions) -> bool {
+    match options.date_separators {
+        Some(ref separators) => separators.contains(&separator),
+        None => separator.is_whitespace() || DEFAULT_DATE_SEPARATORS.contains(separator),
+    }
+}
+
+/// A candidate date of `DateMatch`.
+struct Ymd {
+    year: i32,
+    month: i8,
+    day: i8,
+    /// Whether the year was written with two digits, and its century inferred.
+    two_digit_year: bool,
+}
+
+impl Ymd {
+    /// The guesses of a date with a two-digit year, which are preset as `DatePattern` doesn't
+    /// tell how its year was written, or `None` to estimate the date like the others.
+    fn two_digit_guesses(&self, separator: &str) -> Option<u64> {
+        self.two_digit_year
+            .then(|| super::scoring::date_guesses(self.year, separator, true))
+    }
+}
+
+/// Takes three ints and returns them as a date, inferring the century of two-digit years
+/// with `pivot`, see [`two_to_four_digit_year`].
+fn map_ints_to_ymd(first: u16, second: u16, third: u16, pivot: u16) -> Option<Ymd> {
+    // given a 3-tuple, discard if:
     //   middle int is over 31 (for all ymd formats, years are never allowed in the middle)
     //   middle int is zero
     //   any int is over the max allowable year
@@ -776,7 +1715,12 @@ fn map_ints_to_ymd(first: u16, second: u16, third: u16) -> Option<(i32, i8, i8)>
         if (DATE_MIN_YEAR..=DATE_MAX_YEAR).contains(&year) {
             let dm = map_ints_to_md(second, third);
             if let Some(dm) = dm {
-                return Some((i32::from(year), dm.0, dm.1));
+                return Some(Ymd {
+                    year: i32::from(year),
+                    month: dm.0,
+                    day: dm.1,
+                    two_digit_year: false,
+                });
             } else {
                 // for a candidate that includes a four-digit year,
                 // when the remaining ints don't match to a day and month,
@@ -791,8 +1735,12 @@ fn map_ints_to_ymd(first: u16, second: u16, third: u16) -> Option<(i32, i8, i8)>
     for &(year, second, third) in possible_year_splits {
         let dm = map_ints_to_md(second, third);
         if let Some(dm) = dm {
-            let year = two_to_four_digit_year(year);
-            return Some((i32::from(year), dm.0, dm.1));
+            return Some(Ymd {
+                year: i32::from(two_to_four_digit_year(year, pivot)),
+                month: dm.0,
+                day: dm.1,
+                two_digit_year: true,
+            });
         }
     }
 
@@ -809,10 +1757,16 @@ fn map_ints_to_md(first: u16, second: u16) -> Option<(i8, i8)> {
     None
 }
 
-fn two_to_four_digit_year(year: u16) -> u16 {
+/// Infers the century of a two-digit year: years up to `pivot` are in the 2000s, and later
+/// ones in the 1900s, e.g. with the default pivot of 50, `15` is 2015 and `87` is 1987.
+///
+/// This is a guess: `01` may as well be 1901, and which of the other numbers of a date is the
+/// day or the month is ambiguous too, e.g. `091101` is read as the 9th of November 2001,
+/// even though it may be meant as September 11.
+fn two_to_four_digit_year(year: u16, pivot: u16) -> u16 {
     if year > 99 {
         year
-    } else if year > 50 {
+    } else if year > pivot {
         // 87 -> 1987
         year + 1900
     } else {
@@ -821,6 +1775,9 @@ fn two_to_four_digit_year(year: u16) -> u16 {
     }
 }
 
+const DEFAULT_DATE_SEPARATORS: &str = "/\\_.-";
+/// The default of [`ZxcvbnOptions::two_digit_year_pivot`], as in the JavaScript zxcvbn.
+const DEFAULT_TWO_DIGIT_YEAR_PIVOT: u16 = 50;
 const DATE_MIN_YEAR: u16 = 1000;
 const DATE_MAX_YEAR: u16 = 2050;
 lazy_static! {
@@ -848,16 +1805,117 @@ lazy_static! {
         table
     };
     static ref MAYBE_DATE_NO_SEPARATOR_REGEX: Regex = Regex::new(r"^[0-9]{4,8}$").unwrap();
-    static ref MAYBE_DATE_WITH_SEPARATOR_REGEX: Regex = Regex::new(r"^([0-9]{1,4})([\s/\\_.-])([0-9]{1,2})([\s/\\_.-])([0-9]{1,4})$").unwrap();
+    static ref MAYBE_DATE_WITH_SEPARATOR_REGEX: Regex = Regex::new(r"^([0-9]{1,4})([^0-9])([0-9]{1,2})([^0-9])([0-9]{1,4})$").unwrap();
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::frequency_lists::DictionaryType;
     use crate::matching;
     use crate::matching::patterns::*;
+    use crate::matching::Match;
     use crate::matching::Matcher;
+    use crate::ZxcvbnOptions;
     use std::collections::HashMap;
 
+    /// The matches of `password` with `options`, without user inputs.
+    fn matches(password: &str, options: &ZxcvbnOptions) -> Vec<Match> {
+        matching::omnimatch_with_options(
+            password,
+            &matching::user_inputs_dictionary(&HashMap::new()),
+            options,
+        )
+    }
+
+    /// The matches of `password` with `options` in `dictionary`, along with their pattern.
+    fn dictionary_matches(
+        password: &str,
+        options: &ZxcvbnOptions,
+        dictionary: DictionaryType,
+    ) -> Vec<(Match, DictionaryPattern)> {
+        matches(password, options)
+            .into_iter()
+            .filter_map(|m| match m.pattern {
+                MatchPattern::Dictionary(ref p) if p.dictionary_name == dictionary => {
+                    let p = p.clone();
+                    Some((m, p))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "builder")]
+    #[test]
+    fn test_match_builder_defaults() {
+        let m = matching::MatchBuilder::default().build().unwrap();
+        assert_eq!(m, Match::default());
+        assert_eq!((m.i, m.j), (0, 0));
+        assert_eq!(m.token, "");
+        assert_eq!(m.pattern, MatchPattern::BruteForce);
+
+        let m = matching::MatchBuilder::default()
+            .j(7)
+            .token("password".to_string())
+            .guesses(Some(3))
+            .build()
+            .unwrap();
+        assert_eq!(m.token(), "password");
+        assert_eq!(m.guesses, Some(3));
+    }
+
+    #[cfg(not(feature = "zeroize"))]
+    #[test]
+    fn test_token_of_unicode_passwords() {
+        let password = "\u{1f600}m\u{e4}rz2010\u{e7}a";
+        for m in crate::zxcvbn(password, &[]).sequence() {
+            let expected: String = password.chars().skip(m.i).take(m.j - m.i + 1).collect();
+            assert_eq!(m.token(), expected);
+        }
+        let matches = crate::zxcvbn_all_matches(password, &[]);
+        let m = matches.iter().find(|m| m.token() == "2010").unwrap();
+        assert_eq!((m.i, m.j), (5, 8));
+    }
+
+    #[test]
+    fn test_display_token() {
+        let test_data = [
+            ("password", "p******d"),
+            ("abc", "a*c"),
+            ("ab", "**"),
+            ("a", "*"),
+            ("", ""),
+            ("m\u{e4}rz2010", "m******0"),
+        ];
+        for &(token, masked) in &test_data {
+            let m = Match {
+                token: token.to_string(),
+                ..Match::default()
+            };
+            assert_eq!(m.display_token(), masked);
+        }
+    }
+
+    #[test]
+    fn test_masked_match_debug() {
+        let m = Match {
+            i: 0,
+            j: 7,
+            token: "password".to_string(),
+            pattern: MatchPattern::Dictionary(DictionaryPattern {
+                matched_word: "password".to_string(),
+                ..DictionaryPattern::default()
+            }),
+            guesses: Some(2),
+        };
+        let debug = format!("{:?}", m.masked());
+        assert_eq!(
+            debug,
+            "Match { i: 0, j: 7, token: \"p******d\", pattern: \"dictionary\", guesses: Some(2) }"
+        );
+        assert!(!debug.contains("password"));
+    }
+
     #[test]
     fn test_translate() {
         let chr_map = vec![('a', 'A'), ('b', 'B')]
@@ -953,7 +2011,6 @@ mod tests {
 
     #[test]
     fn test_dictionary_matches_user_inputs() {
-        use crate::frequency_lists::DictionaryType;
         let user_inputs = [("bejeebus".to_string(), 1)]
             .iter()
             .cloned()
@@ -975,6 +2032,292 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_dictionary_matches_extra_dictionaries() {
+        static WORDS: &[&str] = &["quokka", "wallaby", "numbat"];
+        let options = ZxcvbnOptions::new().with_extra_dictionary("marsupials", WORDS);
+        let matches = (matching::DictionaryMatch {}).get_matches_with_options(
+            "Wallaby7numbat",
+            &matching::user_inputs_dictionary(&HashMap::new()),
+            &[],
+            &options,
+        );
+        let custom: Vec<(&str, usize, usize, usize)> = matches
+            .iter()
+            .filter_map(|m| match m.pattern {
+                MatchPattern::Dictionary(ref p)
+                    if p.dictionary_name == DictionaryType::Custom("marsupials") =>
+                {
+                    Some((m.token.as_str(), m.i, m.j, p.rank))
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(custom, [("Wallaby", 0, 6, 2), ("numbat", 8, 13, 3)]);
+
+        let entropy = crate::zxcvbn_with_options("quokka", &[], &options);
+        assert_eq!(entropy.sequence().len(), 1);
+        assert!(matches!(
+            entropy.sequence()[0].pattern,
+            MatchPattern::Dictionary(ref p) if p.dictionary_name == DictionaryType::Custom("marsupials")
+        ));
+    }
+
+    #[test]
+    fn test_dictionary_matches_organization_words() {
+        use crate::frequency_lists::{set_organization_words, DictionaryType};
+        // the words are global, so they must not occur in the passwords of other tests
+        let password = "Globexcorp99hankscorpion";
+        let organization_matches = || -> Vec<(String, usize)> {
+            (matching::DictionaryMatch {})
+                .get_matches(password, &HashMap::new())
+                .into_iter()
+                .filter_map(|m| match m.pattern {
+                    MatchPattern::Dictionary(ref p)
+                        if p.dictionary_name == DictionaryType::Organization =>
+                    {
+                        Some((m.token.clone(), p.rank))
+                    }
+                    _ => None,
+                })
+                .collect()
+        };
+        assert!(organization_matches().is_empty());
+
+        set_organization_words(&["GlobexCorp", "hankscorpion"]);
+        let registered = organization_matches();
+        let from_other_thread = std::thread::spawn(organization_matches).join().unwrap();
+        set_organization_words(&[]);
+
+        assert_eq!(
+            registered,
+            [
+                ("Globexcorp".to_string(), 1),
+                ("hankscorpion".to_string(), 2)
+            ]
+        );
+        assert_eq!(from_other_thread, registered);
+        assert!(organization_matches().is_empty());
+    }
+
+    #[test]
+    fn test_matches_phrases_of_adjacent_words() {
+        static PHRASES: &[&str] = &["loveyou", "thankyouverymuch", "qzxjvk"];
+        let options = ZxcvbnOptions::new().with_phrases(PHRASES);
+        let phrases =
+            |password: &str, options: &ZxcvbnOptions| -> Vec<(String, usize, usize, usize)> {
+                dictionary_matches(password, options, DictionaryType::Phrases)
+                    .into_iter()
+                    .map(|(m, p)| (m.token, m.i, m.j, p.rank))
+                    .collect()
+            };
+        assert_eq!(
+            phrases("xLoveYou!", &options),
+            [("LoveYou".to_string(), 1, 7, 1)]
+        );
+        assert_eq!(
+            phrases("thankyouverymuch", &options),
+            [("thankyouverymuch".to_string(), 0, 15, 2)]
+        );
+        // a phrase is only matched when it is made of dictionary words
+        assert!(phrases("qzxjvk", &options).is_empty());
+        // phrases are opt-in
+        assert!(phrases("xLoveYou!", &ZxcvbnOptions::new()).is_empty());
+
+        let without_phrases = crate::zxcvbn("loveyoumore", &[]);
+        let with_phrases = crate::zxcvbn_with_options("loveyoumore", &[], &options);
+        assert!(with_phrases.guesses() < without_phrases.guesses());
+        assert!(matches!(
+            with_phrases.sequence()[0].pattern,
+            MatchPattern::Dictionary(ref p) if p.dictionary_name == DictionaryType::Phrases
+        ));
+    }
+
+    #[test]
+    fn test_match_kind() {
+        let kind = |pattern: MatchPattern| {
+            Match {
+                pattern,
+                ..Match::default()
+            }
+            .kind()
+        };
+        assert_eq!(
+            kind(MatchPattern::Dictionary(DictionaryPattern::default())),
+            PatternKind::Dictionary
+        );
+        assert_eq!(
+            kind(MatchPattern::Dictionary(DictionaryPattern {
+                l33t: true,
+                ..DictionaryPattern::default()
+            })),
+            PatternKind::L33t
+        );
+        assert_eq!(
+            kind(MatchPattern::Dictionary(DictionaryPattern {
+                reversed: true,
+                ..DictionaryPattern::default()
+            })),
+            PatternKind::Reverse
+        );
+        assert_eq!(
+            kind(MatchPattern::Date(DatePattern::default())),
+            PatternKind::Date
+        );
+        assert_eq!(kind(MatchPattern::BruteForce), PatternKind::Bruteforce);
+        assert_eq!(PatternKind::Bruteforce.to_string(), "bruteforce");
+        assert_eq!(PatternKind::L33t.as_str(), "l33t");
+
+        let kinds: Vec<PatternKind> = crate::zxcvbn("drowssap!qwerty", &[])
+            .sequence()
+            .iter()
+            .map(Match::kind)
+            .collect();
+        assert_eq!(kinds, [PatternKind::Reverse, PatternKind::Spatial]);
+    }
+
+    #[test]
+    fn test_disabled_builtin_dictionaries_are_not_matched() {
+        let found = |password: &str, options: &ZxcvbnOptions, dictionary: DictionaryType| {
+            !dictionary_matches(password, options, dictionary).is_empty()
+        };
+        let options = ZxcvbnOptions::new()
+            .builtin_dictionary(DictionaryType::Surnames, false)
+            .builtin_dictionary(DictionaryType::Passwords, false);
+        for password in ["smithpassword", "drowssap", "p4ssw0rd", "s1mp1e"] {
+            assert!(
+                !found(password, &options, DictionaryType::Surnames),
+                "{}",
+                password
+            );
+            assert!(
+                !found(password, &options, DictionaryType::Passwords),
+                "{}",
+                password
+            );
+        }
+        assert!(found("smithpassword", &options, DictionaryType::English));
+        // the default enables every dictionary, and disabled ones can be enabled again
+        let default = ZxcvbnOptions::new();
+        assert!(found("smithpassword", &default, DictionaryType::Surnames));
+        assert!(found("smithpassword", &default, DictionaryType::Passwords));
+        let options = options.builtin_dictionary(DictionaryType::Surnames, true);
+        assert!(found("smithpassword", &options, DictionaryType::Surnames));
+    }
+
+    #[test]
+    fn test_dictionary_matches_replaced_password_list() {
+        static PASSWORDS: &[&str] = &["motdepasse", "azerty", "soleil"];
+        let passwords = |password: &str, options: &ZxcvbnOptions| -> Vec<(String, usize)> {
+            dictionary_matches(password, options, DictionaryType::Passwords)
+                .into_iter()
+                .map(|(_, p)| (p.matched_word, p.rank))
+                .collect()
+        };
+        let options = ZxcvbnOptions::new().password_list(Some(PASSWORDS));
+        for (password, word, rank) in [
+            ("azerty", "azerty", 2),
+            ("Soleil", "soleil", 3),
+            ("essapedtom", "motdepasse", 1),
+            ("4z3rty", "azerty", 2),
+        ] {
+            assert!(
+                passwords(password, &options).contains(&(word.to_string(), rank)),
+                "{}",
+                password
+            );
+        }
+        // the built-in passwords are no longer matched
+        assert!(passwords("password", &options).is_empty());
+        assert!(passwords("drowssap", &options).is_empty());
+        assert!(passwords("p4ssw0rd", &options).is_empty());
+        // until the default is restored
+        let options = options.password_list(None);
+        assert!(passwords("password", &options).contains(&("password".to_string(), 2)));
+        assert!(!passwords("motdepasse", &options).contains(&("motdepasse".to_string(), 1)));
+        // disabling the passwords also disables the replacement
+        let options = ZxcvbnOptions::new()
+            .password_list(Some(PASSWORDS))
+            .builtin_dictionary(DictionaryType::Passwords, false);
+        assert!(passwords("azerty", &options).is_empty());
+    }
+
+    #[test]
+    fn test_disable_matcher() {
+        use std::collections::HashSet;
+        let kinds = |password: &str, options: &ZxcvbnOptions| -> HashSet<PatternKind> {
+            matches(password, options).iter().map(Match::kind).collect()
+        };
+        let password = "p4ssw0rd1987qwertyabcabc";
+        let all = kinds(password, &ZxcvbnOptions::new());
+        for kind in [
+            PatternKind::Dictionary,
+            PatternKind::L33t,
+            PatternKind::Spatial,
+            PatternKind::Repeat,
+            PatternKind::Sequence,
+            PatternKind::Regex,
+        ] {
+            assert!(all.contains(&kind), "{:?}", kind);
+            let options = ZxcvbnOptions::new().disable_matcher(kind);
+            let mut expected = all.clone();
+            expected.remove(&kind);
+            assert_eq!(kinds(password, &options), expected, "{:?}", kind);
+        }
+        // the regular expressions of the options are regex matches too
+        #[cfg(feature = "regex")]
+        {
+            use regex::Regex;
+            let options = ZxcvbnOptions::new()
+                .with_regex(Regex::new("qwe").unwrap(), 10)
+                .disable_matcher(PatternKind::Regex);
+            assert!(!kinds("qwe", &options).contains(&PatternKind::Regex));
+        }
+        // disabling a matcher twice is the same as once
+        let options = ZxcvbnOptions::new()
+            .disable_matcher(PatternKind::Date)
+            .disable_matcher(PatternKind::Date);
+        assert_eq!(options.disabled_matchers, [PatternKind::Date]);
+    }
+
+    #[test]
+    fn test_matches_diceware_passphrases() {
+        static WORDS: &[&str] = &["correct", "horse", "battery", "staple", "zebra", "lemon"];
+        let options = ZxcvbnOptions::new().with_diceware_wordlist(WORDS);
+        let diceware =
+            |password: &str, options: &ZxcvbnOptions| -> Vec<(String, usize, usize, usize, u64)> {
+                dictionary_matches(password, options, DictionaryType::Diceware)
+                    .into_iter()
+                    .map(|(m, p)| (m.token, m.i, m.j, p.rank, m.guesses.unwrap()))
+                    .collect()
+            };
+        // the rank is the one of the first word, the guesses the number of passphrases
+        assert_eq!(
+            diceware("xyz Zebra lemon", &options),
+            [("Zebra lemon".to_string(), 4, 14, 5, 36)]
+        );
+        assert_eq!(
+            diceware("horse-staple-lemon", &options),
+            [
+                ("horse-staple".to_string(), 0, 11, 2, 36),
+                ("horse-staple-lemon".to_string(), 0, 17, 2, 216),
+                ("staple-lemon".to_string(), 6, 17, 4, 36),
+            ]
+        );
+        // the words must be joined by the same separator
+        assert!(diceware("horse-staple_lemon.zebra", &options).is_empty());
+        assert!(diceware("horsestaple", &options).is_empty());
+        // diceware lists are opt-in
+        assert!(diceware("horse-staple", &ZxcvbnOptions::new()).is_empty());
+
+        let password = "correct-horse-battery-staple";
+        let without_diceware = crate::zxcvbn(password, &[]);
+        let with_diceware = crate::zxcvbn_with_options(password, &[], &options);
+        // the words of the list to the power of 4, plus the additive term of the sequence
+        assert_eq!(with_diceware.guesses(), 6u64.pow(4) + 1);
+        assert!(with_diceware.guesses() < without_diceware.guesses());
+    }
+
     #[test]
     fn test_dictionary_matches_against_reversed_words() {
         let matches = (matching::ReverseDictionaryMatch {}).get_matches("rehtom", &HashMap::new());
@@ -990,7 +2333,7 @@ mod tests {
             } else {
                 panic!("Wrong match pattern")
             };
-            assert_eq!(p.reversed, true);
+            assert!(p.reversed);
         }
     }
 
@@ -1063,10 +2406,51 @@ mod tests {
             } else {
                 panic!("Wrong match pattern")
             };
-            assert_eq!(p.l33t, true);
+            assert!(p.l33t);
         }
     }
 
+    #[test]
+    fn test_dictionary_matches_l33ted_user_inputs() {
+        let user_inputs = crate::sanitize_inputs(&["Oliver"]);
+        let matches = (matching::L33tMatch {}).get_matches("x0l1v3r", &user_inputs);
+        let (m, p) = matches
+            .iter()
+            .find_map(|m| match m.pattern {
+                MatchPattern::Dictionary(ref p)
+                    if p.dictionary_name == DictionaryType::UserInputs =>
+                {
+                    Some((m, p))
+                }
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(m.token, "0l1v3r");
+        assert_eq!((m.i, m.j), (1, 6));
+        assert_eq!(p.matched_word, "oliver");
+        assert_eq!(p.rank, 1);
+        assert!(p.l33t);
+
+        let entropy = crate::zxcvbn("0l1v3r", &["oliver"]);
+        assert_eq!(entropy.sequence().len(), 1);
+        assert!(
+            entropy.guesses() < crate::zxcvbn("0l1v3r", &[]).guesses(),
+            "the l33ted user input should be easier to guess than without user inputs"
+        );
+    }
+
+    #[test]
+    fn test_l33t_sub_display_is_sorted() {
+        let matches = (matching::L33tMatch {}).get_matches("p@ssw0rd", &HashMap::new());
+        let m = matches.iter().find(|m| m.token == "p@ssw0rd").unwrap();
+        let p = if let MatchPattern::Dictionary(ref p) = m.pattern {
+            p
+        } else {
+            panic!("Wrong match pattern")
+        };
+        assert_eq!(p.sub_display.as_deref(), Some("0 -> o, @ -> a"));
+    }
+
     #[test]
     fn test_dictionary_matches_overlapping_l33ted_words() {
         let matches = (matching::L33tMatch {}).get_matches("p@ssw0rd", &HashMap::new());
@@ -1082,7 +2466,41 @@ mod tests {
             } else {
                 panic!("Wrong match pattern")
             };
-            assert_eq!(p.l33t, true);
+            assert!(p.l33t);
+        }
+    }
+
+    #[test]
+    fn test_dictionary_matches_characters_substituting_different_letters() {
+        let matches = (matching::L33tMatch {}).get_matches("xs1mp1e", &HashMap::new());
+        let m = matches.iter().find(|m| m.token == "s1mp1e").unwrap();
+        assert_eq!((m.i, m.j), (1, 6));
+        let p = if let MatchPattern::Dictionary(ref p) = m.pattern {
+            p
+        } else {
+            panic!("Wrong match pattern")
+        };
+        assert_eq!(p.matched_word, "simple");
+        assert!(p.l33t);
+        assert_eq!(p.sub_display.as_deref(), Some("1 -> i, 1 -> l"));
+    }
+
+    #[test]
+    fn test_common_l33t_passwords_are_weak() {
+        for &password in &[
+            "p@ssw0rd", "p@$$w0rd", "pa$$word", "passw0rd", "p455w0rd", "l3tm31n", "dr@g0n",
+            "m0nk3y", "$h@d0w", "5unsh1ne", "m@st3r", "1l0v3y0u", "f00tb@ll", "b@s3b@ll",
+            "pr1nc3ss", "qw3rty", "w3lc0m3", "s3cr3t", "h3ll0", "c0mput3r", "s1mp1e", "d1g1t@1",
+            "w1111@m", "f1n@1",
+        ] {
+            let entropy = crate::zxcvbn(password, &[]);
+            assert_eq!(entropy.sequence().len(), 1, "{}", password);
+            assert!(
+                entropy.score() <= crate::Score::One,
+                "{} scores {}",
+                password,
+                entropy.score()
+            );
         }
     }
 
@@ -1092,6 +2510,40 @@ mod tests {
         assert!(!matches.iter().any(|m| &m.token == "p4@ssword"));
     }
 
+    #[cfg(not(feature = "zeroize"))]
+    #[test]
+    fn test_skips_passwords_without_l33t_characters() {
+        let password = "correcthorsebatterystaple";
+        assert!(matching::relevant_l33t_subtable(password).is_empty());
+        // Without relevant substitutions, there's only the empty substitution to try,
+        // which never yields an l33t match
+        assert_eq!(
+            matching::enumerate_l33t_replacements(&HashMap::new()),
+            vec![HashMap::new()]
+        );
+        assert!((matching::L33tMatch {})
+            .get_matches(password, &HashMap::new())
+            .is_empty());
+
+        let entropy = crate::zxcvbn(password, &[]);
+        assert_eq!(entropy.guesses(), 273_500_327_700_640);
+        let tokens: Vec<&str> = entropy
+            .sequence()
+            .iter()
+            .map(|m| m.token.as_str())
+            .collect();
+        assert_eq!(tokens, ["correct", "horse", "battery", "staple"]);
+
+        let entropy = crate::zxcvbn("p@ssw0rd", &[]);
+        assert_eq!(entropy.guesses(), 9);
+        let tokens: Vec<&str> = entropy
+            .sequence()
+            .iter()
+            .map(|m| m.token.as_str())
+            .collect();
+        assert_eq!(tokens, ["p@ssw0rd"]);
+    }
+
     #[test]
     fn test_doesnt_match_single_character_l33ted_words() {
         let matches = (matching::L33tMatch {}).get_matches("4 ( @", &HashMap::new());
@@ -1122,6 +2574,146 @@ mod tests {
         assert_eq!(p.graph, "qwerty".to_string());
         assert_eq!(p.turns, 2);
         assert_eq!(p.shifted_count, 3);
+        assert!(!p.alternating_shift);
+    }
+
+    #[test]
+    fn test_matches_alternating_shift_spatial_patterns() {
+        for &(password, alternating_shift) in &[
+            ("qWeRtY", true),
+            ("QwErTy", true),
+            ("!2#4%", true),
+            ("qWERty", false),
+        ] {
+            let m = (matching::SpatialMatch {})
+                .get_matches(password, &HashMap::new())
+                .into_iter()
+                .find(|m| m.token == *password)
+                .unwrap();
+            let p = if let MatchPattern::Spatial(ref p) = m.pattern {
+                p
+            } else {
+                panic!("Wrong match pattern")
+            };
+            assert_eq!(p.alternating_shift, alternating_shift, "{}", password);
+        }
+    }
+
+    #[test]
+    fn test_matches_spatial_patterns_spanning_rows() {
+        // walks down the columns, with vertical adjacency between rows
+        for &(password, tokens_and_turns) in &[
+            ("1qaz", &[("1qaz", 1)][..]),
+            ("2wsx", &[("2wsx", 1)]),
+            ("1qazxsw2", &[("1qazxsw2", 3)]),
+            ("zaq12wsx", &[("zaq12wsx", 3)]),
+            ("1qaz2wsx", &[("1qaz", 1), ("2wsx", 1)]),
+        ] {
+            let matches = (matching::SpatialMatch {}).get_matches(password, &HashMap::new());
+            for &(token, turns) in tokens_and_turns {
+                let m = matches
+                    .iter()
+                    .find(|m| m.token == token && matches!(m.pattern, MatchPattern::Spatial(ref p) if p.graph == "qwerty"))
+                    .unwrap_or_else(|| panic!("no spatial match of {} in {}", token, password));
+                let p = if let MatchPattern::Spatial(ref p) = m.pattern {
+                    p
+                } else {
+                    panic!("Wrong match pattern")
+                };
+                assert_eq!(p.turns, turns, "{}", token);
+            }
+        }
+    }
+
+    #[test]
+    fn test_matches_dvorak_home_row() {
+        let matches = (matching::SpatialMatch {}).get_matches("aoeui", &HashMap::new());
+        let p = matches
+            .iter()
+            .find_map(|m| match m.pattern {
+                MatchPattern::Spatial(ref p) if m.token == "aoeui" && p.graph == "dvorak" => {
+                    Some(p)
+                }
+                _ => None,
+            })
+            .unwrap();
+        // a walk in a single direction has one turn, its initial direction, as in the scoring
+        // of spatial matches: a straight line is `turns == 1`, not `turns == 0`
+        assert_eq!(p.turns, 1);
+        assert!(p.is_straight_line());
+        assert_eq!(p.shifted_count, 0);
+    }
+
+    #[test]
+    fn test_spatial_pattern_is_straight_line() {
+        for &(password, straight) in &[
+            ("qwerty", true),
+            ("1qaz", true),
+            ("poiuy", true),
+            ("qwedcx", false),
+            ("zaq12wsx", false),
+            ("6tfGHJ", false),
+        ] {
+            let m = (matching::SpatialMatch {})
+                .get_matches(password, &HashMap::new())
+                .into_iter()
+                .find(|m| m.token == *password)
+                .unwrap();
+            let p = if let MatchPattern::Spatial(ref p) = m.pattern {
+                p
+            } else {
+                panic!("Wrong match pattern")
+            };
+            assert_eq!(p.is_straight_line(), straight, "{}", password);
+        }
+    }
+
+    #[test]
+    fn test_matches_spatial_patterns_of_extra_graphs() {
+        // a single row of greek keys
+        let mut adjacents = HashMap::new();
+        adjacents.insert('α', vec![None, Some("βΒ")]);
+        adjacents.insert('β', vec![Some("αΑ"), Some("γΓ")]);
+        adjacents.insert('γ', vec![Some("βΒ"), Some("δΔ")]);
+        adjacents.insert('δ', vec![Some("γΓ"), None]);
+        let greek = matching::AdjacencyGraph::new("greek", adjacents);
+
+        let password = "1αβγδ1";
+        assert!(!matching::omnimatch(password, &HashMap::new())
+            .iter()
+            .any(|m| matches!(m.pattern, MatchPattern::Spatial(_))));
+        let graphs = [greek];
+        let matches = matching::omnimatch_with_graphs(password, &HashMap::new(), &graphs);
+        let (m, p) = matches
+            .iter()
+            .find_map(|m| match m.pattern {
+                MatchPattern::Spatial(ref p) => Some((m, p)),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(m.token, "αβγδ");
+        assert_eq!((m.i, m.j), (1, 4));
+        assert_eq!(p.graph, "greek");
+        assert_eq!(p.turns, 1);
+        assert_eq!(p.shifted_count, 0);
+
+        // the bases of repeats are matched with the extra graphs too
+        let matches = matching::omnimatch_with_graphs("αβγβαβγβ", &HashMap::new(), &graphs);
+        let repeat = matches
+            .iter()
+            .find_map(|m| match m.pattern {
+                MatchPattern::Repeat(ref p) => Some(p),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(repeat.base_token, "αβγβ");
+        assert!(matches!(
+            repeat.base_matches[..],
+            [Match {
+                pattern: MatchPattern::Spatial(ref p),
+                ..
+            }] if p.graph == "greek"
+        ));
     }
 
     #[test]
@@ -1165,6 +2757,114 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_matches_custom_sequences() {
+        static GREEK: &[&str] = &["α", "β", "γ", "δ", "ε", "ζ", "η", "θ"];
+        static ROMAN: &[&str] = &["i", "ii", "iii", "iv", "v", "vi", "vii", "viii", "ix", "x"];
+        let options = ZxcvbnOptions::new()
+            .with_sequence("greek", GREEK)
+            .with_sequence("roman", ROMAN);
+        let sequences = |password: &str| -> Vec<(String, &str, u8, bool)> {
+            (matching::SequenceMatch {})
+                .get_matches_with_options(
+                    password,
+                    &matching::user_inputs_dictionary(&HashMap::new()),
+                    &[],
+                    &options,
+                )
+                .into_iter()
+                .filter_map(|m| match m.pattern {
+                    MatchPattern::Sequence(ref p)
+                        if p.sequence_name == "greek" || p.sequence_name == "roman" =>
+                    {
+                        Some((
+                            m.token.clone(),
+                            p.sequence_name,
+                            p.sequence_space,
+                            p.ascending,
+                        ))
+                    }
+                    _ => None,
+                })
+                .collect()
+        };
+        assert_eq!(
+            sequences("xαβγδx"),
+            [("αβγδ".to_string(), "greek", 8, true)]
+        );
+        assert_eq!(sequences("θηζ"), [("θηζ".to_string(), "greek", 8, false)]);
+        // too short, or not consecutive
+        assert!(sequences("αβ").is_empty());
+        assert!(sequences("αγε").is_empty());
+        assert_eq!(
+            sequences("vviviiviii"),
+            [("vviviiviii".to_string(), "roman", 10, true)]
+        );
+
+        // guessed based on the size of the alphabet
+        let entropy = crate::zxcvbn_with_options("αβγδεζ", &[], &options);
+        assert_eq!(entropy.sequence().len(), 1);
+        // plus one for the additive penalty of a sequence of one match
+        assert_eq!(entropy.guesses(), 8 * 6 + 1);
+        // elements of several characters count once
+        let entropy = crate::zxcvbn_with_options("vviviiviii", &[], &options);
+        assert_eq!(entropy.guesses(), 10 * 4 + 1);
+        assert!(entropy.guesses() < crate::zxcvbn("vviviiviii", &[]).guesses());
+    }
+
+    #[test]
+    fn test_custom_matchers() {
+        // matches product codes like "ACME-1234"
+        let options = ZxcvbnOptions::new().add_matcher(Box::new(|password: &str| {
+            let chars: Vec<char> = password.chars().collect();
+            (0..chars.len().saturating_sub(8))
+                .filter(|&i| {
+                    chars[i..i + 5].iter().collect::<String>() == "ACME-"
+                        && chars[i + 5..i + 9].iter().all(char::is_ascii_digit)
+                })
+                .map(|i| Match {
+                    i,
+                    j: i + 8,
+                    token: chars[i..i + 9].iter().collect(),
+                    pattern: MatchPattern::Regex(RegexPattern {
+                        regex_name: "product_code",
+                        regex_match: vec![chars[i..i + 9].iter().collect()],
+                    }),
+                    guesses: Some(10_000),
+                })
+                .collect()
+        }));
+        let entropy = crate::zxcvbn_with_options("xACME-4821", &[], &options);
+        let m = entropy
+            .sequence()
+            .iter()
+            .find(|m| matches!(m.pattern, MatchPattern::Regex(_)))
+            .unwrap();
+        assert_eq!((m.i, m.j), (1, 9));
+        assert!(matches!(
+            m.pattern,
+            MatchPattern::Regex(RegexPattern {
+                regex_name: "product_code",
+                ..
+            })
+        ));
+        assert!(entropy.guesses() < crate::zxcvbn("xACME-4821", &[]).guesses());
+
+        // matches outside of the password are ignored
+        let options = ZxcvbnOptions::new().add_matcher(Box::new(|_: &str| {
+            vec![Match {
+                i: 2,
+                j: 20,
+                guesses: Some(1),
+                ..Match::default()
+            }]
+        }));
+        assert_eq!(
+            crate::zxcvbn_with_options("abc", &[], &options).guesses(),
+            crate::zxcvbn("abc", &[]).guesses()
+        );
+    }
+
     #[test]
     fn test_doesnt_match_len_1_sequences() {
         for &password in &["", "a", "1"] {
@@ -1208,7 +2908,7 @@ mod tests {
             panic!("Wrong match pattern")
         };
         assert_eq!(p.sequence_name, "lower");
-        assert_eq!(p.ascending, false);
+        assert!(!p.ascending);
     }
 
     #[test]
@@ -1386,6 +3086,66 @@ mod tests {
         assert_eq!(p.base_token, "ab".to_string());
     }
 
+    #[test]
+    fn test_identifies_repeats_of_alternating_characters() {
+        for &(password, base_token) in &[("abcabcabc", "abc"), ("xyzxyzxyz", "xyz")] {
+            let matches = (matching::RepeatMatch {}).get_matches(password, &HashMap::new());
+            let m = matches.iter().find(|m| m.token == *password).unwrap();
+            assert_eq!((m.i, m.j), (0, 8));
+            let p = if let MatchPattern::Repeat(ref p) = m.pattern {
+                p
+            } else {
+                panic!("Wrong match pattern")
+            };
+            assert_eq!(p.base_token, base_token);
+            assert_eq!(p.repeat_count, 3);
+        }
+    }
+
+    #[test]
+    fn test_repeat_base_token_minimizes_guesses() {
+        let password = "123123123123";
+        let matches = (matching::RepeatMatch {}).get_matches(password, &HashMap::new());
+        let m = matches.iter().find(|m| m.token == *password).unwrap();
+        let p = if let MatchPattern::Repeat(ref p) = m.pattern {
+            p
+        } else {
+            panic!("Wrong match pattern")
+        };
+        assert_eq!(p.base_token, "123123".to_string());
+        assert_eq!(p.repeat_count, 2);
+    }
+
+    #[cfg(not(feature = "zeroize"))]
+    #[test]
+    fn test_repeated_padding_is_matched_as_repeat() {
+        for &(password, word, padding, random) in &[
+            ("password!!!!", "password", "!!!!", "password!?]."),
+            (
+                "admin...........",
+                "admin",
+                "...........",
+                "admin.,;:-_+=*/~",
+            ),
+        ] {
+            let entropy = crate::zxcvbn(password, &[]);
+            let sequence = entropy.sequence();
+            assert_eq!(sequence.len(), 2, "{}", password);
+            assert_eq!(sequence[0].token, word);
+            assert_eq!(sequence[1].token, padding);
+            let p = if let MatchPattern::Repeat(ref p) = sequence[1].pattern {
+                p
+            } else {
+                panic!("Wrong match pattern")
+            };
+            assert_eq!(p.repeat_count, padding.len());
+            // the padding costs a single symbol per repetition, or the minimum guesses of a match
+            let repeat_guesses = p.base_guesses * padding.len() as u64;
+            assert!(sequence[1].guesses.unwrap() <= repeat_guesses.max(50));
+            assert!(entropy.guesses() < crate::zxcvbn(random, &[]).guesses());
+        }
+    }
+
     #[test]
     fn test_identifies_repeat_with_multibyte_utf8() {
         let password = "x\u{1F431}\u{1F436}\u{1F431}\u{1F436}";
@@ -1419,6 +3179,96 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_common_affix_match() {
+        let found = |password: &str| -> Vec<(String, usize, usize, Option<u64>)> {
+            let mut matches = matching::common_affix_match_helper(password);
+            matching::sort_matches(&mut matches);
+            matches
+                .into_iter()
+                .map(|m| (m.token, m.i, m.j, m.guesses))
+                .collect()
+        };
+        assert_eq!(
+            found("monkey123"),
+            [
+                ("123".to_string(), 6, 8, Some(50)),
+                ("3".to_string(), 8, 8, Some(28)),
+            ]
+        );
+        assert_eq!(
+            found("1love1"),
+            [
+                ("1".to_string(), 0, 0, Some(10)),
+                ("1".to_string(), 5, 5, Some(10)),
+            ]
+        );
+        assert_eq!(found("!dragon!!")[0], ("!".to_string(), 0, 0, Some(10)));
+        assert_eq!(found("dragon!@#")[0], ("!@#".to_string(), 6, 8, Some(50)));
+        // a whole password isn't an affix
+        assert!(found("123").iter().all(|m| m.0 != "123"));
+        assert!(found("password").is_empty());
+
+        let is_affix = |m: &Match| matches!(m.pattern, MatchPattern::Regex(ref p) if p.regex_name == matching::COMMON_AFFIX_REGEX_NAME);
+        let matches = matching::omnimatch("shadow!", &HashMap::new());
+        assert!(!matches.iter().any(is_affix));
+        let options = ZxcvbnOptions::new().common_affixes(true);
+        let matches = matching::omnimatch_with_options(
+            "shadow!",
+            &matching::user_inputs_dictionary(&HashMap::new()),
+            &options,
+        );
+        assert!(matches.iter().any(|m| m.token == "!" && is_affix(m)));
+        let matches = matching::omnimatch_with_options(
+            "shadow!",
+            &matching::user_inputs_dictionary(&HashMap::new()),
+            &options.disable_matcher(PatternKind::Regex),
+        );
+        assert!(!matches.iter().any(is_affix));
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_regex_match() {
+        use regex::Regex;
+
+        let employee_id = Regex::new(r"EMP\d{6}").unwrap();
+        let project = Regex::new(r"(PRJ|prj)-(\d+)?").unwrap();
+        let patterns = [(employee_id, 1_000), (project, 50)];
+        let password = "EMP123456prj-EMP654321PRJ-7";
+        let matches = matching::regex_match(password, &patterns);
+        let found: Vec<_> = matches
+            .iter()
+            .map(|m| (m.token.as_str(), m.i, m.j, m.guesses))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("EMP123456", 0, 8, Some(1_000)),
+                ("EMP654321", 13, 21, Some(1_000)),
+                ("prj-", 9, 12, Some(50)),
+                ("PRJ-7", 22, 26, Some(50)),
+            ]
+        );
+        let p = if let MatchPattern::CustomRegex(ref p) = matches[2].pattern {
+            p
+        } else {
+            panic!("Wrong match pattern")
+        };
+        assert_eq!(p.pattern_str, r"(PRJ|prj)-(\d+)?");
+        assert_eq!(matches[2].kind(), PatternKind::Regex);
+
+        // character indices, and no empty matches
+        let matches = matching::regex_match("ééEMP000001", &patterns[..1]);
+        assert_eq!((matches[0].i, matches[0].j), (2, 10));
+        assert!(matching::regex_match("abc", &[(Regex::new("x*").unwrap(), 1)]).is_empty());
+
+        let options = ZxcvbnOptions::new().with_regex(patterns[0].0.clone(), 1_000);
+        let entropy = crate::zxcvbn_with_options("EMP123456", &[], &options);
+        assert_eq!(entropy.sequence().len(), 1);
+        assert!(entropy.guesses() < crate::zxcvbn("EMP123456", &[]).guesses());
+    }
+
     #[test]
     fn test_date_matching_with_various_separators() {
         let separators = ["", " ", "-", "/", "\\", "_", "."];
@@ -1440,6 +3290,58 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_date_matching_with_custom_separators() {
+        let options = ZxcvbnOptions::new().date_separators("_,");
+        let date_match = |password: &str| {
+            (matching::DateMatch {})
+                .get_matches_with_options(
+                    password,
+                    &matching::user_inputs_dictionary(&HashMap::new()),
+                    &[],
+                    &options,
+                )
+                .into_iter()
+                .find(|m| m.token == password)
+        };
+        assert!(date_match("1,2,03").is_some());
+        assert!(date_match("1_2_03").is_some());
+        assert!(date_match("1122003").is_some());
+        assert!(date_match("1.2.03").is_none());
+        assert!(date_match("1 2 03").is_none());
+        assert!(date_match("1,2_03").is_none());
+
+        let m = date_match("1_2_03").unwrap();
+        let p = if let MatchPattern::Date(ref p) = m.pattern {
+            p
+        } else {
+            panic!("Wrong match pattern")
+        };
+        assert_eq!((p.year, p.month, p.day), (2003, 2, 1));
+        assert_eq!(p.separator, "_");
+    }
+
+    #[test]
+    fn test_separated_dates_have_the_same_guesses() {
+        let guesses = |password: &str| {
+            let matches = matching::omnimatch(password, &HashMap::new());
+            crate::scoring::most_guessable_match_sequence(password, &matches, false).guesses
+        };
+        assert_eq!(guesses("1.2.03"), guesses("1_2_03"));
+        assert_eq!(guesses("1.2.03"), guesses("1/2/03"));
+        let dates = |password: &str| {
+            (matching::DateMatch {})
+                .get_matches(password, &HashMap::new())
+                .into_iter()
+                .filter_map(|m| match m.pattern {
+                    MatchPattern::Date(p) => Some((m.i, m.j, p.year, p.month, p.day)),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(dates("1.2.03"), dates("1_2_03"));
+    }
+
     #[test]
     fn test_date_matches_year_closest_to_reference_year() {
         let now = time::OffsetDateTime::now_utc();
@@ -1459,6 +3361,54 @@ mod tests {
         assert_eq!(p.separator, "".to_string());
     }
 
+    #[test]
+    fn test_date_matching_infers_century_of_two_digit_years() {
+        let date_match = |password: &str, options: &ZxcvbnOptions| {
+            let m = (matching::DateMatch {})
+                .get_matches_with_options(
+                    password,
+                    &matching::user_inputs_dictionary(&HashMap::new()),
+                    &[],
+                    options,
+                )
+                .into_iter()
+                .find(|m| m.token == password)
+                .unwrap();
+            match m.pattern {
+                MatchPattern::Date(p) => p,
+                _ => panic!("Wrong match pattern"),
+            }
+        };
+        let options = ZxcvbnOptions::new();
+        // ambiguous: the day is assumed to come before the month
+        let p = date_match("091101", &options);
+        assert_eq!((p.year, p.month, p.day), (2001, 11, 9));
+        assert_eq!(date_match("1/1/45", &options).year, 2045);
+        assert_eq!(date_match("1/1/50", &options).year, 2050);
+        assert_eq!(date_match("1/1/51", &options).year, 1951);
+        assert_eq!(date_match("1/1/1931", &options).year, 1931);
+
+        let options = ZxcvbnOptions::new().two_digit_year_pivot(30);
+        assert_eq!(date_match("1/1/30", &options).year, 2030);
+        assert_eq!(date_match("1/1/31", &options).year, 1931);
+        assert_eq!(date_match("1/1/45", &options).year, 1945);
+
+        // only 100 years can be written with two digits
+        let options = ZxcvbnOptions::new().two_digit_year_pivot(0);
+        let guesses = |password: &str| {
+            (matching::DateMatch {}).get_matches_with_options(
+                password,
+                &matching::user_inputs_dictionary(&HashMap::new()),
+                &[],
+                &options,
+            )[0]
+            .guesses
+        };
+        assert_eq!(date_match("1/1/01", &options).year, 1901);
+        assert_eq!(guesses("1/1/01"), Some(365 * 100 * 4));
+        assert_eq!(guesses("1/1/1901"), None);
+    }
+
     #[test]
     fn test_date_matches() {
         let test_data = [(1, 1, 1999), (11, 8, 2000), (9, 12, 2005), (22, 11, 1551)];
@@ -1576,6 +3526,34 @@ mod tests {
         assert_eq!(p.separator, "/".to_string());
     }
 
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_omnimatch_matches_sequential() {
+        let user_inputs = [("alice".to_string(), 1)].into_iter().collect();
+        for password in [
+            "r0sebudmaelstrom11/20/91aaaa",
+            "p@ssw0rdalice1qaz2wsx",
+            "abcabcabc19/08/2011zyxw",
+            "𐰊𐰂𐰄𐰀𐰁",
+        ] {
+            let sequential: Vec<Match> = matching::MATCHERS
+                .iter()
+                .flat_map(|(_, x)| x.get_matches(password, &user_inputs))
+                .collect();
+            let parallel = matching::omnimatch(password, &user_inputs);
+            // matches spanning the same range are not ordered deterministically,
+            // even by the sequential path, so compare them irrespective of order.
+            assert_eq!(parallel.len(), sequential.len());
+            for m in &parallel {
+                let count = |matches: &[Match]| matches.iter().filter(|x| *x == m).count();
+                assert_eq!(count(&parallel), count(&sequential));
+            }
+            assert!(parallel
+                .windows(2)
+                .all(|w| (w[0].i, w[0].j) <= (w[1].i, w[1].j)));
+        }
+    }
+
     #[test]
     fn test_omnimatch() {
         assert_eq!(matching::omnimatch("", &HashMap::new()), Vec::new());
@@ -1593,4 +3571,180 @@ mod tests {
                 .any(|m| m.pattern.variant() == pattern_name && m.i == i && m.j == j));
         }
     }
+
+    /// Checks that the indices of the matches are in bounds and agree with their tokens.
+    fn assert_valid_matches(password: &str, matches: &[Match]) {
+        let len = password.chars().count();
+        for m in matches {
+            assert!(
+                m.i <= m.j && m.j < len,
+                "{:?} out of bounds of {:?}",
+                m,
+                password
+            );
+            let token: String = password.chars().take(m.j + 1).skip(m.i).collect();
+            assert_eq!(m.token, token);
+        }
+    }
+
+    #[test]
+    fn test_dictionary_match_cases() {
+        let matcher = matching::DictionaryMatch {};
+        let matches = matcher.get_matches("#password#", &HashMap::new());
+        assert!(matches.iter().any(|m| m.token == "password"
+            && matches!(m.pattern, MatchPattern::Dictionary(ref p) if p.rank == 2)));
+        assert_valid_matches("#password#", &matches);
+
+        for password in &["", "a", "#%&*", "aZ9%"] {
+            assert!(matcher.get_matches(password, &HashMap::new()).is_empty());
+        }
+        for password in &["aaa".to_string(), "a".repeat(100), "1".repeat(100)] {
+            assert_valid_matches(password, &matcher.get_matches(password, &HashMap::new()));
+        }
+    }
+
+    #[test]
+    fn test_reverse_dictionary_match_cases() {
+        let matcher = matching::ReverseDictionaryMatch {};
+        let matches = matcher.get_matches("#drowssap#", &HashMap::new());
+        assert!(matches.iter().any(|m| m.token == "drowssap"
+            && matches!(m.pattern, MatchPattern::Dictionary(ref p) if p.reversed && p.matched_word == "password")));
+        assert_valid_matches("#drowssap#", &matches);
+
+        for password in &["", "a", "#%&*", "\u{e9}"] {
+            assert!(matcher.get_matches(password, &HashMap::new()).is_empty());
+        }
+        let matches = matcher.get_matches("\u{f1}drowssap\u{e9}", &HashMap::new());
+        assert!(matches.iter().any(|m| (m.i, m.j) == (1, 8)));
+        assert_valid_matches("\u{f1}drowssap\u{e9}", &matches);
+        let password = "\u{e9}".repeat(100);
+        assert_valid_matches(&password, &matcher.get_matches(&password, &HashMap::new()));
+    }
+
+    #[test]
+    fn test_l33t_match_cases() {
+        let matcher = matching::L33tMatch {};
+        let matches = matcher.get_matches("#p4ssw0rd#", &HashMap::new());
+        assert!(matches.iter().any(|m| m.token == "p4ssw0rd"
+            && matches!(m.pattern, MatchPattern::Dictionary(ref p) if p.l33t && p.matched_word == "password")));
+        assert_valid_matches("#p4ssw0rd#", &matches);
+
+        for password in &["", "4", "#%&*", "\u{e9}"] {
+            assert!(matcher.get_matches(password, &HashMap::new()).is_empty());
+        }
+        let matches = matcher.get_matches("\u{f1}p4ssw0rd\u{e9}", &HashMap::new());
+        assert!(matches.iter().any(|m| (m.i, m.j) == (1, 8)));
+        assert_valid_matches("\u{f1}p4ssw0rd\u{e9}", &matches);
+        let password = "4".repeat(100);
+        assert_valid_matches(&password, &matcher.get_matches(&password, &HashMap::new()));
+    }
+
+    #[test]
+    fn test_spatial_match_cases() {
+        let matcher = matching::SpatialMatch {};
+        let matches = matcher.get_matches("#qwerty#", &HashMap::new());
+        assert!(matches.iter().any(|m| m.token == "qwerty"
+            && matches!(m.pattern, MatchPattern::Spatial(ref p) if p.graph == "qwerty" && p.turns == 1)));
+        assert_valid_matches("#qwerty#", &matches);
+
+        // same-key runs are not keyboard walks
+        for password in [
+            "".to_string(),
+            "a".to_string(),
+            "a1z".to_string(),
+            "aaa".to_string(),
+            "a".repeat(100),
+        ] {
+            assert!(matcher.get_matches(&password, &HashMap::new()).is_empty());
+        }
+        let long_walk = "qwertyuiop".repeat(10);
+        assert_valid_matches(
+            &long_walk,
+            &matcher.get_matches(&long_walk, &HashMap::new()),
+        );
+    }
+
+    #[test]
+    fn test_repeat_match_cases() {
+        let matcher = matching::RepeatMatch {};
+        let matches = matcher.get_matches("#abcabc#", &HashMap::new());
+        assert!(matches.iter().any(|m| m.token == "abcabc"
+            && matches!(m.pattern, MatchPattern::Repeat(ref p) if p.base_token == "abc")));
+        assert_valid_matches("#abcabc#", &matches);
+
+        for password in &["", "a", "abcdef", "aZ9%"] {
+            assert!(matcher.get_matches(password, &HashMap::new()).is_empty());
+        }
+        let password = "a".repeat(100);
+        let matches = matcher.get_matches(&password, &HashMap::new());
+        assert_valid_matches(&password, &matches);
+        assert_eq!(matches.len(), 1);
+        assert!(matches!(
+            matches[0].pattern,
+            MatchPattern::Repeat(ref p) if p.base_token == "a" && p.repeat_count == 100
+        ));
+    }
+
+    #[test]
+    fn test_sequence_match_cases() {
+        let matcher = matching::SequenceMatch {};
+        let matches = matcher.get_matches("#abcdef#", &HashMap::new());
+        assert!(matches.iter().any(|m| m.token == "abcdef"
+            && matches!(m.pattern, MatchPattern::Sequence(ref p) if p.sequence_name == "lower" && p.ascending)));
+        assert_valid_matches("#abcdef#", &matches);
+
+        // a constant run has no delta, so it is a repeat rather than a sequence
+        for password in [
+            "".to_string(),
+            "a".to_string(),
+            "aZ9%".to_string(),
+            "aaa".to_string(),
+            "a".repeat(100),
+        ] {
+            assert!(matcher.get_matches(&password, &HashMap::new()).is_empty());
+        }
+        let password: String = ('a'..='z').chain('A'..='Z').cycle().take(100).collect();
+        assert_valid_matches(&password, &matcher.get_matches(&password, &HashMap::new()));
+    }
+
+    #[test]
+    fn test_regex_match_cases() {
+        let matcher = matching::RegexMatch {};
+        let matches = matcher.get_matches("#1987#", &HashMap::new());
+        assert!(matches.iter().any(|m| m.token == "1987"
+            && matches!(m.pattern, MatchPattern::Regex(ref p) if p.regex_name == "recent_year")));
+        assert_valid_matches("#1987#", &matches);
+
+        for password in &["", "1", "abcdef", "\u{e9}"] {
+            assert!(matcher.get_matches(password, &HashMap::new()).is_empty());
+        }
+        // the indices count chars rather than the bytes of the multibyte characters
+        let matches = matcher.get_matches("\u{f1}\u{e9}1987\u{e9}", &HashMap::new());
+        assert!(matches.iter().any(|m| (m.i, m.j) == (2, 5)));
+        assert_valid_matches("\u{f1}\u{e9}1987\u{e9}", &matches);
+        let password = "1".repeat(100);
+        assert_valid_matches(&password, &matcher.get_matches(&password, &HashMap::new()));
+    }
+
+    #[test]
+    fn test_date_match_cases() {
+        let matcher = matching::DateMatch {};
+        let matches = matcher.get_matches("#13/5/1921#", &HashMap::new());
+        assert!(matches.iter().any(|m| m.token == "13/5/1921"
+            && matches!(m.pattern, MatchPattern::Date(ref p) if (p.year, p.month, p.day) == (1921, 5, 13))));
+        assert_valid_matches("#13/5/1921#", &matches);
+
+        for password in [
+            "".to_string(),
+            "1".to_string(),
+            "abcdef".to_string(),
+            "a".repeat(100),
+        ] {
+            assert!(matcher.get_matches(&password, &HashMap::new()).is_empty());
+        }
+        let password = "1".repeat(100);
+        let matches = matcher.get_matches(&password, &HashMap::new());
+        assert!(!matches.is_empty());
+        assert_valid_matches(&password, &matches);
+    }
 }
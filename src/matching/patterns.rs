@@ -1,12 +1,16 @@
 use crate::frequency_lists::DictionaryType;
 use crate::matching::Match;
 use std::collections::HashMap;
+use std::fmt;
 
 /// Pattern type used to detect a match
+///
+/// More patterns may be added in minor releases.
 #[derive(Debug, Clone, PartialEq, Default)]
 #[cfg_attr(feature = "ser", derive(serde::Serialize))]
 #[cfg_attr(feature = "ser", serde(tag = "pattern"))]
 #[cfg_attr(feature = "ser", serde(rename_all = "lowercase"))]
+#[non_exhaustive]
 pub enum MatchPattern {
     /// A match based on a word in a dictionary
     Dictionary(DictionaryPattern),
@@ -20,13 +24,17 @@ pub enum MatchPattern {
     Regex(RegexPattern),
     /// A match based on date patterns
     Date(DatePattern),
+    /// A match of a regular expression added with
+    /// [`ZxcvbnOptions::with_regex`](crate::ZxcvbnOptions::with_regex).
+    #[cfg(feature = "regex")]
+    #[cfg_attr(feature = "ser", serde(rename = "custom_regex"))]
+    CustomRegex(CustomRegexPattern),
     /// A match based on bruteforce attempting to guess a password
     #[default]
     BruteForce,
 }
 
 impl MatchPattern {
-    #[cfg(test)]
     pub(crate) fn variant(&self) -> &str {
         match *self {
             MatchPattern::Dictionary(_) => "dictionary",
@@ -35,11 +43,66 @@ impl MatchPattern {
             MatchPattern::Sequence(_) => "sequence",
             MatchPattern::Regex(_) => "regex",
             MatchPattern::Date(_) => "date",
+            #[cfg(feature = "regex")]
+            MatchPattern::CustomRegex(_) => "custom_regex",
             MatchPattern::BruteForce => "bruteforce",
         }
     }
 }
 
+/// The kind of pattern of a match, without its details, e.g. to count which kinds of patterns
+/// make passwords weak, see [`Match::kind`].
+///
+/// Dictionary matches are split into plain, l33t and reversed words.
+/// More kinds may be added in minor releases.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+#[cfg_attr(feature = "ser", serde(rename_all = "snake_case"))]
+#[non_exhaustive]
+pub enum PatternKind {
+    /// A word of a dictionary.
+    Dictionary,
+    /// Keys close to one another on a keyboard.
+    Spatial,
+    /// A repeated string.
+    Repeat,
+    /// A sequence of characters, e.g. `abcd`.
+    Sequence,
+    /// One of the regular expressions of zxcvbn, e.g. a recent year, or a user-supplied one.
+    Regex,
+    /// A date.
+    Date,
+    /// Characters which are guessed by bruteforce.
+    Bruteforce,
+    /// A word of a dictionary with l33t substitutions, e.g. `p4ssw0rd`.
+    L33t,
+    /// A word of a dictionary spelled backwards.
+    Reverse,
+}
+
+impl PatternKind {
+    /// The name of the kind in snake case, e.g. `"l33t"`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PatternKind::Dictionary => "dictionary",
+            PatternKind::Spatial => "spatial",
+            PatternKind::Repeat => "repeat",
+            PatternKind::Sequence => "sequence",
+            PatternKind::Regex => "regex",
+            PatternKind::Date => "date",
+            PatternKind::Bruteforce => "bruteforce",
+            PatternKind::L33t => "l33t",
+            PatternKind::Reverse => "reverse",
+        }
+    }
+}
+
+impl fmt::Display for PatternKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// A match based on a word in a dictionary
 #[derive(Debug, Clone, PartialEq, Default)]
 #[cfg_attr(feature = "builder", derive(Builder))]
@@ -58,11 +121,16 @@ pub struct DictionaryPattern {
     pub l33t: bool,
     /// Substitutions used for the match.
     pub sub: Option<HashMap<char, char>>,
-    /// String for displaying the substitutions used for the match.
+    /// String for displaying the substitutions used for the match,
+    /// e.g. `"0 -> o, @ -> a"`. Substitutions are ordered by the substituted character.
     pub sub_display: Option<String>,
-    /// Number of variations of the matched dictionary word.
+    /// Number of uppercase variations of the matched dictionary word.
     pub uppercase_variations: u64,
-    /// Number of variations of the matched dictionary word.
+    /// Number of l33t variations of the matched dictionary word,
+    /// i.e. the factor by which the l33t substitutions multiply the guesses.
+    /// This is `1` for words without l33t substitutions.
+    ///
+    /// Like `uppercase_variations`, this is only set once the match has been scored.
     pub l33t_variations: u64,
     /// Estimated number of tries for guessing the dictionary word.
     pub base_guesses: u64,
@@ -80,6 +148,16 @@ pub struct SpatialPattern {
     pub turns: usize,
     /// Number of shifts in the matched spatial pattern.
     pub shifted_count: usize,
+    /// Whether shifted and unshifted keys alternate throughout the pattern, e.g. `qWeRtY`.
+    pub alternating_shift: bool,
+}
+
+impl SpatialPattern {
+    /// Whether the pattern goes in a single direction on the keyboard, e.g. `qwerty` or the
+    /// column `1qaz`, rather than turning, e.g. `qwedcx`.
+    pub fn is_straight_line(&self) -> bool {
+        self.turns <= 1
+    }
 }
 
 /// A match based on repeating patterns
@@ -110,6 +188,10 @@ pub struct SequencePattern {
     pub sequence_space: u8,
     /// Whether the matched sequence is ascending.
     pub ascending: bool,
+    /// The number of elements matched of a custom sequence registered with
+    /// [`ZxcvbnOptions::with_sequence`](crate::ZxcvbnOptions::with_sequence),
+    /// or `None` for the built-in sequences of characters.
+    pub element_count: Option<usize>,
 }
 
 /// A match based on one of the regex patterns used in zxcvbn.
@@ -124,6 +206,18 @@ pub struct RegexPattern {
     pub regex_match: Vec<String>,
 }
 
+/// A match of a user-supplied regular expression, see
+/// [`regex_match`](crate::matching::regex_match).
+#[cfg(feature = "regex")]
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "builder", derive(Builder))]
+#[cfg_attr(feature = "builder", builder(default))]
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+pub struct CustomRegexPattern {
+    /// The source of the regular expression that was matched.
+    pub pattern_str: String,
+}
+
 /// A match based on date patterns
 #[derive(Debug, Clone, PartialEq, Default)]
 #[cfg_attr(feature = "builder", derive(Builder))]
@@ -0,0 +1,162 @@
+//! Contains structs and methods for assessing passwords against the
+//! memorized secret recommendations of NIST SP 800-63B.
+//!
+//! NIST SP 800-63B emphasizes length over composition rules, and recommends comparing
+//! passwords against lists of commonly-used, expected or compromised values.
+//! This maps zxcvbn's dictionaries and patterns onto those criteria.
+//! It does not check breach databases, which have to be queried separately.
+//!
+//! # Example
+//! ```rust
+//! use zxcvbn::zxcvbn_nist;
+//!
+//! let assessment = zxcvbn_nist("password", &[]);
+//! assert!(!assessment.passes_nist());
+//! assert!(assessment.nist_notes().contains(&"Found in common passwords list"));
+//! ```
+
+use crate::frequency_lists::DictionaryType;
+use crate::matching::patterns::MatchPattern;
+use crate::{zxcvbn, Entropy, Score};
+
+/// Minimum length of a user-chosen memorized secret.
+const NIST_MIN_LENGTH: usize = 8;
+
+/// The result of assessing a password against NIST SP 800-63B.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+pub struct NistAssessment {
+    /// Whether the password meets all of the assessed criteria.
+    passes_nist: bool,
+    /// Explanations of how the password fares for each criterion.
+    nist_notes: Vec<&'static str>,
+    /// The underlying zxcvbn evaluation.
+    entropy: Entropy,
+}
+
+impl NistAssessment {
+    /// Whether the password meets all of the assessed criteria.
+    pub fn passes_nist(&self) -> bool {
+        self.passes_nist
+    }
+
+    /// Explanations of how the password fares for each criterion,
+    /// e.g. "Meets minimum length" or "Found in common passwords list".
+    pub fn nist_notes(&self) -> &[&'static str] {
+        &self.nist_notes
+    }
+
+    /// The underlying zxcvbn evaluation.
+    pub fn entropy(&self) -> &Entropy {
+        &self.entropy
+    }
+}
+
+/// Evaluates a password with `zxcvbn` and assesses it against NIST SP 800-63B.
+///
+/// A password passes if it is at least 8 characters long, is not a common password
+/// or a single dictionary word, does not consist only of repetitive or sequential characters,
+/// does not contain any of the context-specific `user_inputs`, and is not easily guessable
+/// (a score of at least 2).
+pub fn zxcvbn_nist(password: &str, user_inputs: &[&str]) -> NistAssessment {
+    let entropy = zxcvbn(password, user_inputs);
+    let mut passes_nist = true;
+    let mut nist_notes = Vec::new();
+
+    if password.chars().count() >= NIST_MIN_LENGTH {
+        nist_notes.push("Meets minimum length");
+    } else {
+        passes_nist = false;
+        nist_notes.push("Shorter than the minimum length of 8 characters");
+    }
+
+    let sole_pattern = match entropy.sequence() {
+        [m] => Some(&m.pattern),
+        _ => None,
+    };
+    match sole_pattern {
+        Some(MatchPattern::Dictionary(p)) if p.dictionary_name == DictionaryType::Passwords => {
+            passes_nist = false;
+            nist_notes.push("Found in common passwords list");
+        }
+        Some(MatchPattern::Dictionary(p))
+            if p.dictionary_name != DictionaryType::UserInputs
+                && p.dictionary_name != DictionaryType::Diceware =>
+        {
+            passes_nist = false;
+            nist_notes.push("Not found in common passwords list");
+            nist_notes.push("Is a single dictionary word");
+        }
+        Some(MatchPattern::Repeat(_)) | Some(MatchPattern::Sequence(_)) => {
+            passes_nist = false;
+            nist_notes.push("Not found in common passwords list");
+            nist_notes.push("Consists of repetitive or sequential characters");
+        }
+        _ => nist_notes.push("Not found in common passwords list"),
+    }
+
+    let contains_user_input = entropy.sequence().iter().any(|m| {
+        matches!(
+            m.pattern,
+            MatchPattern::Dictionary(ref p) if p.dictionary_name == DictionaryType::UserInputs
+        )
+    });
+    if contains_user_input {
+        passes_nist = false;
+        nist_notes.push("Contains context-specific words");
+    }
+
+    if entropy.score() < Score::Two {
+        passes_nist = false;
+        nist_notes.push("Easily guessable");
+    }
+
+    NistAssessment {
+        passes_nist,
+        nist_notes,
+        entropy,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_zxcvbn_nist() {
+        let assessment = zxcvbn_nist("correcthorsebatterystaple", &[]);
+        assert!(assessment.passes_nist());
+        assert_eq!(
+            assessment.nist_notes(),
+            &["Meets minimum length", "Not found in common passwords list"]
+        );
+
+        let assessment = zxcvbn_nist("password", &[]);
+        assert!(!assessment.passes_nist());
+        assert!(assessment
+            .nist_notes()
+            .contains(&"Found in common passwords list"));
+
+        let assessment = zxcvbn_nist("Xk9#q2", &[]);
+        assert!(!assessment.passes_nist());
+        assert!(assessment
+            .nist_notes()
+            .contains(&"Shorter than the minimum length of 8 characters"));
+
+        let assessment = zxcvbn_nist("abcdefghijkl", &[]);
+        assert!(!assessment.passes_nist());
+        assert!(assessment
+            .nist_notes()
+            .contains(&"Consists of repetitive or sequential characters"));
+
+        let assessment = zxcvbn_nist("jsmith8634!xQ", &["jsmith"]);
+        assert!(!assessment.passes_nist());
+        assert!(assessment
+            .nist_notes()
+            .contains(&"Contains context-specific words"));
+    }
+}
@@ -0,0 +1,532 @@
+use std::fmt;
+use std::sync::Arc;
+use std::time::Instant;
+
+#[cfg(feature = "regex")]
+use regex::Regex;
+
+use crate::frequency_lists::{DictionaryType, RankedDictionary};
+use crate::matching::patterns::PatternKind;
+use crate::matching::MatcherFn;
+use crate::password_policy::CharacterClasses;
+use crate::scoring::VariationWeights;
+
+/// How [`zxcvbn_bytes_with_options`] decodes passwords and user inputs which are not valid UTF-8.
+///
+/// [`zxcvbn_bytes_with_options`]: crate::zxcvbn_bytes_with_options
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ByteDecoding {
+    /// Return an error for passwords which are not valid UTF-8.
+    #[default]
+    Strict,
+    /// Replace invalid UTF-8 sequences with `U+FFFD REPLACEMENT CHARACTER`.
+    Lossy,
+    /// Decode passwords which are not valid UTF-8 as Latin-1 (ISO-8859-1),
+    /// i.e. every byte is the character with the same code point.
+    Latin1,
+    /// Treat bytes which are not part of valid UTF-8 sequences as opaque symbols,
+    /// which are guessed by bruteforce with a cardinality of 256. This is what
+    /// [`zxcvbn_bytes`](crate::zxcvbn_bytes) does.
+    Opaque,
+}
+
+/// Options for customizing how a password is evaluated by [`zxcvbn_with_options`].
+///
+/// The default options produce the same results as [`zxcvbn`]. The options which change how
+/// guesses are estimated, e.g. [`count_spatial_walks`](Self::count_spatial_walks) or
+/// [`common_affixes`](Self::common_affixes), are off by default, as they make the scores
+/// diverge from the canonical ones, and from other zxcvbn implementations.
+///
+/// # Example
+/// ```rust
+/// use zxcvbn::{zxcvbn_with_options, ZxcvbnOptions};
+///
+/// let options = ZxcvbnOptions::new().min_length(12);
+/// let entropy = zxcvbn_with_options("TestMeNow!", &[], &options);
+/// assert!(entropy.feedback().is_some());
+/// ```
+///
+/// [`zxcvbn`]: crate::zxcvbn
+/// [`zxcvbn_with_options`]: crate::zxcvbn_with_options
+#[derive(Debug, Clone, Default)]
+pub struct ZxcvbnOptions {
+    pub(crate) min_length: Option<usize>,
+    pub(crate) always_feedback: bool,
+    pub(crate) date_separators: Option<Vec<char>>,
+    pub(crate) two_digit_year_pivot: Option<u16>,
+    pub(crate) analyze_full_length: bool,
+    pub(crate) deadline: Option<Instant>,
+    pub(crate) extra_dictionaries: Vec<(&'static str, RankedDictionary<'static>)>,
+    pub(crate) disabled_dictionaries: Vec<DictionaryType>,
+    pub(crate) disabled_matchers: Vec<PatternKind>,
+    pub(crate) password_list: Option<RankedDictionary<'static>>,
+    pub(crate) phrases: Option<RankedDictionary<'static>>,
+    pub(crate) diceware: Option<RankedDictionary<'static>>,
+    pub(crate) sequences: Vec<(&'static str, &'static [&'static str])>,
+    pub(crate) byte_decoding: ByteDecoding,
+    pub(crate) variation_weights: VariationWeights,
+    pub(crate) character_classes: Option<CharacterClasses>,
+    pub(crate) unicode_bruteforce_cardinality: bool,
+    pub(crate) count_spatial_walks: bool,
+    pub(crate) penalize_word_with_number_suffix: bool,
+    pub(crate) common_affixes: bool,
+    pub(crate) strict_saturation: bool,
+    pub(crate) matchers: Vec<CustomMatcher>,
+    #[cfg(feature = "regex")]
+    pub(crate) regexes: Vec<(Regex, u64)>,
+}
+
+/// A matcher added with [`ZxcvbnOptions::add_matcher`], shared between clones of the options.
+#[derive(Clone)]
+pub(crate) struct CustomMatcher(pub(crate) Arc<MatcherFn>);
+
+impl fmt::Debug for CustomMatcher {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("CustomMatcher")
+    }
+}
+
+impl ZxcvbnOptions {
+    /// Create options matching the default behavior of `zxcvbn`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Suggest using at least `min_length` characters for passwords shorter than that,
+    /// even when the password has a high enough score to otherwise receive no feedback.
+    pub fn min_length(mut self, min_length: usize) -> Self {
+        self.min_length = Some(min_length);
+        self
+    }
+
+    /// Always generate feedback, even for passwords with a score of 3 or 4.
+    /// Strong passwords receive encouragement and generic hardening suggestions.
+    pub fn always_feedback(mut self, always_feedback: bool) -> Self {
+        self.always_feedback = always_feedback;
+        self
+    }
+
+    /// Only accept the characters of `separators` between the day, month and year of dates,
+    /// instead of whitespace and `/\_.-`. Dates without separators are always matched.
+    pub fn date_separators(mut self, separators: &str) -> Self {
+        self.date_separators = Some(separators.chars().collect());
+        self
+    }
+
+    /// Infer the century of two-digit years in dates: years up to `pivot` are in the 2000s,
+    /// and later ones in the 1900s. The default pivot is 50, as in the JavaScript zxcvbn,
+    /// so `091101` is a date in 2001 and `11/09/87` one in 1987. Spreadsheets use 30.
+    ///
+    /// Which century is meant is ambiguous, and so is whether a number of a date is its day
+    /// or its month: when both are possible, the day is assumed to come first.
+    pub fn two_digit_year_pivot(mut self, pivot: u16) -> Self {
+        self.two_digit_year_pivot = Some(pivot);
+        self
+    }
+
+    /// Evaluate the whole password, instead of only its first 100 characters. The truncation
+    /// doesn't split what users see as a single character, e.g. a letter and its combining
+    /// accents or an emoji family, so a few more characters may be evaluated.
+    ///
+    /// This gives accurate results for long passphrases, but should only be enabled for
+    /// trusted input: the time taken by the matching and scoring grows at least quadratically
+    /// with the length of the password, so very long inputs can be used for denial of service.
+    pub fn analyze_full_length(mut self, analyze_full_length: bool) -> Self {
+        self.analyze_full_length = analyze_full_length;
+        self
+    }
+
+    /// Abandon the evaluation of the whole password with
+    /// [`analyze_full_length`](Self::analyze_full_length) once `deadline` has passed, to bound the
+    /// time taken by very long inputs. When it has passed, only the first 100 characters are
+    /// evaluated instead, which is fast.
+    ///
+    /// The deadline is only checked between the matchers and before the scoring, so it is not
+    /// a hard limit: a single slow matcher, or the scoring itself, runs to completion and can
+    /// overrun it.
+    ///
+    /// The result of an abandoned evaluation is the same as without `analyze_full_length`: the
+    /// rest of the password is ignored, so it's usually conservative, with fewer guesses than
+    /// a full evaluation. It is recognizable by its
+    /// [`password_length`](crate::Entropy::password_length), which is about 100 instead of the
+    /// length of the password: the truncation keeps the grapheme cluster of the last character
+    /// whole, so it may be a few more. Passwords of at most 100 characters are always evaluated
+    /// in full.
+    ///
+    /// This is not supported on `wasm32-unknown-unknown`, which has no clock for `Instant`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::time::{Duration, Instant};
+    /// use zxcvbn::{zxcvbn_with_options, ZxcvbnOptions};
+    ///
+    /// let password = "correct horse battery staple ".repeat(100);
+    /// let options = ZxcvbnOptions::new()
+    ///     .analyze_full_length(true)
+    ///     .with_deadline(Instant::now() + Duration::from_millis(100));
+    /// let entropy = zxcvbn_with_options(&password, &[], &options);
+    /// if entropy.password_length() < password.len() {
+    ///     println!("only the first 100 characters were evaluated in time");
+    /// }
+    /// ```
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Set how [`zxcvbn_bytes_with_options`] decodes passwords which are not valid UTF-8.
+    /// By default, they are rejected.
+    ///
+    /// [`zxcvbn_bytes_with_options`]: crate::zxcvbn_bytes_with_options
+    pub fn byte_decoding(mut self, byte_decoding: ByteDecoding) -> Self {
+        self.byte_decoding = byte_decoding;
+        self
+    }
+
+    /// Treat passwords whose guesses saturate at `u64::MAX` as a hard pass: they always get
+    /// a score of 4 and no feedback, even with [`always_feedback`](Self::always_feedback)
+    /// or [`min_length`](Self::min_length).
+    pub fn strict_saturation(mut self, strict_saturation: bool) -> Self {
+        self.strict_saturation = strict_saturation;
+        self
+    }
+
+    /// Set the factors by which predictable variations, such as capitalization,
+    /// multiply the guesses of a pattern.
+    ///
+    /// The defaults are the values of the original zxcvbn.
+    pub fn variation_weights(mut self, weights: VariationWeights) -> Self {
+        self.variation_weights = weights;
+        self
+    }
+
+    /// Only count the characters of `classes` in the search space of bruteforce guessing,
+    /// e.g. when a site forbids symbols. The smaller the alphabet, the fewer guesses are
+    /// needed to bruteforce the random parts of a password, so the estimates are lower than
+    /// with the default, which assumes that any printable character is allowed.
+    ///
+    /// This doesn't check that the password only contains allowed characters, see
+    /// [`PasswordPolicy`](crate::password_policy::PasswordPolicy) for that.
+    ///
+    /// # Example
+    /// ```rust
+    /// use zxcvbn::password_policy::CharacterClasses;
+    /// use zxcvbn::{zxcvbn, zxcvbn_with_options, ZxcvbnOptions};
+    ///
+    /// let options = ZxcvbnOptions::new().allowed_character_classes(CharacterClasses {
+    ///     symbols: false,
+    ///     ..CharacterClasses::default()
+    /// });
+    /// assert!(
+    ///     zxcvbn_with_options("qJv8rTnw2", &[], &options).guesses() < zxcvbn("qJv8rTnw2", &[]).guesses()
+    /// );
+    /// ```
+    pub fn allowed_character_classes(mut self, classes: CharacterClasses) -> Self {
+        self.character_classes = Some(classes);
+        self
+    }
+
+    /// Bruteforce guess the characters which aren't ASCII based on the size of the alphabet of
+    /// their script, e.g. tens of thousands of CJK ideographs or a few hundred Cyrillic letters,
+    /// instead of as printable ASCII characters. The alphabets are scaled to cardinalities like
+    /// those of [`allowed_character_classes`](Self::allowed_character_classes), which still
+    /// applies to the ASCII characters.
+    ///
+    /// The scripts are recognized by ranges of code points: Latin-1 and Latin Extended, Greek,
+    /// Cyrillic, Hebrew, Arabic, the scripts of India, Thai, Kana, CJK ideographs, Hangul
+    /// syllables and emoji are assumed to have as many characters as their ranges, and the
+    /// other scripts 256 characters.
+    ///
+    /// # Example
+    /// ```rust
+    /// use zxcvbn::{zxcvbn, zxcvbn_with_options, ZxcvbnOptions};
+    ///
+    /// let options = ZxcvbnOptions::new().unicode_bruteforce_cardinality(true);
+    /// assert!(zxcvbn_with_options("密码安全问题", &[], &options).guesses() > zxcvbn("密码安全问题", &[]).guesses());
+    /// assert_eq!(zxcvbn_with_options("jd8h3lq", &[], &options).guesses(), zxcvbn("jd8h3lq", &[]).guesses());
+    /// ```
+    pub fn unicode_bruteforce_cardinality(mut self, unicode_bruteforce_cardinality: bool) -> Self {
+        self.unicode_bruteforce_cardinality = unicode_bruteforce_cardinality;
+        self
+    }
+
+    /// Enable or disable matching passwords against one of the built-in dictionaries,
+    /// e.g. the surnames when they cause false positives for the names of a locale.
+    /// Every built-in dictionary is enabled by default, and this has no effect on the other
+    /// dictionaries, which are only matched when they are added.
+    ///
+    /// # Example
+    /// ```rust
+    /// use zxcvbn::frequency_lists::DictionaryType;
+    /// use zxcvbn::{zxcvbn, zxcvbn_with_options, ZxcvbnOptions};
+    ///
+    /// let options = ZxcvbnOptions::new().builtin_dictionary(DictionaryType::Surnames, false);
+    /// assert!(zxcvbn_with_options("rodriguez", &[], &options).guesses() > zxcvbn("rodriguez", &[]).guesses());
+    /// ```
+    pub fn builtin_dictionary(mut self, dictionary: DictionaryType, enabled: bool) -> Self {
+        self.disabled_dictionaries
+            .retain(|&disabled| disabled != dictionary);
+        if !enabled {
+            self.disabled_dictionaries.push(dictionary);
+        }
+        self
+    }
+
+    /// Skip the matcher of the patterns of `kind`, e.g. to trade accuracy for speed when evaluating
+    /// PINs. This is less accurate even for digits: the spatial matcher finds keypad walks like
+    /// `147258`, and the dictionary matcher common PINs like `123456`. Disabling
+    /// [`PatternKind::Dictionary`] also skips the phrases and the diceware wordlist, disabling
+    /// [`PatternKind::Spatial`] the extra keyboard layouts, and disabling [`PatternKind::Regex`]
+    /// the regular expressions added with `with_regex` with the `regex` feature. The matchers added
+    /// with [`add_matcher`](Self::add_matcher) still run, and the characters which aren't part of
+    /// any match are guessed by bruteforce, so [`PatternKind::Bruteforce`] can't be disabled.
+    ///
+    /// # Example
+    /// ```rust
+    /// use zxcvbn::matching::patterns::PatternKind;
+    /// use zxcvbn::{zxcvbn_with_options, ZxcvbnOptions};
+    ///
+    /// let options = [
+    ///     PatternKind::Dictionary,
+    ///     PatternKind::Reverse,
+    ///     PatternKind::L33t,
+    ///     PatternKind::Spatial,
+    /// ]
+    /// .into_iter()
+    /// .fold(ZxcvbnOptions::new(), ZxcvbnOptions::disable_matcher);
+    /// // faster, but `123456` is no longer found as the most common password
+    /// let entropy = zxcvbn_with_options("123456", &[], &options);
+    /// assert_eq!(entropy.sequence()[0].kind(), PatternKind::Sequence);
+    /// ```
+    pub fn disable_matcher(mut self, kind: PatternKind) -> Self {
+        if !self.disabled_matchers.contains(&kind) {
+            self.disabled_matchers.push(kind);
+        }
+        self
+    }
+
+    /// Replace the built-in list of common passwords with `words`, a frequency list ordered
+    /// from the most to the least common password, e.g. one for another market, or restore
+    /// the built-in list with `None`. Dictionary, reversed and l33t matches in it are reported
+    /// with `DictionaryType::Passwords` and guessed by their rank in `words`.
+    ///
+    /// Passwords are lowercased before being matched, so the words should be lowercase too.
+    /// Disabling `DictionaryType::Passwords` with [`builtin_dictionary`](Self::builtin_dictionary)
+    /// also disables the replacement.
+    ///
+    /// # Example
+    /// ```rust
+    /// use zxcvbn::{zxcvbn_with_options, ZxcvbnOptions};
+    ///
+    /// static PASSWORDS: &[&str] = &["123456", "motdepasse", "azerty"];
+    /// let options = ZxcvbnOptions::new().password_list(Some(PASSWORDS));
+    /// assert_eq!(zxcvbn_with_options("azerty", &[], &options).guesses(), 4);
+    /// assert!(zxcvbn_with_options("password", &[], &options).guesses() > 1000);
+    /// let options = options.password_list(None);
+    /// assert_eq!(zxcvbn_with_options("password", &[], &options).guesses(), 3);
+    /// ```
+    pub fn password_list(mut self, words: Option<&'static [&'static str]>) -> Self {
+        self.password_list = words.map(RankedDictionary::from_words);
+        self
+    }
+
+    /// Estimate keyboard patterns by counting the walks which exist on their keyboard, instead of
+    /// assuming that every key has the average number of neighbors. Walks along the edges of
+    /// the keyboard, e.g. `qwerty` or `1qaz2wsx`, can't continue in every direction, so the
+    /// average overestimates them: with this option, the number of walks at most as long and
+    /// with at most as many turns as the pattern is used when it's lower.
+    ///
+    /// The counting is this crate's own model, not a port of the adjacency counting of zxcvbn3
+    /// or of another implementation. It only applies to the built-in keyboards, and to walks of
+    /// at most 40 keys.
+    ///
+    /// # Example
+    /// ```rust
+    /// use zxcvbn::{zxcvbn, zxcvbn_with_options, ZxcvbnOptions};
+    ///
+    /// let options = ZxcvbnOptions::new().count_spatial_walks(true);
+    /// assert!(zxcvbn_with_options("zxcvbnm,./", &[], &options).guesses() < zxcvbn("zxcvbnm,./", &[]).guesses());
+    /// ```
+    pub fn count_spatial_walks(mut self, count_spatial_walks: bool) -> Self {
+        self.count_spatial_walks = count_spatial_walks;
+        self
+    }
+
+    /// Divide the guesses of passwords made of a single word followed by 2 to 4 digits, like
+    /// `summer2023` or `john1985`, by 10. Attackers try this template early, so it's weaker
+    /// than the product of the guesses of the word and of the digits. These passwords get the
+    /// [`Warning::AWordFollowedByANumberIsEasyToGuess`](crate::feedback::Warning::AWordFollowedByANumberIsEasyToGuess)
+    /// feedback either way.
+    ///
+    /// # Example
+    /// ```rust
+    /// use zxcvbn::{zxcvbn, zxcvbn_with_options, ZxcvbnOptions};
+    ///
+    /// let options = ZxcvbnOptions::new().penalize_word_with_number_suffix(true);
+    /// assert_eq!(zxcvbn_with_options("summer2023", &[], &options).guesses(), zxcvbn("summer2023", &[]).guesses() / 10);
+    /// ```
+    pub fn penalize_word_with_number_suffix(mut self, penalize: bool) -> Self {
+        self.penalize_word_with_number_suffix = penalize;
+        self
+    }
+
+    /// Match the strings which are most commonly added before or after a word, like `123`, `!` or
+    /// `007`, from a small built-in list. They are guessed by their rank in the list, but no fewer
+    /// than the other matches of part of a password, which usually needs fewer guesses than
+    /// bruteforcing them, or than the sequences and repeats they may be. The matches are regex
+    /// matches named [`COMMON_AFFIX_REGEX_NAME`](crate::matching::COMMON_AFFIX_REGEX_NAME), and
+    /// years are left to the recent year matches.
+    ///
+    /// # Example
+    /// ```rust
+    /// use zxcvbn::{zxcvbn, zxcvbn_with_options, ZxcvbnOptions};
+    ///
+    /// let options = ZxcvbnOptions::new().common_affixes(true);
+    /// assert!(zxcvbn_with_options("tigger007", &[], &options).guesses() < zxcvbn("tigger007", &[]).guesses());
+    /// ```
+    pub fn common_affixes(mut self, common_affixes: bool) -> Self {
+        self.common_affixes = common_affixes;
+        self
+    }
+
+    /// Also match passwords against `words`, a frequency list ordered from the most to the
+    /// least common word, e.g. one embedded with `include_wordlist!` from the `zxcvbn-macros`
+    /// crate. Matches in it are reported with `DictionaryType::Custom(name)`.
+    ///
+    /// Passwords are lowercased before being matched, so the words should be lowercase too.
+    pub fn with_extra_dictionary(
+        mut self,
+        name: &'static str,
+        words: &'static [&'static str],
+    ) -> Self {
+        self.extra_dictionaries
+            .push((name, RankedDictionary::from_words(words)));
+        self
+    }
+
+    /// Also match sequences of consecutive `elements` of a custom ordered alphabet, e.g. Greek
+    /// letters or roman numerals. Runs of at least 3 consecutive elements, ascending or
+    /// descending, are reported as sequence matches named `name`, and are guessed based on
+    /// the number of elements of the alphabet instead of the size of the Latin alphabet.
+    ///
+    /// Elements can have several characters, e.g. `"iv"`, and are matched case-sensitively.
+    ///
+    /// # Example
+    /// ```rust
+    /// use zxcvbn::{zxcvbn_with_options, ZxcvbnOptions};
+    ///
+    /// static ROMAN: &[&str] = &["i", "ii", "iii", "iv", "v", "vi", "vii", "viii", "ix", "x"];
+    /// let options = ZxcvbnOptions::new().with_sequence("roman", ROMAN);
+    /// let entropy = zxcvbn_with_options("vviviiviii", &[], &options);
+    /// assert_eq!(entropy.sequence().len(), 1);
+    /// ```
+    pub fn with_sequence(mut self, name: &'static str, elements: &'static [&'static str]) -> Self {
+        self.sequences.push((name, elements));
+        self
+    }
+
+    /// Detect common phrases, e.g. `loveyou`, made of adjacent dictionary words:
+    /// `phrases` is a frequency list ordered from the most to the least common phrase.
+    /// Matches are reported with `DictionaryType::Phrases`, and are guessed by their rank
+    /// instead of as separate words.
+    ///
+    /// Phrases are matched against the lowercased password, so they should be lowercase
+    /// and written without spaces. This is off by default, as it requires a phrase list.
+    pub fn with_phrases(mut self, phrases: &'static [&'static str]) -> Self {
+        self.phrases = Some(RankedDictionary::from_words(phrases));
+        self
+    }
+
+    /// Detect diceware passphrases, e.g. `correct-horse-battery-staple`: runs of at least two
+    /// words of `words` joined by the same separator, a space or one of `-_.`.
+    /// Matches are reported with `DictionaryType::Diceware` and the rank of their first word,
+    /// and are guessed as the number of words of the list to the power of the number of words
+    /// of the passphrase, which is how many passphrases an attacker knowing the list has to try.
+    ///
+    /// Words are matched against the lowercased password, so they should be lowercase.
+    ///
+    /// # Example
+    /// ```rust
+    /// use zxcvbn::{zxcvbn, zxcvbn_with_options, ZxcvbnOptions};
+    ///
+    /// static WORDS: &[&str] = &["correct", "horse", "battery", "staple", "zebra", "lemon"];
+    /// let options = ZxcvbnOptions::new().with_diceware_wordlist(WORDS);
+    /// let password = "correct-horse-battery-staple";
+    /// let entropy = zxcvbn_with_options(password, &[], &options);
+    /// assert_eq!(entropy.sequence().len(), 1);
+    /// assert!(entropy.guesses() < zxcvbn(password, &[]).guesses());
+    /// ```
+    pub fn with_diceware_wordlist(mut self, words: &'static [&'static str]) -> Self {
+        self.diceware = Some(RankedDictionary::from_words(words));
+        self
+    }
+
+    /// Also match the password with `matcher`, e.g. to detect product codes or other
+    /// patterns specific to a service. Its matches are merged with those of the built-in
+    /// matchers before the most guessable sequence of matches is searched.
+    ///
+    /// The indices `i` and `j` of the matches are character indices into the password,
+    /// and `token` must be the characters between them; matches with indices outside of the
+    /// password are ignored. Matches should preset their `guesses`, as only the built-in
+    /// patterns can be estimated otherwise: regex matches of other regular expressions
+    /// are guessed by bruteforce.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(feature = "builder")] {
+    /// use zxcvbn::matching::patterns::{MatchPattern, RegexPattern};
+    /// use zxcvbn::matching::{Match, MatchBuilder};
+    /// use zxcvbn::{zxcvbn_with_options, ZxcvbnOptions};
+    ///
+    /// let options = ZxcvbnOptions::new().add_matcher(Box::new(|password: &str| {
+    ///     password
+    ///         .find("ACME")
+    ///         .map(|start| {
+    ///             let i = password[..start].chars().count();
+    ///             MatchBuilder::default()
+    ///                 .i(i)
+    ///                 .j(i + 3)
+    ///                 .token("ACME".to_string())
+    ///                 .pattern(MatchPattern::Regex(RegexPattern {
+    ///                     regex_name: "company",
+    ///                     regex_match: vec!["ACME".to_string()],
+    ///                 }))
+    ///                 .guesses(Some(1))
+    ///                 .build()
+    ///                 .unwrap()
+    ///         })
+    ///         .into_iter()
+    ///         .collect::<Vec<Match>>()
+    /// }));
+    /// let entropy = zxcvbn_with_options("ACME", &[], &options);
+    /// assert!(matches!(
+    ///     entropy.sequence()[0].pattern,
+    ///     MatchPattern::Regex(RegexPattern { regex_name: "company", .. })
+    /// ));
+    /// # }
+    /// ```
+    pub fn add_matcher(mut self, matcher: MatcherFn) -> Self {
+        self.matchers.push(CustomMatcher(Arc::new(matcher)));
+        self
+    }
+
+    /// Also match the password with `regex`, e.g. `EMP\d{6}` for employee IDs, and guess each of
+    /// its matches with `guesses`, see [`regex_match`](crate::matching::regex_match).
+    ///
+    /// This requires the `regex` feature, since `Regex` becomes part of the public API.
+    ///
+    /// # Example
+    /// ```rust
+    /// use regex::Regex;
+    /// use zxcvbn::{zxcvbn, zxcvbn_with_options, ZxcvbnOptions};
+    ///
+    /// let options = ZxcvbnOptions::new().with_regex(Regex::new(r"EMP\d{6}").unwrap(), 1000);
+    /// let entropy = zxcvbn_with_options("EMP123456", &[], &options);
+    /// assert!(entropy.guesses() < zxcvbn("EMP123456", &[]).guesses());
+    /// ```
+    #[cfg(feature = "regex")]
+    pub fn with_regex(mut self, regex: Regex, guesses: u64) -> Self {
+        self.regexes.push((regex, guesses));
+        self
+    }
+}
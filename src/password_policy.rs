@@ -0,0 +1,229 @@
+//! Contains structs and methods for checking passwords against composition rules,
+//! e.g. "at least 8 characters, with an uppercase letter and a digit".
+//!
+//! Composition rules are often mandated by compliance requirements, but say little about
+//! how hard a password is to guess: `Password1!` satisfies most of them.
+//! They complement the entropy-based score of [`zxcvbn`](crate::zxcvbn) rather than replacing it.
+//!
+//! # Example
+//! ```rust
+//! use zxcvbn::password_policy::{PasswordPolicy, PolicyViolation};
+//!
+//! let policy = PasswordPolicy {
+//!     min_length: 8,
+//!     require_uppercase: true,
+//!     require_digit: true,
+//!     ..PasswordPolicy::default()
+//! };
+//! assert_eq!(
+//!     policy.check("secret"),
+//!     vec![
+//!         PolicyViolation::TooShort { actual: 6, required: 8 },
+//!         PolicyViolation::MissingUppercase,
+//!         PolicyViolation::MissingDigit,
+//!     ]
+//! );
+//! assert!(policy.check("Secret123").is_empty());
+//! ```
+
+use std::fmt;
+
+/// A set of composition rules for passwords.
+///
+/// The default policy accepts every password.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PasswordPolicy {
+    /// Minimum number of characters.
+    pub min_length: usize,
+    /// Require at least one uppercase letter.
+    pub require_uppercase: bool,
+    /// Require at least one lowercase letter.
+    pub require_lowercase: bool,
+    /// Require at least one ASCII digit, from `0` to `9`.
+    pub require_digit: bool,
+    /// Require at least one symbol, i.e. a character which is neither a letter nor a digit.
+    pub require_symbol: bool,
+}
+
+impl PasswordPolicy {
+    /// Checks `password` against the rules of the policy.
+    ///
+    /// Returns the violated rules, in the order of the fields of `PasswordPolicy`,
+    /// or an empty `Vec` if the password complies with the policy.
+    pub fn check(&self, password: &str) -> Vec<PolicyViolation> {
+        let mut violations = Vec::new();
+        let length = password.chars().count();
+        if length < self.min_length {
+            violations.push(PolicyViolation::TooShort {
+                actual: length,
+                required: self.min_length,
+            });
+        }
+        if self.require_uppercase && !password.chars().any(char::is_uppercase) {
+            violations.push(PolicyViolation::MissingUppercase);
+        }
+        if self.require_lowercase && !password.chars().any(char::is_lowercase) {
+            violations.push(PolicyViolation::MissingLowercase);
+        }
+        if self.require_digit && !password.chars().any(|c| c.is_ascii_digit()) {
+            violations.push(PolicyViolation::MissingDigit);
+        }
+        if self.require_symbol && password.chars().all(char::is_alphanumeric) {
+            violations.push(PolicyViolation::MissingSymbol);
+        }
+        violations
+    }
+}
+
+/// The classes of characters which passwords may contain, e.g. to estimate their strength
+/// with [`ZxcvbnOptions::allowed_character_classes`](crate::ZxcvbnOptions::allowed_character_classes).
+///
+/// The default allows every class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CharacterClasses {
+    /// Allow the lowercase letters `a-z`.
+    pub lowercase: bool,
+    /// Allow the uppercase letters `A-Z`.
+    pub uppercase: bool,
+    /// Allow the digits `0-9`.
+    pub digits: bool,
+    /// Allow the printable ASCII symbols, including the space.
+    pub symbols: bool,
+}
+
+impl Default for CharacterClasses {
+    fn default() -> Self {
+        CharacterClasses {
+            lowercase: true,
+            uppercase: true,
+            digits: true,
+            symbols: true,
+        }
+    }
+}
+
+impl CharacterClasses {
+    /// The number of allowed characters, e.g. 62 for letters and digits.
+    pub fn cardinality(&self) -> u64 {
+        [
+            (self.lowercase, 26),
+            (self.uppercase, 26),
+            (self.digits, 10),
+            (self.symbols, 33),
+        ]
+        .iter()
+        .filter(|(allowed, _)| *allowed)
+        .map(|(_, size)| size)
+        .sum()
+    }
+}
+
+/// A rule of a [`PasswordPolicy`] which a password doesn't comply with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+#[non_exhaustive]
+pub enum PolicyViolation {
+    /// The password has fewer characters than required.
+    TooShort {
+        /// The number of characters of the password.
+        actual: usize,
+        /// The minimum number of characters.
+        required: usize,
+    },
+    /// The password has no uppercase letter.
+    MissingUppercase,
+    /// The password has no lowercase letter.
+    MissingLowercase,
+    /// The password has no digit.
+    MissingDigit,
+    /// The password has no symbol.
+    MissingSymbol,
+}
+
+impl fmt::Display for PolicyViolation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PolicyViolation::TooShort { required, .. } => {
+                write!(f, "Use at least {} characters.", required)
+            }
+            PolicyViolation::MissingUppercase => write!(f, "Add an uppercase letter."),
+            PolicyViolation::MissingLowercase => write!(f, "Add a lowercase letter."),
+            PolicyViolation::MissingDigit => write!(f, "Add a digit."),
+            PolicyViolation::MissingSymbol => write!(f, "Add a symbol."),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_policy_accepts_everything() {
+        let policy = PasswordPolicy::default();
+        assert!(policy.check("").is_empty());
+        assert!(policy.check("a").is_empty());
+    }
+
+    #[test]
+    fn test_check() {
+        let policy = PasswordPolicy {
+            min_length: 10,
+            require_uppercase: true,
+            require_lowercase: true,
+            require_digit: true,
+            require_symbol: true,
+        };
+        assert_eq!(
+            policy.check("ÉCOLE"),
+            vec![
+                PolicyViolation::TooShort {
+                    actual: 5,
+                    required: 10
+                },
+                PolicyViolation::MissingLowercase,
+                PolicyViolation::MissingDigit,
+                PolicyViolation::MissingSymbol,
+            ]
+        );
+        assert_eq!(
+            policy.check("correct horse 9"),
+            vec![PolicyViolation::MissingUppercase]
+        );
+        assert!(policy.check("Password1!").is_empty());
+        assert_eq!(
+            policy.check("Password١!"),
+            vec![PolicyViolation::MissingDigit]
+        );
+    }
+
+    #[test]
+    fn test_character_classes_cardinality() {
+        assert_eq!(CharacterClasses::default().cardinality(), 95);
+        let alphanumeric = CharacterClasses {
+            symbols: false,
+            ..CharacterClasses::default()
+        };
+        assert_eq!(alphanumeric.cardinality(), 62);
+        let digits = CharacterClasses {
+            lowercase: false,
+            uppercase: false,
+            digits: true,
+            symbols: false,
+        };
+        assert_eq!(digits.cardinality(), 10);
+    }
+
+    #[test]
+    fn test_violation_display() {
+        assert_eq!(
+            PolicyViolation::TooShort {
+                actual: 3,
+                required: 8
+            }
+            .to_string(),
+            "Use at least 8 characters."
+        );
+        assert_eq!(PolicyViolation::MissingSymbol.to_string(), "Add a symbol.");
+    }
+}
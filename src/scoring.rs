@@ -1,5 +1,23 @@
+//! Contains structs and methods for scoring a set of matches, i.e. finding the sequence
+//! of matches covering a password which needs the fewest guesses to crack.
+//!
+//! This is the second half of the evaluation done by [`zxcvbn`](crate::zxcvbn),
+//! which can be used on matches from other sources than the built-in matchers.
+//!
+//! # Example
+//! ```rust
+//! use zxcvbn::scoring::most_guessable_match_sequence;
+//! use zxcvbn::{zxcvbn, zxcvbn_all_matches};
+//!
+//! let matches = zxcvbn_all_matches("correcthorse", &[]);
+//! let result = most_guessable_match_sequence("correcthorse", &matches, false);
+//! assert_eq!(result.guesses, zxcvbn("correcthorse", &[]).guesses());
+//! ```
+
 use crate::matching::patterns::*;
 use crate::matching::Match;
+use crate::options::ZxcvbnOptions;
+use crate::password_policy::CharacterClasses;
 use std::collections::HashMap;
 use std::{cmp, fmt::Display};
 
@@ -48,8 +66,10 @@ impl Display for Score {
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct GuessCalculation {
+/// The optimal sequence of matches covering a password, and the guesses needed to crack it.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+pub struct ScoringResult {
     /// Estimated guesses needed to crack the password
     pub guesses: u64,
     /// Order of magnitude of `guesses`
@@ -58,6 +78,65 @@ pub struct GuessCalculation {
     pub sequence: Vec<Match>,
 }
 
+/// The factors by which the predictable variations of a pattern multiply its guesses.
+///
+/// The defaults are the values of the original zxcvbn. Changing them makes the scores
+/// diverge from those of other zxcvbn implementations, so they should only be tuned
+/// to match specific composition rules.
+///
+/// # Example
+/// ```rust
+/// use zxcvbn::scoring::VariationWeights;
+/// use zxcvbn::{zxcvbn, zxcvbn_with_options, ZxcvbnOptions};
+///
+/// let weights = VariationWeights {
+///     capitalization: 4,
+///     ..VariationWeights::default()
+/// };
+/// let options = ZxcvbnOptions::new().variation_weights(weights);
+/// let entropy = zxcvbn_with_options("Dragonfly", &[], &options);
+/// assert!(entropy.guesses() > zxcvbn("Dragonfly", &[]).guesses());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VariationWeights {
+    /// Factor for dictionary words with a common capitalization: capitalized, all uppercase,
+    /// or only the last letter uppercase. Defaults to 2.
+    pub capitalization: u64,
+    /// Factor for each l33t substitution applied to every occurrence of a letter,
+    /// e.g. digits or symbols replacing all of the `a`s. Defaults to 2.
+    pub l33t_substitution: u64,
+    /// Factor for keyboard patterns which are entirely typed with shift, e.g. `!@#$`,
+    /// or alternate between shifted and unshifted keys. Defaults to 2.
+    pub shift: u64,
+}
+
+impl Default for VariationWeights {
+    fn default() -> Self {
+        VariationWeights {
+            capitalization: 2,
+            l33t_substitution: 2,
+            shift: 2,
+        }
+    }
+}
+
+/// The weights of the guess estimates of the matches.
+#[derive(Debug, Clone, Copy, Default)]
+struct EstimateWeights {
+    /// the factors of the predictable variations.
+    variations: VariationWeights,
+    /// whether keyboard patterns are estimated by counting the walks on their graph.
+    count_spatial_walks: bool,
+}
+
+/// The memoization table of the dynamic programming used for scoring a match sequence.
+///
+/// `table.0[k][l]` holds the overall guesses of the best sequence of `l` matches covering the
+/// password up to its character `k`, inclusive, or 0 if no sequence of `l` matches fares better
+/// than a shorter one covering the same characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DpTable(pub Vec<Vec<u64>>);
+
 #[derive(Debug, Clone)]
 struct Optimal {
     /// optimal.m[k][l] holds final match in the best length-l match sequence covering the
@@ -70,6 +149,16 @@ struct Optimal {
     pi: Vec<HashMap<usize, u64>>,
     /// same structure as optimal.m -- holds the overall metric.
     g: Vec<HashMap<usize, u64>>,
+    /// the weights used for estimating the guesses of each match.
+    weights: EstimateWeights,
+    /// the cardinality of the bruteforce matches, if the allowed characters are restricted.
+    bruteforce_cardinality: Option<f64>,
+    /// whether bruteforce guessing a character which isn't ASCII depends on its script.
+    unicode_bruteforce_cardinality: bool,
+    /// the sorted indices of the characters standing for opaque bytes.
+    opaque_bytes: Vec<usize>,
+    /// whether the sequences of a word followed by a number get fewer guesses.
+    penalize_word_with_number_suffix: bool,
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -86,17 +175,272 @@ lazy_static! {
 }
 
 const MIN_YEAR_SPACE: i32 = 20;
+const TWO_DIGIT_YEAR_SPACE: i32 = 100;
 const BRUTEFORCE_CARDINALITY: u64 = 10;
+/// The factor dividing the guesses of a word followed by a number, see
+/// [`ZxcvbnOptions::penalize_word_with_number_suffix`].
+const WORD_WITH_NUMBER_SUFFIX_PENALTY: u64 = 10;
+/// The number of printable ASCII characters, the alphabet `BRUTEFORCE_CARDINALITY` stands for.
+const PRINTABLE_ASCII_CARDINALITY: f64 = 95.;
+/// The cardinality of bruteforce guessing a byte which isn't part of a valid UTF-8 sequence.
+const OPAQUE_BYTE_CARDINALITY: u64 = 256;
+/// The ranges of characters, from the first to the last, whose alphabets are assumed when
+/// bruteforce guessing characters which aren't ASCII with
+/// [`ZxcvbnOptions::unicode_bruteforce_cardinality`]. An attacker who knows the script of a
+/// character is assumed to try every code point of its range:
+/// - Latin-1 Supplement and Latin Extended-A and -B: 432 characters
+/// - Greek: 144 characters
+/// - Cyrillic and its supplement: 304 characters
+/// - Hebrew: 112 characters
+/// - Arabic: 256 characters
+/// - Devanagari and the other scripts of India up to Malayalam: 1,280 characters
+/// - Thai: 128 characters
+/// - Hiragana and Katakana: 192 characters
+/// - CJK Unified Ideographs and their Extension A: 27,648 characters
+/// - Hangul syllables: 11,172 characters
+/// - Emoji and pictographs: 2,048 characters
+const SCRIPT_RANGES: &[(char, char)] = &[
+    ('\u{80}', '\u{24F}'),
+    ('\u{370}', '\u{3FF}'),
+    ('\u{400}', '\u{52F}'),
+    ('\u{590}', '\u{5FF}'),
+    ('\u{600}', '\u{6FF}'),
+    ('\u{900}', '\u{DFF}'),
+    ('\u{E00}', '\u{E7F}'),
+    ('\u{3040}', '\u{30FF}'),
+    ('\u{3400}', '\u{9FFF}'),
+    ('\u{AC00}', '\u{D7A3}'),
+    ('\u{1F300}', '\u{1FAFF}'),
+];
+/// The alphabet assumed for the characters outside of `SCRIPT_RANGES`, the usual size of the
+/// Unicode blocks of the other scripts.
+const OTHER_SCRIPT_CARDINALITY: f64 = 256.;
+/// The first of the 256 characters standing for the bytes of invalid UTF-8 sequences, which are
+/// the last characters of the supplementary private use area B. Only their positions passed to
+/// the scoring tell them apart from the same characters in a valid password.
+const OPAQUE_BYTES_START: u32 = 0x10FF00;
 const MIN_GUESSES_BEFORE_GROWING_SEQUENCE: u64 = 10_000;
-const MIN_SUBMATCH_GUESSES_SINGLE_CHAR: u64 = 10;
-const MIN_SUBMATCH_GUESSES_MULTI_CHAR: u64 = 50;
-
+pub(crate) const MIN_SUBMATCH_GUESSES_SINGLE_CHAR: u64 = 10;
+pub(crate) const MIN_SUBMATCH_GUESSES_MULTI_CHAR: u64 = 50;
+
+/// Find the sequence of `matches` covering `password` which needs the fewest guesses to crack,
+/// filling the gaps between matches with bruteforce matches.
+///
+/// The `i` and `j` of the matches are indices of characters of `password`.
+/// With `exclude_additive`, sequences with more matches are not penalized, as is done
+/// when scoring the base token of a repeat.
+///
+/// # Panics
+/// Panics if a match ends after the last character of `password`.
 pub fn most_guessable_match_sequence(
     password: &str,
-    matches: &[crate::matching::Match],
+    matches: &[Match],
+    exclude_additive: bool,
+) -> ScoringResult {
+    score_match_sequence(password, matches, exclude_additive).0
+}
+
+/// Same as `most_guessable_match_sequence`, but also returns the memoization table
+/// of the dynamic programming.
+pub fn score_match_sequence(
+    password: &str,
+    matches: &[Match],
     exclude_additive: bool,
-) -> GuessCalculation {
+) -> (ScoringResult, DpTable) {
+    search(
+        password,
+        matches,
+        exclude_additive,
+        &EstimateWeights::default(),
+        None,
+        false,
+        &[],
+        false,
+    )
+}
+
+/// The candidate matches of a password as a directed acyclic graph, in which
+/// [`most_guessable_match_sequence`] searches the path needing the fewest guesses.
+///
+/// Every path from the first to the last node is a way to tile the password with matches.
+/// The guesses of a path of `l` edges are `l!` times the product of the guesses of its edges,
+/// plus `10000^(l - 1)` for trying the shorter paths first.
+#[cfg(feature = "match_graph")]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+pub struct MatchGraph {
+    /// The number of nodes, one more than the number of characters of the password.
+    /// Node `k` is the position before the character `k`, so the last node is the end
+    /// of the password.
+    pub node_count: usize,
+    /// The edges, sorted by their first and then their last node.
+    pub edges: Vec<MatchEdge>,
+}
+
+/// An edge of a [`MatchGraph`]: a match of the characters from `from` to `to - 1`.
+#[cfg(feature = "match_graph")]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+pub struct MatchEdge {
+    /// The node before the first character of the match, i.e. `m.i`.
+    pub from: usize,
+    /// The node after the last character of the match, i.e. `m.j + 1`.
+    pub to: usize,
+    /// The estimated guesses of the match, the weight of the edge.
+    pub guesses: u64,
+    /// The match, with its `guesses` estimated.
+    pub m: Match,
+}
+
+/// Build the graph searched by [`most_guessable_match_sequence`] from the `matches` of
+/// `password`, e.g. the ones returned by [`zxcvbn_all_matches`](crate::zxcvbn_all_matches),
+/// to render the alternative sequences of matches.
+///
+/// Besides the `matches`, the graph has a bruteforce edge for every span of characters,
+/// which the search uses to fill the gaps between the matches.
+///
+/// # Example
+/// ```rust
+/// use zxcvbn::scoring::{match_graph, most_guessable_match_sequence};
+/// use zxcvbn::zxcvbn_all_matches;
+///
+/// let matches = zxcvbn_all_matches("password1", &[]);
+/// let graph = match_graph("password1", &matches);
+/// assert_eq!(graph.node_count, 10);
+/// for m in most_guessable_match_sequence("password1", &matches, false).sequence {
+///     assert!(graph.edges.iter().any(|edge| edge.m == m));
+/// }
+/// ```
+#[cfg(feature = "match_graph")]
+pub fn match_graph(password: &str, matches: &[Match]) -> MatchGraph {
+    let chars: Vec<char> = password.chars().collect();
+    let n = chars.len();
+    let weights = EstimateWeights::default();
+    let bruteforce_matches = (0..n).flat_map(|i| {
+        let chars = &chars;
+        (i..n).map(move |j| Match {
+            pattern: MatchPattern::BruteForce,
+            token: chars[i..=j].iter().collect(),
+            i,
+            j,
+            guesses: None,
+        })
+    });
+    let mut edges: Vec<MatchEdge> = matches
+        .iter()
+        .cloned()
+        .chain(bruteforce_matches)
+        .map(|mut m| MatchEdge {
+            from: m.i,
+            to: m.j + 1,
+            guesses: estimate_guesses(&mut m, password, &weights),
+            m,
+        })
+        .collect();
+    edges.sort_by_key(|edge| (edge.from, edge.to));
+    MatchGraph {
+        node_count: n + 1,
+        edges,
+    }
+}
+
+/// Same as `most_guessable_match_sequence`, with the variation weights, allowed characters,
+/// spatial walk counting and word with number suffix penalty of `options`.
+///
+/// The characters at the sorted indices `opaque_bytes` stand for bytes of invalid UTF-8
+/// sequences, see [`opaque_byte`].
+pub(crate) fn most_guessable_match_sequence_with_options(
+    password: &str,
+    matches: &[Match],
+    exclude_additive: bool,
+    options: &ZxcvbnOptions,
+    opaque_bytes: &[usize],
+) -> ScoringResult {
+    search(
+        password,
+        matches,
+        exclude_additive,
+        &EstimateWeights {
+            variations: options.variation_weights,
+            count_spatial_walks: options.count_spatial_walks,
+        },
+        options.character_classes.map(bruteforce_cardinality),
+        options.unicode_bruteforce_cardinality,
+        opaque_bytes,
+        options.penalize_word_with_number_suffix,
+    )
+    .0
+}
+
+/// Whether the sequence is a single word followed by 2 to 4 digits, e.g. a year,
+/// like `summer2023` or `john1985`.
+pub(crate) fn is_word_with_number_suffix(sequence: &[Match]) -> bool {
+    match sequence {
+        [word, number] => is_number_suffix_of_word(word, number),
+        _ => false,
+    }
+}
+
+/// Whether `number` is 2 to 4 digits following the dictionary match `word`.
+fn is_number_suffix_of_word(word: &Match, number: &Match) -> bool {
+    matches!(word.pattern, MatchPattern::Dictionary(_))
+        && (2..=4).contains(&number.token.len())
+        && number.token.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// The cardinality of bruteforce guessing a character when only the characters of `classes`
+/// are allowed.
+///
+/// `BRUTEFORCE_CARDINALITY` is the cardinality for all printable ASCII characters, so it's
+/// scaled logarithmically with the size of the allowed alphabet: the restriction divides the
+/// guesses of the bruteforce matches, and keeps them proportional to the search space.
+fn bruteforce_cardinality(classes: CharacterClasses) -> f64 {
+    scaled_bruteforce_cardinality(classes.cardinality() as f64)
+}
+
+/// The cardinality of bruteforce guessing a character of the script of `c`, which isn't ASCII,
+/// scaled from the size of its alphabet like [`bruteforce_cardinality`].
+fn script_bruteforce_cardinality(c: char) -> f64 {
+    let size = SCRIPT_RANGES
+        .iter()
+        .find(|&&(first, last)| first <= c && c <= last)
+        .map_or(OTHER_SCRIPT_CARDINALITY, |&(first, last)| {
+            f64::from(u32::from(last) - u32::from(first) + 1)
+        });
+    scaled_bruteforce_cardinality(size)
+}
+
+/// Scales `BRUTEFORCE_CARDINALITY` from the printable ASCII characters to an alphabet of `size`
+/// characters.
+fn scaled_bruteforce_cardinality(size: f64) -> f64 {
+    if size <= 1. {
+        return 1.;
+    }
+    (BRUTEFORCE_CARDINALITY as f64).powf(size.ln() / PRINTABLE_ASCII_CARDINALITY.ln())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn search(
+    password: &str,
+    matches: &[Match],
+    exclude_additive: bool,
+    weights: &EstimateWeights,
+    bruteforce_cardinality: Option<f64>,
+    unicode_bruteforce_cardinality: bool,
+    opaque_bytes: &[usize],
+    penalize_word_with_number_suffix: bool,
+) -> (ScoringResult, DpTable) {
     let n = password.chars().count();
+    if n == 0 {
+        return (
+            ScoringResult {
+                guesses: 0,
+                guesses_log10: f64::NEG_INFINITY,
+                sequence: Vec::new(),
+            },
+            DpTable(Vec::new()),
+        );
+    }
 
     // partition matches into sublists according to ending index j
     let mut matches_by_j: Vec<Vec<Match>> = vec![Vec::new(); n];
@@ -112,6 +456,11 @@ pub fn most_guessable_match_sequence(
         m: vec![HashMap::new(); n],
         pi: vec![HashMap::new(); n],
         g: vec![HashMap::new(); n],
+        weights: *weights,
+        bruteforce_cardinality,
+        unicode_bruteforce_cardinality,
+        opaque_bytes: opaque_bytes.to_vec(),
+        penalize_word_with_number_suffix,
     };
 
     /// helper: considers whether a length-l sequence ending at match m is better (fewer guesses)
@@ -124,25 +473,42 @@ pub fn most_guessable_match_sequence(
         exclude_additive: bool,
     ) {
         let k = m.j;
-        let mut pi = estimate_guesses(&mut m, password);
+        let mut pi = estimate_guesses(&mut m, password, &optimal.weights);
         if len > 1 {
             // we're considering a length-l sequence ending with match m:
             // obtain the product term in the minimization function by multiplying m's guesses
             // by the product of the length-(l-1) sequence ending just before m, at m.i - 1.
             pi = pi.saturating_mul(optimal.pi[m.i - 1][&(len - 1)]);
         }
-        // calculate the minimization func
-        let mut guesses = (factorial(len) as u64).saturating_mul(pi);
-        if !exclude_additive {
-            let additive = if len == 1 {
-                1
-            } else {
-                (2..len).fold(MIN_GUESSES_BEFORE_GROWING_SEQUENCE, |acc, _| {
-                    acc.saturating_mul(MIN_GUESSES_BEFORE_GROWING_SEQUENCE)
-                })
-            };
-            guesses = guesses.saturating_add(additive);
-        }
+        // calculate the minimization func.
+        // once the product saturates, the factorial and additive terms can't change it.
+        let guesses = if pi == u64::MAX {
+            u64::MAX
+        } else {
+            let mut guesses = factorial(len).saturating_mul(pi);
+            if !exclude_additive {
+                let additive = if len == 1 {
+                    1
+                } else {
+                    let exp = u32::try_from(len - 1).unwrap_or(u32::MAX);
+                    MIN_GUESSES_BEFORE_GROWING_SEQUENCE.saturating_pow(exp)
+                };
+                guesses = guesses.saturating_add(additive);
+            }
+            guesses
+        };
+        // a word followed by a number covering the whole password is tried early by attackers.
+        let guesses = if optimal.penalize_word_with_number_suffix
+            && len == 2
+            && k + 1 == optimal.m.len()
+            && optimal.m[m.i - 1]
+                .get(&1)
+                .map_or(false, |word| is_number_suffix_of_word(word, &m))
+        {
+            (guesses / WORD_WITH_NUMBER_SUFFIX_PENALTY).max(1)
+        } else {
+            guesses
+        };
         // update state if new best.
         // first see if any competing sequences covering this prefix, with l or fewer matches,
         // fare better than this sequence. if so, skip it and return.
@@ -163,13 +529,13 @@ pub fn most_guessable_match_sequence(
     /// helper: evaluate bruteforce matches ending at k.
     fn bruteforce_update(k: usize, password: &str, optimal: &mut Optimal, exclude_additive: bool) {
         // see if a single bruteforce match spanning the k-prefix is optimal.
-        let m = make_bruteforce_match(0, k, password);
+        let m = make_bruteforce_match(0, k, password, optimal);
         update(m, 1, password, optimal, exclude_additive);
         for i in 1..=k {
             // generate k bruteforce matches, spanning from (i=1, j=k) up to (i=k, j=k).
             // see if adding these new matches to any of the sequences in optimal[i-1]
             // leads to new bests.
-            let m = make_bruteforce_match(i, k, password);
+            let m = make_bruteforce_match(i, k, password, optimal);
             for (l, last_m) in optimal.m[i - 1].clone() {
                 // corner: an optimal sequence will never have two adjacent bruteforce matches.
                 // it is strictly better to have a single bruteforce match spanning the same region:
@@ -185,13 +551,30 @@ pub fn most_guessable_match_sequence(
     }
 
     /// helper: make bruteforce match objects spanning i to j, inclusive.
-    fn make_bruteforce_match(i: usize, j: usize, password: &str) -> Match {
+    fn make_bruteforce_match(i: usize, j: usize, password: &str, optimal: &Optimal) -> Match {
+        let token: String = password.chars().take(j + 1).skip(i).collect();
+        let opaque_bytes = &optimal.opaque_bytes;
+        let has_opaque_bytes = opaque_bytes
+            .get(opaque_bytes.partition_point(|&k| k < i))
+            .map_or(false, |&k| k <= j);
         Match {
             pattern: MatchPattern::BruteForce,
-            token: password.chars().take(j + 1).skip(i).collect(),
+            guesses: if optimal.bruteforce_cardinality.is_some()
+                || optimal.unicode_bruteforce_cardinality
+                || has_opaque_bytes
+            {
+                Some(bruteforce_guesses(
+                    &token,
+                    |k| has_opaque_bytes && opaque_bytes.binary_search(&(i + k)).is_ok(),
+                    optimal.bruteforce_cardinality,
+                    optimal.unicode_bruteforce_cardinality,
+                ))
+            } else {
+                None
+            },
+            token,
             i,
             j,
-            ..Match::default()
         }
     }
 
@@ -245,18 +628,45 @@ pub fn most_guessable_match_sequence(
         optimal.g[n - 1][&optimal_l]
     };
 
-    GuessCalculation {
-        guesses,
-        guesses_log10: (guesses as f64).log10(),
-        sequence: optimal_match_sequence,
-    }
+    let table = optimal
+        .g
+        .iter()
+        .map(|g| {
+            let mut row = vec![0; g.keys().max().map_or(0, |&l| l + 1)];
+            for (&l, &guesses) in g {
+                row[l] = guesses;
+            }
+            row
+        })
+        .collect();
+
+    (
+        ScoringResult {
+            guesses,
+            guesses_log10: (guesses as f64).log10(),
+            sequence: optimal_match_sequence,
+        },
+        DpTable(table),
+    )
+}
+
+/// The character standing for `byte` of an invalid UTF-8 sequence.
+pub(crate) fn opaque_byte(byte: u8) -> char {
+    char::from_u32(OPAQUE_BYTES_START + u32::from(byte)).unwrap()
 }
 
-fn factorial(n: usize) -> usize {
-    (1..=n).product()
+fn factorial(n: usize) -> u64 {
+    let mut result: u64 = 1;
+    for i in 2..=n as u64 {
+        result = result.saturating_mul(i);
+        if result == u64::MAX {
+            break;
+        }
+    }
+    result
 }
 
-fn estimate_guesses(m: &mut Match, password: &str) -> u64 {
+fn estimate_guesses(m: &mut Match, password: &str, weights: &EstimateWeights) -> u64 {
     if let Some(guesses) = m.guesses {
         // a match's guess estimate doesn't change. cache it.
         return guesses;
@@ -270,60 +680,104 @@ fn estimate_guesses(m: &mut Match, password: &str) -> u64 {
     } else {
         1
     };
-    let guesses = m.pattern.estimate(&m.token);
+    let guesses = m.pattern.estimate_with_weights(&m.token, weights);
     m.guesses = Some(cmp::max(guesses, min_guesses));
     m.guesses.unwrap()
 }
 
 trait Estimator {
-    fn estimate(&mut self, token: &str) -> u64;
+    fn estimate_with_weights(&mut self, token: &str, weights: &EstimateWeights) -> u64;
+
+    #[cfg(test)]
+    fn estimate(&mut self, token: &str) -> u64 {
+        self.estimate_with_weights(token, &EstimateWeights::default())
+    }
 }
 
 impl Estimator for MatchPattern {
-    fn estimate(&mut self, token: &str) -> u64 {
+    fn estimate_with_weights(&mut self, token: &str, weights: &EstimateWeights) -> u64 {
         match *self {
-            MatchPattern::Dictionary(ref mut p) => p.estimate(token),
-            MatchPattern::Spatial(ref mut p) => p.estimate(token),
-            MatchPattern::Repeat(ref mut p) => p.estimate(token),
-            MatchPattern::Sequence(ref mut p) => p.estimate(token),
-            MatchPattern::Regex(ref mut p) => p.estimate(token),
-            MatchPattern::Date(ref mut p) => p.estimate(token),
-            MatchPattern::BruteForce => {
-                let mut guesses = BRUTEFORCE_CARDINALITY;
-                let token_len = token.chars().count();
-                if token_len >= 2 {
-                    for _ in 2..=token_len {
-                        guesses = guesses.saturating_mul(BRUTEFORCE_CARDINALITY);
-                    }
-                }
-                // small detail: make bruteforce matches at minimum one guess bigger than smallest allowed
-                // submatch guesses, such that non-bruteforce submatches over the same [i..j] take precedence.
-                let min_guesses = if token_len == 1 {
-                    MIN_SUBMATCH_GUESSES_SINGLE_CHAR + 1
+            MatchPattern::Dictionary(ref mut p) => p.estimate_with_weights(token, weights),
+            MatchPattern::Spatial(ref mut p) => p.estimate_with_weights(token, weights),
+            MatchPattern::Repeat(ref mut p) => p.estimate_with_weights(token, weights),
+            MatchPattern::Sequence(ref mut p) => p.estimate_with_weights(token, weights),
+            MatchPattern::Regex(ref mut p) => p.estimate_with_weights(token, weights),
+            MatchPattern::Date(ref mut p) => p.estimate_with_weights(token, weights),
+            // the matches of user-supplied regular expressions come with their guesses
+            #[cfg(feature = "regex")]
+            MatchPattern::CustomRegex(_) => bruteforce_guesses(token, |_| false, None, false),
+            MatchPattern::BruteForce => bruteforce_guesses(token, |_| false, None, false),
+        }
+    }
+}
+
+/// The guesses of a bruteforce match, with `BRUTEFORCE_CARDINALITY` or the given cardinality
+/// for each character whose index in `token` isn't an opaque byte according to `is_opaque`,
+/// or the cardinality of its script with `unicode` for each character which isn't ASCII either.
+fn bruteforce_guesses(
+    token: &str,
+    is_opaque: impl Fn(usize) -> bool,
+    cardinality: Option<f64>,
+    unicode: bool,
+) -> u64 {
+    let token_len = token.chars().count();
+    let guesses = match cardinality {
+        None if !unicode => {
+            let mut guesses: u64 = 1;
+            for k in 0..token_len {
+                let cardinality = if is_opaque(k) {
+                    OPAQUE_BYTE_CARDINALITY
                 } else {
-                    MIN_SUBMATCH_GUESSES_MULTI_CHAR + 1
+                    BRUTEFORCE_CARDINALITY
                 };
-                cmp::max(guesses, min_guesses)
+                guesses = guesses.saturating_mul(cardinality);
+                if guesses == u64::MAX {
+                    break;
+                }
             }
+            guesses
         }
-    }
+        // the conversion saturates at u64::MAX
+        _ => token
+            .chars()
+            .enumerate()
+            .map(|(k, c)| {
+                if is_opaque(k) {
+                    OPAQUE_BYTE_CARDINALITY as f64
+                } else if unicode && !c.is_ascii() {
+                    script_bruteforce_cardinality(c)
+                } else {
+                    cardinality.unwrap_or(BRUTEFORCE_CARDINALITY as f64)
+                }
+            })
+            .product::<f64>()
+            .round() as u64,
+    };
+    // small detail: make bruteforce matches at minimum one guess bigger than smallest allowed
+    // submatch guesses, such that non-bruteforce submatches over the same [i..j] take precedence.
+    let min_guesses = if token_len == 1 {
+        MIN_SUBMATCH_GUESSES_SINGLE_CHAR + 1
+    } else {
+        MIN_SUBMATCH_GUESSES_MULTI_CHAR + 1
+    };
+    cmp::max(guesses, min_guesses)
 }
 
 impl Estimator for DictionaryPattern {
-    fn estimate(&mut self, token: &str) -> u64 {
-        let uppercase_variations = uppercase_variations(token);
-        let l33t_variations = l33t_variations(self, token);
+    fn estimate_with_weights(&mut self, token: &str, weights: &EstimateWeights) -> u64 {
+        let uppercase_variations = uppercase_variations(token, &weights.variations);
+        let l33t_variations = l33t_variations(self, token, &weights.variations);
         self.base_guesses = self.rank as u64;
         self.uppercase_variations = uppercase_variations;
         self.l33t_variations = l33t_variations;
         self.base_guesses
-            * self.uppercase_variations
-            * self.l33t_variations
-            * if self.reversed { 2 } else { 1 }
+            .saturating_mul(self.uppercase_variations)
+            .saturating_mul(self.l33t_variations)
+            .saturating_mul(if self.reversed { 2 } else { 1 })
     }
 }
 
-fn uppercase_variations(token: &str) -> u64 {
+fn uppercase_variations(token: &str, weights: &VariationWeights) -> u64 {
     if token.chars().all(char::is_lowercase) || token.to_lowercase().as_str() == token {
         return 1;
     }
@@ -335,7 +789,7 @@ fn uppercase_variations(token: &str) -> u64 {
         && token.chars().filter(|&c| c.is_uppercase()).count() == 1)
         || token.chars().all(char::is_uppercase)
     {
-        return 2;
+        return weights.capitalization;
     }
     // otherwise calculate the number of ways to capitalize U+L uppercase+lowercase letters
     // with U uppercase letters or less. or, if there's more uppercase than lower (for eg. PASSwORD),
@@ -344,14 +798,14 @@ fn uppercase_variations(token: &str) -> u64 {
     let lower = token.chars().filter(|c| c.is_lowercase()).count();
     (1..=cmp::min(upper, lower))
         .map(|i| n_ck(upper + lower, i))
-        .sum()
+        .fold(0, u64::saturating_add)
 }
 
-fn l33t_variations(pattern: &DictionaryPattern, token: &str) -> u64 {
+fn l33t_variations(pattern: &DictionaryPattern, token: &str, weights: &VariationWeights) -> u64 {
     if !pattern.l33t {
         return 1;
     }
-    let mut variations = 1;
+    let mut variations: u64 = 1;
     for (subbed, unsubbed) in pattern.sub.as_ref().unwrap() {
         // lower-case match.token before calculating: capitalization shouldn't affect l33t calc.
         let token = token.to_lowercase();
@@ -361,13 +815,15 @@ fn l33t_variations(pattern: &DictionaryPattern, token: &str) -> u64 {
             // for this sub, password is either fully subbed (444) or fully unsubbed (aaa)
             // treat that as doubling the space (attacker needs to try fully subbed chars in addition to
             // unsubbed.)
-            variations *= 2;
+            variations = variations.saturating_mul(weights.l33t_substitution);
         } else {
             // this case is similar to capitalization:
             // with aa44a, U = 3, S = 2, attacker needs to try unsubbed + one sub + two subs
             let p = cmp::min(unsubbed, subbed);
-            let possibilities: u64 = (1..=p).map(|i| n_ck(unsubbed + subbed, i)).sum();
-            variations *= possibilities;
+            let possibilities = (1..=p)
+                .map(|i| n_ck(unsubbed + subbed, i))
+                .fold(0, u64::saturating_add);
+            variations = variations.saturating_mul(possibilities);
         }
     }
     variations
@@ -392,11 +848,12 @@ fn n_ck(n: usize, k: usize) -> u64 {
 }
 
 impl Estimator for SpatialPattern {
-    fn estimate(&mut self, token: &str) -> u64 {
-        let (starts, degree) = if ["qwerty", "dvorak"].contains(&self.graph.as_str()) {
-            (*KEYBOARD_STARTING_POSITIONS, *KEYBOARD_AVERAGE_DEGREE)
-        } else {
+    fn estimate_with_weights(&mut self, token: &str, weights: &EstimateWeights) -> u64 {
+        let (starts, degree) = if ["keypad", "mac_keypad"].contains(&self.graph.as_str()) {
             (*KEYPAD_STARTING_POSITIONS, *KEYPAD_AVERAGE_DEGREE)
+        } else {
+            // custom graphs are treated as keyboards
+            (*KEYBOARD_STARTING_POSITIONS, *KEYBOARD_AVERAGE_DEGREE)
         };
         let mut guesses = 0u64;
         let len = token.chars().count();
@@ -407,21 +864,32 @@ impl Estimator for SpatialPattern {
                 guesses = guesses.saturating_add(
                     n_ck(i - 1, j - 1)
                         .saturating_mul(starts)
-                        .saturating_mul(degree.pow(j as u32)),
+                        .saturating_mul(degree.saturating_pow(j as u32)),
                 );
             }
         }
+        // the average degree overestimates walks near the edges of the keyboard, which can't
+        // continue in every direction. the number of walks which actually exist on the graph
+        // is an upper bound of the patterns to try, so it's used when it's lower,
+        // see [`ZxcvbnOptions::count_spatial_walks`].
+        if weights.count_spatial_walks {
+            if let Some(walks) = count_spatial_walks(&self.graph, len, self.turns) {
+                guesses = cmp::min(guesses, walks);
+            }
+        }
         // add extra guesses for shifted keys. (% instead of 5, A instead of a.)
         // math is similar to extra guesses of l33t substitutions in dictionary matches.
         let shifted_count = self.shifted_count;
         if shifted_count > 0 {
             let unshifted_count = len - shifted_count;
-            if unshifted_count == 0 {
-                guesses = guesses.saturating_mul(2);
+            if unshifted_count == 0 || self.alternating_shift {
+                // all-shifted and alternating patterns are single, predictable shift schemes,
+                // so they only double the space, like a capitalized dictionary word.
+                guesses = guesses.saturating_mul(weights.variations.shift);
             } else {
-                let shifted_variations: u64 = (1..=cmp::min(shifted_count, unshifted_count))
+                let shifted_variations = (1..=cmp::min(shifted_count, unshifted_count))
                     .map(|i| n_ck(shifted_count + unshifted_count, i))
-                    .sum();
+                    .fold(0, u64::saturating_add);
                 guesses = guesses.saturating_mul(shifted_variations);
             }
         }
@@ -445,17 +913,124 @@ fn calc_average_degree(graph: &HashMap<char, Vec<Option<&'static str>>>) -> u64
     sum / graph.len() as u64
 }
 
+/// The length of the longest walks counted by [`count_spatial_walks`]. Longer walks are
+/// estimated with the average degree only, as their counts are rarely lower.
+///
+/// Counting the walks, and this limit, are this crate's own model of keyboard patterns:
+/// they don't come from zxcvbn3 or another zxcvbn implementation.
+const MAX_COUNTED_WALK_LEN: usize = 40;
+
+lazy_static! {
+    static ref WALK_COUNTS: HashMap<&'static str, Vec<Vec<u64>>> = {
+        let mut table = HashMap::with_capacity(4);
+        table.insert("qwerty", walk_count_table(&crate::adjacency_graphs::QWERTY));
+        table.insert("dvorak", walk_count_table(&crate::adjacency_graphs::DVORAK));
+        table.insert("keypad", walk_count_table(&crate::adjacency_graphs::KEYPAD));
+        table.insert(
+            "mac_keypad",
+            walk_count_table(&crate::adjacency_graphs::MAC_KEYPAD),
+        );
+        table
+    };
+}
+
+/// The number of walks on the built-in graph named `graph` with 2 to `len` keys and 1 to
+/// `turns` turns, i.e. the spatial patterns which are at most as long and have at most as
+/// many turns. Returns `None` for other graphs and for walks longer than
+/// `MAX_COUNTED_WALK_LEN`.
+fn count_spatial_walks(graph: &str, len: usize, turns: usize) -> Option<u64> {
+    let table = WALK_COUNTS.get(graph)?;
+    let rows = table.get(2..=len)?;
+    Some(
+        rows.iter()
+            .flat_map(|row| row.iter().skip(1).take(turns))
+            .fold(0, |acc, &count| acc.saturating_add(count)),
+    )
+}
+
+/// Counts the walks on `graph`: the entry `[n][t]` is the number of walks of `n` keys which
+/// change direction `t` times, counting the first direction as a turn like the spatial matcher.
+/// Shifted keys start walks too, like in `KEYBOARD_STARTING_POSITIONS`.
+fn walk_count_table(graph: &HashMap<char, Vec<Option<&'static str>>>) -> Vec<Vec<u64>> {
+    let keys: Vec<char> = graph.keys().copied().collect();
+    let index: HashMap<char, usize> = keys.iter().enumerate().map(|(i, &c)| (c, i)).collect();
+    let directions = graph.values().map(Vec::len).max().unwrap_or_default();
+    // the unshifted key in each direction of each key, if any
+    let neighbors: Vec<Vec<Option<usize>>> = keys
+        .iter()
+        .map(|key| {
+            (0..directions)
+                .map(|direction| {
+                    graph[key]
+                        .get(direction)
+                        .copied()
+                        .flatten()
+                        .and_then(|adjacent| adjacent.chars().next())
+                        .and_then(|adjacent| index.get(&adjacent).copied())
+                })
+                .collect()
+        })
+        .collect();
+
+    // walks[t][key * directions + direction]: the number of walks of the current length with
+    // t turns, ending at key and last moving in direction.
+    let mut walks = vec![vec![0u64; keys.len() * directions]; 2];
+    for key_neighbors in &neighbors {
+        for (direction, neighbor) in key_neighbors.iter().enumerate() {
+            if let Some(neighbor) = neighbor {
+                walks[1][neighbor * directions + direction] += 1;
+            }
+        }
+    }
+    let sum = |walks: &[Vec<u64>]| -> Vec<u64> {
+        walks
+            .iter()
+            .map(|counts| counts.iter().fold(0, |acc: u64, &c| acc.saturating_add(c)))
+            .collect()
+    };
+    let mut table = vec![Vec::new(), Vec::new(), sum(&walks)];
+    for len in 3..=MAX_COUNTED_WALK_LEN {
+        let mut longer_walks = vec![vec![0u64; keys.len() * directions]; len];
+        for (turns, counts) in walks.iter().enumerate() {
+            for (state, &count) in counts.iter().enumerate() {
+                if count == 0 {
+                    continue;
+                }
+                let (key, last_direction) = (state / directions, state % directions);
+                for (direction, neighbor) in neighbors[key].iter().enumerate() {
+                    if let Some(neighbor) = neighbor {
+                        let turns = if direction == last_direction {
+                            turns
+                        } else {
+                            turns + 1
+                        };
+                        let walks = &mut longer_walks[turns][neighbor * directions + direction];
+                        *walks = walks.saturating_add(count);
+                    }
+                }
+            }
+        }
+        table.push(sum(&longer_walks));
+        walks = longer_walks;
+    }
+    table
+}
+
 impl Estimator for RepeatPattern {
-    fn estimate(&mut self, _: &str) -> u64 {
+    fn estimate_with_weights(&mut self, _: &str, _weights: &EstimateWeights) -> u64 {
+        // as in the JS zxcvbn: each guess of the base token is tried with each repeat count
         self.base_guesses.saturating_mul(self.repeat_count as u64)
     }
 }
 
 impl Estimator for SequencePattern {
-    fn estimate(&mut self, token: &str) -> u64 {
+    fn estimate_with_weights(&mut self, token: &str, _weights: &EstimateWeights) -> u64 {
         let first_chr = token.chars().next().unwrap();
         // lower guesses for obvious starting points
-        let mut base_guesses = if ['a', 'A', 'z', 'Z', '0', '1', '9'].contains(&first_chr) {
+        let mut base_guesses: u64 = if self.element_count.is_some() {
+            // sequences registered through `ZxcvbnOptions::with_sequence`
+            u64::from(self.sequence_space)
+        } else if ['a', 'A', 'z', 'Z', '0', '1', '9'].contains(&first_chr) {
             4
         } else if first_chr.is_ascii_digit() {
             10
@@ -469,22 +1044,24 @@ impl Estimator for SequencePattern {
             // 2x guesses
             base_guesses *= 2;
         }
-        base_guesses * token.chars().count() as u64
+        let len = self.element_count.unwrap_or_else(|| token.chars().count());
+        base_guesses.saturating_mul(len as u64)
     }
 }
 
 impl Estimator for RegexPattern {
-    fn estimate(&mut self, token: &str) -> u64 {
+    fn estimate_with_weights(&mut self, token: &str, _weights: &EstimateWeights) -> u64 {
         if CHAR_CLASS_BASES.keys().any(|x| *x == self.regex_name) {
-            CHAR_CLASS_BASES[self.regex_name].pow(token.chars().count() as u32)
+            CHAR_CLASS_BASES[self.regex_name].saturating_pow(token.chars().count() as u32)
         } else {
-            match self.regex_name {
-                "recent_year" => {
-                    let year_space =
-                        (self.regex_match[0].parse::<i32>().unwrap() - *REFERENCE_YEAR).abs();
+            let year = self.regex_match.first().map_or(token, String::as_str);
+            match (self.regex_name, year.parse::<i32>()) {
+                ("recent_year", Ok(year)) => {
+                    let year_space = (year - *REFERENCE_YEAR).abs();
                     cmp::max(year_space, MIN_YEAR_SPACE) as u64
                 }
-                _ => unreachable!(),
+                // e.g. the regex matches of custom matchers which didn't preset their guesses
+                _ => bruteforce_guesses(token, |_| false, None, false),
             }
         }
     }
@@ -504,16 +1081,26 @@ lazy_static! {
 }
 
 impl Estimator for DatePattern {
-    fn estimate(&mut self, _: &str) -> u64 {
-        // base guesses: (year distance from REFERENCE_YEAR) * num_days * num_years
-        let year_space = cmp::max((self.year - *REFERENCE_YEAR).abs(), MIN_YEAR_SPACE);
-        let mut guesses = year_space as u64 * 365;
-        // add factor of 4 for separator selection (one of ~4 choices)
-        if !self.separator.is_empty() {
-            guesses *= 4;
-        }
-        guesses
+    fn estimate_with_weights(&mut self, _: &str, _weights: &EstimateWeights) -> u64 {
+        date_guesses(self.year, &self.separator, false)
+    }
+}
+
+/// The guesses of a date of `year` written with `separator`, with `two_digit_year` if its
+/// year was written with two digits, and its century inferred.
+pub(crate) fn date_guesses(year: i32, separator: &str, two_digit_year: bool) -> u64 {
+    // base guesses: (year distance from REFERENCE_YEAR) * num_days * num_years
+    let mut year_space = cmp::max((year - *REFERENCE_YEAR).abs(), MIN_YEAR_SPACE);
+    if two_digit_year {
+        // only 100 years can be written with two digits, whichever their century
+        year_space = cmp::min(year_space, TWO_DIGIT_YEAR_SPACE);
+    }
+    let mut guesses = year_space as u64 * 365;
+    // add factor of 4 for separator selection (one of ~4 choices)
+    if !separator.is_empty() {
+        guesses *= 4;
     }
+    guesses
 }
 
 #[cfg(test)]
@@ -521,7 +1108,7 @@ mod tests {
     use crate::matching::patterns::*;
     use crate::matching::Match;
     use crate::scoring;
-    use crate::scoring::Estimator;
+    use crate::scoring::{EstimateWeights, Estimator, VariationWeights};
     use quickcheck::TestResult;
     use std::collections::HashMap;
 
@@ -544,7 +1131,7 @@ mod tests {
 
     quickcheck! {
         fn test_n_ck_mul_overflow(n: usize, k: usize) -> TestResult {
-            if n >= 63 && n <= 100 {
+            if (63..=100).contains(&n) {
                 scoring::n_ck(n, k); // Must not panic
                 TestResult::from_bool(true)
             } else {
@@ -552,6 +1139,16 @@ mod tests {
             }
         }
 
+        fn test_guesses_dont_overflow(password: String) -> TestResult {
+            // overflows panic in tests, so saturated guesses are the only acceptable outcome
+            if password.is_empty() {
+                return TestResult::discard();
+            }
+            let matches = crate::matching::omnimatch(&password, &HashMap::new());
+            let result = scoring::most_guessable_match_sequence(&password, &matches, false);
+            TestResult::from_bool(result.guesses >= 1)
+        }
+
         fn test_n_ck_mirror_identity(n: usize, k: usize) -> TestResult {
             if k > n || n >= 63 {
                 return TestResult::discard();
@@ -590,7 +1187,8 @@ mod tests {
             ..Match::default()
         };
 
-        let result = scoring::most_guessable_match_sequence(password, &[m.clone()], true);
+        let result =
+            scoring::most_guessable_match_sequence(password, std::slice::from_ref(&m), true);
         assert_eq!(result.sequence.len(), 2);
         assert_eq!(result.sequence[0], m);
         let m1 = &result.sequence[1];
@@ -599,6 +1197,78 @@ mod tests {
         assert_eq!(m1.j, 9);
     }
 
+    #[test]
+    fn test_search_empty_password() {
+        let (result, table) = scoring::score_match_sequence("", &[], false);
+        assert_eq!(result.guesses, 0);
+        assert!(result.sequence.is_empty());
+        assert!(table.0.is_empty());
+    }
+
+    #[test]
+    fn test_score_match_sequence_dp_table() {
+        let password = "0123456789";
+        let m = Match {
+            i: 0,
+            j: 5,
+            guesses: Some(1),
+            pattern: MatchPattern::Dictionary(DictionaryPattern::default()),
+            ..Match::default()
+        };
+
+        let (result, table) =
+            scoring::score_match_sequence(password, std::slice::from_ref(&m), true);
+        assert_eq!(table.0.len(), password.len());
+        // single bruteforce matches covering the prefixes
+        assert_eq!(table.0[0], [0, 11]);
+        assert_eq!(table.0[4][1], 100_000);
+        assert_eq!(table.0[9][1], 10_000_000_000);
+        // the dictionary match beats bruteforcing its prefix
+        assert_eq!(table.0[5], [0, 1]);
+        // the dictionary match followed by bruteforce: 2! * 1 * guesses of the bruteforce match
+        assert_eq!(table.0[6][2], 2 * 11);
+        assert_eq!(table.0[9][2], 2 * 10_000);
+        assert_eq!(result.guesses, table.0[9][2]);
+        assert_eq!(
+            result.guesses,
+            scoring::most_guessable_match_sequence(password, &[m], true).guesses
+        );
+    }
+
+    #[cfg(feature = "match_graph")]
+    #[test]
+    fn test_match_graph() {
+        let password = "0123456789";
+        let m = Match {
+            i: 0,
+            j: 5,
+            guesses: Some(1),
+            pattern: MatchPattern::Dictionary(DictionaryPattern::default()),
+            ..Match::default()
+        };
+        let graph = scoring::match_graph(password, std::slice::from_ref(&m));
+        assert_eq!(graph.node_count, 11);
+        // the match and a bruteforce edge for each of the 55 spans
+        assert_eq!(graph.edges.len(), 56);
+        assert!(graph
+            .edges
+            .windows(2)
+            .all(|w| (w[0].from, w[0].to) <= (w[1].from, w[1].to)));
+        let from_start: Vec<_> = graph.edges.iter().filter(|e| e.from == 0).collect();
+        assert_eq!(from_start.len(), 11);
+        assert_eq!(from_start[0].m.token, "0");
+        assert_eq!(from_start[0].guesses, 11);
+        // the match comes before the bruteforce edge of the same span
+        assert_eq!(from_start[5].m, m);
+        assert_eq!(from_start[5].to, 6);
+        assert_eq!(from_start[6].m.pattern, MatchPattern::BruteForce);
+        // a whole password bruteforce edge isn't a submatch
+        assert_eq!(from_start[10].guesses, 10_000_000_000);
+        let from_6: Vec<_> = graph.edges.iter().filter(|e| e.from == 6).collect();
+        assert_eq!(from_6.last().unwrap().guesses, 10_000);
+        assert_eq!(from_6.last().unwrap().m.guesses, Some(10_000));
+    }
+
     #[test]
     fn test_search_returns_bruteforce_and_match_when_match_covers_a_suffix() {
         let password = "0123456789";
@@ -610,7 +1280,8 @@ mod tests {
             ..Match::default()
         };
 
-        let result = scoring::most_guessable_match_sequence(password, &[m.clone()], true);
+        let result =
+            scoring::most_guessable_match_sequence(password, std::slice::from_ref(&m), true);
         assert_eq!(result.sequence.len(), 2);
         let m0 = &result.sequence[0];
         assert_eq!(m0.pattern.variant(), "bruteforce");
@@ -630,7 +1301,8 @@ mod tests {
             ..Match::default()
         };
 
-        let result = scoring::most_guessable_match_sequence(password, &[m.clone()], true);
+        let result =
+            scoring::most_guessable_match_sequence(password, std::slice::from_ref(&m), true);
         assert_eq!(result.sequence.len(), 3);
         assert_eq!(result.sequence[1], m);
         let m0 = &result.sequence[0];
@@ -739,7 +1411,10 @@ mod tests {
             guesses: Some(1),
             ..Match::default()
         };
-        assert_eq!(scoring::estimate_guesses(&mut m, ""), 1);
+        assert_eq!(
+            scoring::estimate_guesses(&mut m, "", &EstimateWeights::default()),
+            1
+        );
     }
 
     #[test]
@@ -756,7 +1431,10 @@ mod tests {
             token: token.to_string(),
             ..Match::default()
         };
-        assert_eq!(scoring::estimate_guesses(&mut m, token), p.estimate(token));
+        assert_eq!(
+            scoring::estimate_guesses(&mut m, token, &EstimateWeights::default()),
+            p.estimate(token)
+        );
     }
 
     #[test]
@@ -790,6 +1468,52 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_repeat_guesses_of_multi_character_bases() {
+        // the guesses of the JS zxcvbn: the sequence "abc" takes 4 * 3 guesses, plus 1 for
+        // being a whole sequence, and each of them is tried with 2 repeats
+        for (password, base_guesses, repeat_count, guesses) in
+            [("abcabc", 13, 2, 27), ("ababab", 9, 3, 28)]
+        {
+            let entropy = crate::zxcvbn(password, &[]);
+            assert_eq!(entropy.guesses(), guesses, "{}", password);
+            let m = &entropy.sequence()[0];
+            let p = if let MatchPattern::Repeat(ref p) = m.pattern {
+                p
+            } else {
+                panic!("Wrong match pattern for {}", password)
+            };
+            assert_eq!(p.base_guesses, base_guesses, "{}", password);
+            assert_eq!(p.repeat_count, repeat_count, "{}", password);
+            assert_eq!(m.guesses, Some(base_guesses * repeat_count as u64));
+        }
+
+        // a common password rather than the repeats of its letters
+        let entropy = crate::zxcvbn("aabbcc", &[]);
+        assert_eq!(entropy.guesses(), 7331);
+        let repeats: Vec<Match> = crate::matching::omnimatch("aabbcc", &HashMap::new())
+            .into_iter()
+            .filter(|m| matches!(m.pattern, MatchPattern::Repeat(_)))
+            .collect();
+        assert_eq!(
+            repeats.iter().map(|m| m.token.as_str()).collect::<Vec<_>>(),
+            ["aa", "bb", "cc"]
+        );
+        for mut m in repeats {
+            let (base_guesses, repeat_count) = match m.pattern {
+                MatchPattern::Repeat(ref p) => (p.base_guesses, p.repeat_count),
+                _ => unreachable!(),
+            };
+            assert_eq!(repeat_count, 2);
+            assert_eq!(
+                m.pattern.estimate(&m.token),
+                base_guesses * 2,
+                "{}",
+                m.token
+            );
+        }
+    }
+
     #[test]
     fn test_sequence_guesses() {
         let test_data = [
@@ -837,13 +1561,14 @@ mod tests {
         };
         assert_eq!(
             p.estimate(token),
-            (*scoring::REFERENCE_YEAR - 1972).abs() as u64
+            (*scoring::REFERENCE_YEAR - 1972).unsigned_abs() as u64
         );
     }
 
     #[test]
     fn test_regex_guesses_recent_year() {
-        let token = "2005";
+        let token = (*scoring::REFERENCE_YEAR - 5).to_string();
+        let token = token.as_str();
         let mut p = RegexPattern {
             regex_name: "recent_year",
             regex_match: vec![token.to_string()],
@@ -851,6 +1576,23 @@ mod tests {
         assert_eq!(p.estimate(token), scoring::MIN_YEAR_SPACE as u64);
     }
 
+    #[test]
+    fn test_regex_guesses_unknown_regex() {
+        let token = "ACME";
+        let mut p = RegexPattern {
+            regex_name: "company",
+            ..RegexPattern::default()
+        };
+        assert_eq!(p.estimate(token), 10u64.pow(4));
+        let mut p = RegexPattern {
+            regex_name: "recent_year",
+            ..RegexPattern::default()
+        };
+        let year = scoring::REFERENCE_YEAR.to_string();
+        assert_eq!(p.estimate(&year), scoring::MIN_YEAR_SPACE as u64);
+        assert_eq!(p.estimate(token), 10u64.pow(4));
+    }
+
     #[test]
     fn test_regex_guesses_current_year() {
         let token = time::OffsetDateTime::now_utc().year().to_string();
@@ -872,7 +1614,7 @@ mod tests {
         let token = "1123";
         assert_eq!(
             p.estimate(token),
-            365 * (*scoring::REFERENCE_YEAR - p.year).abs() as u64
+            365 * (*scoring::REFERENCE_YEAR - p.year).unsigned_abs() as u64
         );
     }
 
@@ -888,6 +1630,16 @@ mod tests {
         assert_eq!(p.estimate(token), 365 * scoring::MIN_YEAR_SPACE as u64 * 4);
     }
 
+    #[test]
+    fn test_date_guesses_two_digit_years_have_at_most_100_years() {
+        assert_eq!(scoring::date_guesses(1905, "", true), 365 * 100);
+        assert_eq!(scoring::date_guesses(1905, "/", true), 365 * 100 * 4);
+        assert_eq!(
+            scoring::date_guesses(1905, "", false),
+            365 * (*scoring::REFERENCE_YEAR - 1905).unsigned_abs() as u64
+        );
+    }
+
     #[test]
     #[allow(clippy::clone_on_copy)]
     fn test_spatial_guesses_no_turns_or_shifts() {
@@ -895,12 +1647,13 @@ mod tests {
             graph: "qwerty".to_string(),
             turns: 1,
             shifted_count: 0,
+            alternating_shift: false,
         };
         let token = "zxcvbn";
         let base_guesses = *scoring::KEYBOARD_STARTING_POSITIONS
             * *scoring::KEYBOARD_AVERAGE_DEGREE
             * (token.len() - 1) as u64;
-        assert_eq!(p.estimate(token), base_guesses as u64);
+        assert_eq!(p.estimate(token), base_guesses);
     }
 
     #[test]
@@ -910,6 +1663,7 @@ mod tests {
             graph: "qwerty".to_string(),
             turns: 1,
             shifted_count: 2,
+            alternating_shift: false,
         };
         let token = "ZxCvbn";
         let base_guesses = *scoring::KEYBOARD_STARTING_POSITIONS
@@ -926,13 +1680,30 @@ mod tests {
             graph: "qwerty".to_string(),
             turns: 1,
             shifted_count: 6,
+            alternating_shift: false,
         };
         let token = "ZXCVBN";
         let base_guesses = *scoring::KEYBOARD_STARTING_POSITIONS
             * *scoring::KEYBOARD_AVERAGE_DEGREE
             * (token.len() - 1) as u64
             * 2;
-        assert_eq!(p.estimate(token), base_guesses as u64);
+        assert_eq!(p.estimate(token), base_guesses);
+    }
+
+    #[test]
+    fn test_spatial_guesses_doubles_when_alternating_shift() {
+        let mut p = SpatialPattern {
+            graph: "qwerty".to_string(),
+            turns: 1,
+            shifted_count: 3,
+            alternating_shift: true,
+        };
+        let token = "qWeRtY";
+        let base_guesses = *scoring::KEYBOARD_STARTING_POSITIONS
+            * *scoring::KEYBOARD_AVERAGE_DEGREE
+            * (token.len() - 1) as u64
+            * 2;
+        assert_eq!(p.estimate(token), base_guesses);
     }
 
     #[test]
@@ -942,6 +1713,7 @@ mod tests {
             graph: "qwerty".to_string(),
             turns: 3,
             shifted_count: 0,
+            alternating_shift: false,
         };
         let token = "zxcft6yh";
         let guesses: u64 = (2..(token.len() + 1))
@@ -951,7 +1723,6 @@ mod tests {
                         scoring::n_ck(i - 1, j - 1)
                             * (*scoring::KEYBOARD_STARTING_POSITIONS
                                 * scoring::KEYBOARD_AVERAGE_DEGREE.pow(j as u32))
-                                as u64
                     })
                     .sum::<u64>()
             })
@@ -959,6 +1730,67 @@ mod tests {
         assert_eq!(p.estimate(token), guesses);
     }
 
+    #[test]
+    fn test_spatial_guesses_counting_walks_are_lower_along_edges() {
+        let guesses = |p: &SpatialPattern, token: &str, count_spatial_walks: bool| {
+            let weights = EstimateWeights {
+                count_spatial_walks,
+                ..EstimateWeights::default()
+            };
+            p.clone().estimate_with_weights(token, &weights)
+        };
+        for password in ["qwerty", "1qaz2wsx"] {
+            let spatial_matches: Vec<Match> = crate::zxcvbn_all_matches(password, &[])
+                .into_iter()
+                .filter(|m| matches!(m.pattern, MatchPattern::Spatial(_)))
+                .collect();
+            assert!(!spatial_matches.is_empty(), "{}", password);
+            for m in spatial_matches {
+                let p = match m.pattern {
+                    MatchPattern::Spatial(ref p) => p,
+                    _ => unreachable!(),
+                };
+                let before = guesses(p, &m.token, false);
+                let after = guesses(p, &m.token, true);
+                assert!(after < before, "{}: {} < {}", m.token, after, before);
+            }
+        }
+
+        let p = SpatialPattern {
+            graph: "qwerty".to_string(),
+            turns: 1,
+            shifted_count: 0,
+            alternating_shift: false,
+        };
+        // the straight walks of 2 to 6 keys, instead of 5 * 94 starts * 4 directions
+        assert_eq!(guesses(&p, "qwerty", false), 1880);
+        assert_eq!(guesses(&p, "qwerty", true), 1200);
+        // the number of walks is only an upper bound when it's lower
+        let p = SpatialPattern {
+            graph: "keypad".to_string(),
+            turns: 1,
+            shifted_count: 0,
+            alternating_shift: false,
+        };
+        assert!(guesses(&p, "147", true) <= guesses(&p, "147", false));
+        // custom graphs, and walks which are too long, are estimated as before
+        let p = SpatialPattern {
+            graph: "custom".to_string(),
+            turns: 1,
+            shifted_count: 0,
+            alternating_shift: false,
+        };
+        assert_eq!(guesses(&p, "qwerty", true), guesses(&p, "qwerty", false));
+        assert_eq!(scoring::count_spatial_walks("qwerty", 41, 3), None);
+
+        let options = crate::ZxcvbnOptions::new().count_spatial_walks(true);
+        let password = "zxcvbnm,./";
+        assert!(
+            crate::zxcvbn_with_options(password, &[], &options).guesses()
+                < crate::zxcvbn(password, &[]).guesses()
+        );
+    }
+
     #[test]
     fn test_dictionary_base_guesses_equals_rank() {
         let mut p = DictionaryPattern {
@@ -976,7 +1808,10 @@ mod tests {
             ..DictionaryPattern::default()
         };
         let token = "AAAaaa";
-        assert_eq!(p.estimate(token), 32 * scoring::uppercase_variations(token));
+        assert_eq!(
+            p.estimate(token),
+            32 * scoring::uppercase_variations(token, &VariationWeights::default())
+        );
     }
 
     #[test]
@@ -1001,7 +1836,7 @@ mod tests {
             ..DictionaryPattern::default()
         };
         let token = "aaa@@@";
-        let expected = 32 * scoring::l33t_variations(&p, token);
+        let expected = 32 * scoring::l33t_variations(&p, token, &VariationWeights::default());
         assert_eq!(p.estimate(token), expected);
     }
 
@@ -1016,8 +1851,9 @@ mod tests {
             ..DictionaryPattern::default()
         };
         let token = "AaA@@@";
-        let expected =
-            32 * scoring::l33t_variations(&p, token) * scoring::uppercase_variations(token);
+        let expected = 32
+            * scoring::l33t_variations(&p, token, &VariationWeights::default())
+            * scoring::uppercase_variations(token, &VariationWeights::default());
         assert_eq!(p.estimate(token), expected);
     }
 
@@ -1041,17 +1877,262 @@ mod tests {
             ),
         ];
         for &(word, variants) in &test_data {
-            assert_eq!(scoring::uppercase_variations(word), variants);
+            assert_eq!(
+                scoring::uppercase_variations(word, &VariationWeights::default()),
+                variants
+            );
         }
     }
 
+    #[test]
+    fn test_custom_variation_weights() {
+        let weights = VariationWeights {
+            capitalization: 5,
+            l33t_substitution: 3,
+            shift: 7,
+        };
+        assert_eq!(scoring::uppercase_variations("Abcdef", &weights), 5);
+        assert_eq!(scoring::uppercase_variations("ABCDEF", &weights), 5);
+        assert_eq!(
+            scoring::uppercase_variations("aBcdef", &weights),
+            scoring::n_ck(6, 1)
+        );
+
+        let mut p = SpatialPattern {
+            graph: "qwerty".to_string(),
+            turns: 1,
+            shifted_count: 6,
+            alternating_shift: false,
+        };
+        let base_guesses = p.estimate("!@#$%^");
+        let weights = EstimateWeights {
+            variations: weights,
+            ..EstimateWeights::default()
+        };
+        assert_eq!(
+            p.estimate_with_weights("!@#$%^", &weights),
+            base_guesses / 2 * 7
+        );
+    }
+
+    #[test]
+    fn test_bruteforce_cardinality_of_allowed_characters() {
+        use crate::password_policy::CharacterClasses;
+        use crate::ZxcvbnOptions;
+
+        assert_eq!(
+            scoring::bruteforce_cardinality(CharacterClasses::default()),
+            scoring::BRUTEFORCE_CARDINALITY as f64
+        );
+        let alphanumeric = CharacterClasses {
+            symbols: false,
+            ..CharacterClasses::default()
+        };
+        let lowercase = CharacterClasses {
+            lowercase: true,
+            uppercase: false,
+            digits: false,
+            symbols: false,
+        };
+        let alphanumeric_cardinality = scoring::bruteforce_cardinality(alphanumeric);
+        let lowercase_cardinality = scoring::bruteforce_cardinality(lowercase);
+        assert!(1. < lowercase_cardinality && lowercase_cardinality < alphanumeric_cardinality);
+        assert!(alphanumeric_cardinality < 10.);
+
+        let password = "qJv8rTnw2";
+        let matches = crate::matching::omnimatch(password, &HashMap::new());
+        let guesses = |options: &ZxcvbnOptions| {
+            scoring::most_guessable_match_sequence_with_options(
+                password,
+                &matches,
+                false,
+                options,
+                &[],
+            )
+            .guesses
+        };
+        let default_guesses =
+            scoring::most_guessable_match_sequence(password, &matches, false).guesses;
+        assert_eq!(guesses(&ZxcvbnOptions::new()), default_guesses);
+        assert_eq!(
+            guesses(&ZxcvbnOptions::new().allowed_character_classes(CharacterClasses::default())),
+            default_guesses
+        );
+        assert!(
+            guesses(&ZxcvbnOptions::new().allowed_character_classes(alphanumeric))
+                < default_guesses
+        );
+    }
+
+    #[test]
+    fn test_bruteforce_cardinality_of_unicode_scripts() {
+        use crate::ZxcvbnOptions;
+
+        let cardinality = scoring::script_bruteforce_cardinality;
+        assert!(cardinality('密') > cardinality('한'));
+        assert!(cardinality('한') > cardinality('😀'));
+        assert!(cardinality('😀') > cardinality('ж'));
+        assert!(cardinality('ж') > cardinality('λ'));
+        assert!(cardinality('λ') > scoring::BRUTEFORCE_CARDINALITY as f64);
+        assert_eq!(
+            cardinality('𐰊'),
+            scoring::scaled_bruteforce_cardinality(scoring::OTHER_SCRIPT_CARDINALITY)
+        );
+        assert_eq!(
+            scoring::scaled_bruteforce_cardinality(scoring::PRINTABLE_ASCII_CARDINALITY),
+            scoring::BRUTEFORCE_CARDINALITY as f64
+        );
+
+        // ASCII characters and opaque bytes are guessed as without scripts
+        let opaque = "\u{10FF12}\u{10FFA0}";
+        for (token, is_opaque) in [("jd8h3lq", false), ("x", false), (opaque, true)] {
+            assert_eq!(
+                scoring::bruteforce_guesses(token, |_| is_opaque, None, true),
+                scoring::bruteforce_guesses(token, |_| is_opaque, None, false),
+                "{}",
+                token
+            );
+        }
+        assert_eq!(
+            scoring::bruteforce_guesses(opaque, |_| true, None, false),
+            256 * 256
+        );
+        // the same characters in a valid password are just private use characters
+        assert_eq!(
+            scoring::bruteforce_guesses(opaque, |_| false, None, false),
+            scoring::bruteforce_guesses("xy", |_| false, None, false)
+        );
+        assert_eq!(
+            scoring::bruteforce_guesses("ab密码", |_| false, None, true),
+            (100. * cardinality('密').powi(2)).round() as u64
+        );
+        assert_eq!(
+            scoring::bruteforce_guesses("ab密码", |_| false, Some(5.), true),
+            (25. * cardinality('密').powi(2)).round() as u64
+        );
+
+        let password = "𐰊𐰂𐰄𐰀𐰁";
+        let matches = crate::matching::omnimatch(password, &HashMap::new());
+        let options = ZxcvbnOptions::new().unicode_bruteforce_cardinality(true);
+        let result = scoring::most_guessable_match_sequence_with_options(
+            password,
+            &matches,
+            false,
+            &options,
+            &[],
+        );
+        assert!(
+            result.guesses
+                > scoring::most_guessable_match_sequence(password, &matches, false).guesses
+        );
+    }
+
+    #[test]
+    fn test_penalize_word_with_number_suffix() {
+        use crate::ZxcvbnOptions;
+
+        let score = |password: &str, penalize: bool| {
+            let matches = crate::matching::omnimatch(password, &HashMap::new());
+            let options = ZxcvbnOptions::new().penalize_word_with_number_suffix(penalize);
+            scoring::most_guessable_match_sequence_with_options(
+                password,
+                &matches,
+                false,
+                &options,
+                &[],
+            )
+        };
+        let result = score("summer2023", true);
+        assert!(scoring::is_word_with_number_suffix(&result.sequence));
+        assert_eq!(result.guesses, score("summer2023", false).guesses / 10);
+        // the penalty is part of the search, so the other sequences still compete with it
+        let product: u64 = result.sequence.iter().map(|m| m.guesses.unwrap()).product();
+        assert_eq!(
+            result.guesses,
+            (2 * product + scoring::MIN_GUESSES_BEFORE_GROWING_SEQUENCE) / 10
+        );
+        // only a word followed by a number covering the whole password is penalized
+        assert_eq!(
+            score("summer2023x", true).guesses,
+            score("summer2023x", false).guesses
+        );
+    }
+
+    #[test]
+    fn test_factorial_saturates() {
+        assert_eq!(scoring::factorial(0), 1);
+        assert_eq!(scoring::factorial(5), 120);
+        assert_eq!(scoring::factorial(20), 2_432_902_008_176_640_000);
+        assert_eq!(scoring::factorial(21), u64::MAX);
+        assert_eq!(scoring::factorial(100), u64::MAX);
+    }
+
+    #[test]
+    fn test_estimates_saturate_for_long_tokens() {
+        let token = "aA".repeat(50);
+        assert_eq!(
+            scoring::uppercase_variations(&token, &VariationWeights::default()),
+            u64::MAX
+        );
+
+        let mut subs = HashMap::with_capacity(1);
+        subs.insert('@', 'a');
+        let mut p = DictionaryPattern {
+            rank: 30_000,
+            l33t: true,
+            reversed: true,
+            sub: Some(subs),
+            ..DictionaryPattern::default()
+        };
+        let token = "a@".repeat(50);
+        assert_eq!(
+            scoring::l33t_variations(&p, &token, &VariationWeights::default()),
+            u64::MAX
+        );
+        assert_eq!(p.estimate(&token), u64::MAX);
+
+        let mut p = SpatialPattern {
+            graph: "qwerty".to_string(),
+            turns: 60,
+            shifted_count: 30,
+            alternating_shift: false,
+        };
+        assert_eq!(p.estimate(&"zaQ1".repeat(25)), u64::MAX);
+
+        let mut p = RegexPattern {
+            regex_name: "alpha_lower",
+            regex_match: vec![],
+        };
+        assert_eq!(p.estimate(&"a".repeat(20)), u64::MAX);
+    }
+
+    #[test]
+    fn test_search_saturates_for_long_sequences() {
+        // many short matches, so the factorial of the sequence length overflows
+        let password = "ab".repeat(50);
+        let matches: Vec<Match> = (0..50)
+            .map(|k| Match {
+                i: 2 * k,
+                j: 2 * k + 1,
+                token: "ab".to_string(),
+                guesses: Some(1),
+                pattern: MatchPattern::Dictionary(DictionaryPattern::default()),
+            })
+            .collect();
+        let result = scoring::most_guessable_match_sequence(&password, &matches, false);
+        assert_eq!(result.guesses, u64::MAX);
+    }
+
     #[test]
     fn test_l33t_variations_for_non_l33t() {
         let p = DictionaryPattern {
             l33t: false,
             ..DictionaryPattern::default()
         };
-        assert_eq!(scoring::l33t_variations(&p, ""), 1);
+        assert_eq!(
+            scoring::l33t_variations(&p, "", &VariationWeights::default()),
+            1
+        );
     }
 
     #[test]
@@ -1141,7 +2222,10 @@ mod tests {
                 l33t: !sub.is_empty(),
                 ..DictionaryPattern::default()
             };
-            assert_eq!(scoring::l33t_variations(&p, word), variants);
+            assert_eq!(
+                scoring::l33t_variations(&p, word, &VariationWeights::default()),
+                variants
+            );
         }
     }
 
@@ -1149,7 +2233,7 @@ mod tests {
     #[test]
     fn serde_score() {
         let score = scoring::Score::One;
-        let value = serde_json::to_value(&score).unwrap();
+        let value = serde_json::to_value(score).unwrap();
         assert!(matches!(value, serde_json::Value::Number(_)));
         let new_score = serde_json::from_value(value).unwrap();
         assert_eq!(scoring::Score::One, new_score);
@@ -11,8 +11,8 @@
 //!
 //! let entropy = zxcvbn("password123", &[]);
 //! assert_eq!(entropy.crack_times().guesses(), 596);
-//! assert_eq!(entropy.crack_times().online_throttling_100_per_hour().to_string(), "5 hours");
-//! assert_eq!(entropy.crack_times().online_no_throttling_10_per_second().to_string(), "59 seconds");
+//! assert_eq!(entropy.crack_times().online_throttling_100_per_hour().to_string(), "6 hours");
+//! assert_eq!(entropy.crack_times().online_no_throttling_10_per_second().to_string(), "60 seconds");
 //! assert_eq!(entropy.crack_times().offline_slow_hashing_1e4_per_second().to_string(), "less than a second");
 //! assert_eq!(entropy.crack_times().offline_fast_hashing_1e10_per_second().to_string(), "less than a second");
 //! #
@@ -21,9 +21,104 @@
 //! ```
 
 use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
 
 use crate::scoring::Score;
 
+/// The attack scenarios for which crack times are estimated.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+pub enum ScenarioName {
+    /// Online attack on a service that rate-limits password attempts.
+    #[cfg_attr(feature = "ser", serde(rename = "online_throttling_100_per_hour"))]
+    OnlineThrottling100PerHour,
+    /// Online attack on a service that doesn't rate-limit,
+    /// or where an attacker has outsmarted rate-limiting.
+    #[cfg_attr(feature = "ser", serde(rename = "online_no_throttling_10_per_second"))]
+    OnlineNoThrottling10PerSecond,
+    /// Offline attack with user-unique salting and a slow hash function.
+    #[cfg_attr(feature = "ser", serde(rename = "offline_slow_hashing_1e4_per_second"))]
+    OfflineSlowHashing1e4PerSecond,
+    /// Offline attack with user-unique salting and a fast hash function.
+    #[cfg_attr(
+        feature = "ser",
+        serde(rename = "offline_fast_hashing_1e10_per_second")
+    )]
+    OfflineFastHashing1e10PerSecond,
+}
+
+impl ScenarioName {
+    /// All scenarios, in the same order as the JS version of zxcvbn.
+    pub const ALL: [ScenarioName; 4] = [
+        ScenarioName::OnlineThrottling100PerHour,
+        ScenarioName::OnlineNoThrottling10PerSecond,
+        ScenarioName::OfflineSlowHashing1e4PerSecond,
+        ScenarioName::OfflineFastHashing1e10PerSecond,
+    ];
+
+    /// The name of the scenario, as used by the JS version of zxcvbn,
+    /// e.g. `"online_throttling_100_per_hour"`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ScenarioName::OnlineThrottling100PerHour => "online_throttling_100_per_hour",
+            ScenarioName::OnlineNoThrottling10PerSecond => "online_no_throttling_10_per_second",
+            ScenarioName::OfflineSlowHashing1e4PerSecond => "offline_slow_hashing_1e4_per_second",
+            ScenarioName::OfflineFastHashing1e10PerSecond => "offline_fast_hashing_1e10_per_second",
+        }
+    }
+
+    /// The name of the scenario in camelCase, e.g. `"onlineThrottling100PerHour"`.
+    fn as_camel_case(self) -> &'static str {
+        match self {
+            ScenarioName::OnlineThrottling100PerHour => "onlineThrottling100PerHour",
+            ScenarioName::OnlineNoThrottling10PerSecond => "onlineNoThrottling10PerSecond",
+            ScenarioName::OfflineSlowHashing1e4PerSecond => "offlineSlowHashing1e4PerSecond",
+            ScenarioName::OfflineFastHashing1e10PerSecond => "offlineFastHashing1e10PerSecond",
+        }
+    }
+}
+
+/// Get the scenario named `name`, either in snake_case as used by the JS version of zxcvbn,
+/// e.g. `"online_throttling_100_per_hour"`, or in camelCase, e.g. `"onlineThrottling100PerHour"`.
+///
+/// # Example
+/// ```rust
+/// use zxcvbn::time_estimates::{scenario_from_str, ScenarioName};
+///
+/// assert_eq!(
+///     scenario_from_str("offlineSlowHashing1e4PerSecond"),
+///     Some(ScenarioName::OfflineSlowHashing1e4PerSecond)
+/// );
+/// assert_eq!(scenario_from_str("offline"), None);
+/// ```
+pub fn scenario_from_str(name: &str) -> Option<ScenarioName> {
+    ScenarioName::ALL
+        .iter()
+        .copied()
+        .find(|scenario| scenario.as_str() == name || scenario.as_camel_case() == name)
+}
+
+impl FromStr for ScenarioName {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        scenario_from_str(s).ok_or("unknown zxcvbn attack scenario name")
+    }
+}
+
+impl AsRef<str> for ScenarioName {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Display for ScenarioName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 /// Back-of-the-envelope crack time estimations, in seconds, based on a few scenarios.
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "ser", derive(serde::Serialize))]
@@ -70,6 +165,380 @@ impl CrackTimes {
     pub fn offline_fast_hashing_1e10_per_second(self) -> CrackTimeSeconds {
         CrackTimeSeconds::Float(self.guesses as f64 / 10_000_000_000.00)
     }
+
+    /// Get the time needed to crack the password in the given scenario.
+    pub fn crack_time(self, scenario: ScenarioName) -> CrackTimeSeconds {
+        match scenario {
+            ScenarioName::OnlineThrottling100PerHour => self.online_throttling_100_per_hour(),
+            ScenarioName::OnlineNoThrottling10PerSecond => {
+                self.online_no_throttling_10_per_second()
+            }
+            ScenarioName::OfflineSlowHashing1e4PerSecond => {
+                self.offline_slow_hashing_1e4_per_second()
+            }
+            ScenarioName::OfflineFastHashing1e10PerSecond => {
+                self.offline_fast_hashing_1e10_per_second()
+            }
+        }
+    }
+
+    /// Get the time needed to crack the password in the given scenario, as a `Duration`
+    /// truncated to whole seconds.
+    ///
+    /// This isn't a `const fn`, as the time of the offline scenarios is computed with floats.
+    pub fn for_scenario(self, scenario: ScenarioName) -> Duration {
+        self.crack_time(scenario).into()
+    }
+
+    /// Get the time needed to crack the password offline when it is hashed with `kdf`, assuming
+    /// the attacker has a single high-end GPU, see [`Kdf::guesses_per_second`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use zxcvbn::time_estimates::Kdf;
+    /// use zxcvbn::zxcvbn;
+    ///
+    /// let crack_times = zxcvbn("Tr0ub4dour&3", &[]).crack_times();
+    /// assert_eq!(crack_times.under_kdf(Kdf::Sha256).to_string(), "less than a second");
+    /// assert_eq!(crack_times.under_kdf(Kdf::Bcrypt { cost: 12 }).to_string(), "4 hours");
+    /// ```
+    pub fn under_kdf(self, kdf: Kdf) -> CrackTimeSeconds {
+        CrackTimeSeconds::Float(self.guesses as f64 / kdf.guesses_per_second())
+    }
+
+    /// The scenario in which the password is cracked the fastest, with its time.
+    ///
+    /// This is always the offline attack on a fast hash function, which makes the most
+    /// guesses per second.
+    pub fn minimum(self) -> (ScenarioName, Duration) {
+        let scenario = ScenarioName::OfflineFastHashing1e10PerSecond;
+        (scenario, self.for_scenario(scenario))
+    }
+
+    /// The scenario in which the password is cracked the slowest, with its time.
+    ///
+    /// This is always the online attack on a service that rate-limits password attempts,
+    /// which makes the fewest guesses per second.
+    pub fn maximum(self) -> (ScenarioName, Duration) {
+        let scenario = ScenarioName::OnlineThrottling100PerHour;
+        (scenario, self.for_scenario(scenario))
+    }
+
+    /// Display the time needed to crack the password in an online attack on a service
+    /// that rate-limits password attempts, e.g. "6 hours".
+    pub fn display_online_throttled(self) -> String {
+        self.online_throttling_100_per_hour().to_string()
+    }
+
+    /// Display the time needed to crack the password in an online attack on a service
+    /// that doesn't rate-limit, e.g. "60 seconds".
+    pub fn display_online_no_throttling(self) -> String {
+        self.online_no_throttling_10_per_second().to_string()
+    }
+
+    /// Display the time needed to crack the password in an offline attack
+    /// on a slow hash function, e.g. "less than a second".
+    pub fn display_offline_slow_hashing(self) -> String {
+        self.offline_slow_hashing_1e4_per_second().to_string()
+    }
+
+    /// Display the time needed to crack the password in an offline attack
+    /// on a fast hash function, e.g. "less than a second".
+    pub fn display_offline_fast_hashing(self) -> String {
+        self.offline_fast_hashing_1e10_per_second().to_string()
+    }
+
+    /// Display the time needed to crack the password in the given scenario,
+    /// in the language of `locale`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use zxcvbn::time_estimates::{Locale, ScenarioName};
+    /// use zxcvbn::zxcvbn;
+    ///
+    /// let crack_times = zxcvbn("password123", &[]).crack_times();
+    /// assert_eq!(
+    ///     crack_times.display_with_locale(ScenarioName::OnlineThrottling100PerHour, Locale::En),
+    ///     "6 hours"
+    /// );
+    /// ```
+    pub fn display_with_locale(self, scenario: ScenarioName, locale: Locale) -> String {
+        self.crack_time(scenario).display(locale)
+    }
+
+    /// Check whether cracking the password takes at least `threshold` in the given scenario.
+    ///
+    /// The comparison uses the unrounded number of seconds, so fractions of a second count.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::time::Duration;
+    /// use zxcvbn::time_estimates::ScenarioName;
+    /// use zxcvbn::zxcvbn;
+    ///
+    /// let crack_times = zxcvbn("password", &[]).crack_times();
+    /// let one_day = Duration::from_secs(24 * 60 * 60);
+    /// assert!(!crack_times.meets_threshold(ScenarioName::OfflineFastHashing1e10PerSecond, one_day));
+    /// ```
+    pub fn meets_threshold(self, scenario: ScenarioName, threshold: Duration) -> bool {
+        self.crack_time(scenario).as_secs_f64() >= threshold.as_secs_f64()
+    }
+
+    /// Iterate over all scenarios, in the order of [`ScenarioName::ALL`], with the number of
+    /// seconds needed to crack the password and its human-readable display, e.g. to render
+    /// a table.
+    ///
+    /// # Example
+    /// ```rust
+    /// use zxcvbn::zxcvbn;
+    ///
+    /// for (scenario, seconds, display) in zxcvbn("password123", &[]).crack_times().iter() {
+    ///     println!("{}: {} seconds ({})", scenario, seconds, display);
+    /// }
+    /// ```
+    pub fn iter(self) -> impl Iterator<Item = (ScenarioName, f64, String)> {
+        ScenarioName::ALL.into_iter().map(move |scenario| {
+            let time = self.crack_time(scenario);
+            (scenario, time.as_secs_f64(), time.to_string())
+        })
+    }
+
+    /// The time needed to crack the password in each scenario, in the order of
+    /// [`ScenarioName::ALL`], like iterating over the crack times but without an iterator.
+    pub fn as_array(self) -> [(ScenarioName, Duration); 4] {
+        ScenarioName::ALL.map(|scenario| (scenario, self.crack_time(scenario).into()))
+    }
+
+    /// The number of seconds needed to crack the password in each scenario, in the order of
+    /// [`ScenarioName::ALL`].
+    pub fn as_seconds_array(self) -> [f64; 4] {
+        ScenarioName::ALL.map(|scenario| self.crack_time(scenario).as_secs_f64())
+    }
+
+    /// Get the crack times from the time of each scenario, as returned by
+    /// [`as_array`](Self::as_array), in any order.
+    ///
+    /// The guesses are recovered from the most precise scenario: the durations of the others
+    /// are whole seconds, so the round trip is exact unless the guesses are so high that
+    /// the time of the throttled online attack saturates.
+    ///
+    /// # Example
+    /// ```rust
+    /// use zxcvbn::time_estimates::CrackTimes;
+    /// use zxcvbn::zxcvbn;
+    ///
+    /// let crack_times = zxcvbn("password123", &[]).crack_times();
+    /// assert_eq!(CrackTimes::from_array(crack_times.as_array()), crack_times);
+    /// ```
+    pub fn from_array(times: [(ScenarioName, Duration); 4]) -> Self {
+        for scenario in ScenarioName::ALL {
+            let time = match times.iter().find(|&&(s, _)| s == scenario) {
+                Some(&(_, time)) => time,
+                None => continue,
+            };
+            if scenario == ScenarioName::OnlineThrottling100PerHour {
+                // 36 seconds per guess, as integers
+                if time.as_secs() < u64::MAX {
+                    return CrackTimes::new(time.as_secs() / 36);
+                }
+                continue;
+            }
+            // float to integer casts saturate
+            return CrackTimes::new((time.as_secs_f64() * guess_rate(scenario)).round() as u64);
+        }
+        CrackTimes::new(u64::MAX)
+    }
+}
+
+/// A hash function or key derivation function which passwords are stored with, to estimate
+/// crack times with [`CrackTimes::under_kdf`].
+///
+/// More functions may be added in minor releases.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Kdf {
+    /// Unsalted MD5.
+    Md5,
+    /// Unsalted SHA-1.
+    Sha1,
+    /// Unsalted SHA-256.
+    Sha256,
+    /// The NTLM hash of Windows.
+    Ntlm,
+    /// bcrypt with a cost factor, e.g. 12.
+    Bcrypt {
+        /// The base-2 logarithm of the number of rounds.
+        cost: u32,
+    },
+    /// PBKDF2 with HMAC-SHA256 and a number of iterations, e.g. 600 000.
+    Pbkdf2Sha256 {
+        /// The number of iterations.
+        iterations: u32,
+    },
+    /// scrypt with a CPU/memory cost of `2^log_n`, e.g. 17, and a block size of 8.
+    Scrypt {
+        /// The base-2 logarithm of the CPU/memory cost parameter `N`.
+        log_n: u32,
+    },
+    /// Argon2id with 19 MiB of memory and 2 iterations, the minimum recommended by OWASP.
+    Argon2id,
+}
+
+impl Kdf {
+    /// The number of passwords a single high-end GPU hashes per second with the function,
+    /// rounded from hashcat benchmarks of an RTX 4090. The rate of the functions with a cost
+    /// parameter is extrapolated from one benchmarked cost, which is usually accurate, as
+    /// their work is proportional to the cost. Attackers with several GPUs are proportionally
+    /// faster.
+    pub fn guesses_per_second(self) -> f64 {
+        match self {
+            Kdf::Md5 => 1.6e11,
+            Kdf::Sha1 => 5e10,
+            Kdf::Sha256 => 2.2e10,
+            Kdf::Ntlm => 2.9e11,
+            // 184 thousand per second at cost 5
+            Kdf::Bcrypt { cost } => 184e3 * 2f64.powi(5 - cost.min(63) as i32),
+            // 8.9 million per second with 1000 iterations
+            Kdf::Pbkdf2Sha256 { iterations } => 8.9e9 / f64::from(iterations.max(1)),
+            // 7 thousand per second with N = 2^14
+            Kdf::Scrypt { log_n } => 7e3 * 2f64.powi(14 - log_n.min(63) as i32),
+            // bound by the bandwidth of reading and writing the memory in each iteration
+            Kdf::Argon2id => 1.3e4,
+        }
+    }
+}
+
+/// The number of guesses per second of each scenario.
+fn guess_rate(scenario: ScenarioName) -> f64 {
+    match scenario {
+        ScenarioName::OnlineThrottling100PerHour => 100. / HOUR,
+        ScenarioName::OnlineNoThrottling10PerSecond => 10.,
+        ScenarioName::OfflineSlowHashing1e4PerSecond => 1e4,
+        ScenarioName::OfflineFastHashing1e10PerSecond => 1e10,
+    }
+}
+
+/// Deserializes either the format serialized by this crate, `{"guesses": 596}`, or the crack
+/// times of the JavaScript zxcvbn: its `crack_times_seconds` or `crack_times_display` objects,
+/// or a whole result containing them.
+///
+/// The guesses are recovered from the numeric seconds if there are any, and approximated
+/// from the rounded display strings, e.g. `"3 hours"`, otherwise.
+///
+/// # Example
+/// ```rust
+/// # #[cfg(feature = "ser")] {
+/// use zxcvbn::time_estimates::CrackTimes;
+///
+/// let json = r#"{"online_no_throttling_10_per_second": 59.6, "offline_fast_hashing_1e10_per_second": 5.96e-8}"#;
+/// let crack_times: CrackTimes = serde_json::from_str(json).unwrap();
+/// assert_eq!(crack_times.guesses(), 596);
+/// # }
+/// ```
+#[cfg(feature = "ser")]
+impl<'de> serde::Deserialize<'de> for CrackTimes {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use std::collections::HashMap;
+
+        #[derive(serde::Deserialize)]
+        #[serde(untagged)]
+        enum Seconds {
+            Number(f64),
+            Display(String),
+        }
+
+        #[derive(serde::Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Guesses {
+                guesses: u64,
+            },
+            Scenarios(HashMap<String, Seconds>),
+            Result {
+                crack_times_seconds: Option<HashMap<String, Seconds>>,
+                crack_times_display: Option<HashMap<String, Seconds>>,
+            },
+        }
+
+        fn guesses_of(times: &[HashMap<String, Seconds>]) -> Option<u64> {
+            let mut numbers = Vec::new();
+            let mut displays = Vec::new();
+            for (name, seconds) in times.iter().flatten() {
+                let scenario = match scenario_from_str(name) {
+                    Some(scenario) => scenario,
+                    None => continue,
+                };
+                match seconds {
+                    Seconds::Number(seconds) => numbers.push((scenario, *seconds)),
+                    Seconds::Display(display) => {
+                        if let Some(seconds) = parse_display_time(display) {
+                            // the bounds carry the least information
+                            let bounded = display == "less than a second" || display == "centuries";
+                            displays.push((bounded, scenario, seconds));
+                        }
+                    }
+                }
+            }
+            // prefer exact seconds, and pick the scenarios in a deterministic order
+            let order =
+                |scenario: ScenarioName| ScenarioName::ALL.iter().position(|&s| s == scenario);
+            numbers.sort_by_key(|&(scenario, _)| order(scenario));
+            displays.sort_by_key(|&(bounded, scenario, _)| (bounded, order(scenario)));
+            let (scenario, seconds) = numbers.first().copied().or_else(|| {
+                displays
+                    .first()
+                    .map(|&(_, scenario, seconds)| (scenario, seconds))
+            })?;
+            // float to integer casts saturate
+            Some((seconds * guess_rate(scenario)).round() as u64)
+        }
+
+        let guesses = match Repr::deserialize(deserializer)? {
+            Repr::Guesses { guesses } => Some(guesses),
+            Repr::Scenarios(times) => guesses_of(&[times]),
+            Repr::Result {
+                crack_times_seconds,
+                crack_times_display,
+            } => guesses_of(
+                &crack_times_seconds
+                    .into_iter()
+                    .chain(crack_times_display)
+                    .collect::<Vec<_>>(),
+            ),
+        };
+        guesses
+            .map(CrackTimes::new)
+            .ok_or_else(|| serde::de::Error::custom("no crack time of a known attack scenario"))
+    }
+}
+
+/// Iterates over the crack times of all scenarios, in the order of [`ScenarioName::ALL`].
+///
+/// # Example
+/// ```rust
+/// use zxcvbn::zxcvbn;
+///
+/// let entropy = zxcvbn("password123", &[]);
+/// for (scenario, time) in entropy.crack_times() {
+///     println!("{:?}: {} seconds", scenario, time.as_secs());
+/// }
+/// ```
+impl IntoIterator for CrackTimes {
+    type Item = (ScenarioName, Duration);
+    type IntoIter = std::array::IntoIter<(ScenarioName, Duration), 4>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_array().into_iter()
+    }
+}
+
+impl IntoIterator for &CrackTimes {
+    type Item = (ScenarioName, Duration);
+    type IntoIter = std::array::IntoIter<(ScenarioName, Duration), 4>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        (*self).into_iter()
+    }
 }
 
 /// Represents the time to crack a password.
@@ -83,59 +552,147 @@ pub enum CrackTimeSeconds {
     Float(f64),
 }
 
+impl CrackTimeSeconds {
+    /// The number of seconds needed to crack a password, without rounding.
+    pub fn as_secs_f64(self) -> f64 {
+        match self {
+            CrackTimeSeconds::Integer(i) => i as f64,
+            CrackTimeSeconds::Float(f) => f,
+        }
+    }
+}
+
+const MINUTE: f64 = 60.;
+const HOUR: f64 = MINUTE * 60.;
+const DAY: f64 = HOUR * 24.;
+const MONTH: f64 = DAY * 31.;
+const YEAR: f64 = MONTH * 12.;
+const CENTURY: f64 = YEAR * 100.;
+
+/// The language in which crack times are displayed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum Locale {
+    /// English, as displayed by the JS version of zxcvbn.
+    #[default]
+    En,
+}
+
+impl CrackTimeSeconds {
+    /// Displays the time rounded to the nearest unit in the language of `locale`,
+    /// e.g. "3 days" in English.
+    pub fn display(self, locale: Locale) -> String {
+        match locale {
+            Locale::En => display_time_en(self.as_secs_f64()),
+        }
+    }
+}
+
+/// Displays the time rounded to the nearest unit, e.g. "3 days",
+/// like `display_time` in the JS version of zxcvbn.
 impl fmt::Display for CrackTimeSeconds {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let seconds = match self {
-            CrackTimeSeconds::Integer(i) => *i,
-            CrackTimeSeconds::Float(f) => *f as u64,
-        };
-        const MINUTE: u64 = 60;
-        const HOUR: u64 = MINUTE * 60;
-        const DAY: u64 = HOUR * 24;
-        const MONTH: u64 = DAY * 31;
-        const YEAR: u64 = MONTH * 12;
-        const CENTURY: u64 = YEAR * 100;
-        if seconds < 1 {
-            write!(f, "less than a second")
-        } else if seconds < MINUTE {
-            let base = seconds;
-            write!(f, "{} second{}", base, if base > 1 { "s" } else { "" })
-        } else if seconds < HOUR {
-            let base = seconds / MINUTE;
-            write!(f, "{} minute{}", base, if base > 1 { "s" } else { "" })
-        } else if seconds < DAY {
-            let base = seconds / HOUR;
-            write!(f, "{} hour{}", base, if base > 1 { "s" } else { "" })
-        } else if seconds < MONTH {
-            let base = seconds / DAY;
-            write!(f, "{} day{}", base, if base > 1 { "s" } else { "" })
-        } else if seconds < YEAR {
-            let base = seconds / MONTH;
-            write!(f, "{} month{}", base, if base > 1 { "s" } else { "" })
-        } else if seconds < CENTURY {
-            let base = seconds / YEAR;
-            write!(f, "{} year{}", base, if base > 1 { "s" } else { "" })
-        } else {
-            write!(f, "centuries")
-        }
-    }
-}
-
-impl From<CrackTimeSeconds> for std::time::Duration {
-    fn from(s: CrackTimeSeconds) -> std::time::Duration {
+        f.write_str(&self.display(Locale::En))
+    }
+}
+
+fn display_time_en(seconds: f64) -> String {
+    let (base, unit) = if seconds < 1. {
+        return "less than a second".to_string();
+    } else if seconds < MINUTE {
+        (seconds, "second")
+    } else if seconds < HOUR {
+        (seconds / MINUTE, "minute")
+    } else if seconds < DAY {
+        (seconds / HOUR, "hour")
+    } else if seconds < MONTH {
+        (seconds / DAY, "day")
+    } else if seconds < YEAR {
+        (seconds / MONTH, "month")
+    } else if seconds < CENTURY {
+        (seconds / YEAR, "year")
+    } else {
+        return "centuries".to_string();
+    };
+    let base = base.round() as u64;
+    format!("{} {}{}", base, unit, if base != 1 { "s" } else { "" })
+}
+
+/// Parses a time displayed by zxcvbn, e.g. "3 days", to the number of seconds it stands for.
+///
+/// "less than a second" and "centuries" are parsed to their lower bound, 0 and 100 years.
+#[cfg(feature = "ser")]
+fn parse_display_time(display: &str) -> Option<f64> {
+    match display {
+        "less than a second" => return Some(0.),
+        "centuries" => return Some(CENTURY),
+        _ => {}
+    }
+    let (base, unit) = display.split_once(' ')?;
+    let base: f64 = base.parse().ok()?;
+    let unit = match unit.strip_suffix('s').unwrap_or(unit) {
+        "second" => 1.,
+        "minute" => MINUTE,
+        "hour" => HOUR,
+        "day" => DAY,
+        "month" => MONTH,
+        "year" => YEAR,
+        _ => return None,
+    };
+    Some(base * unit)
+}
+
+impl From<CrackTimeSeconds> for Duration {
+    fn from(s: CrackTimeSeconds) -> Duration {
         match s {
             // TODO: Use `from_secs_f64` when it is stable
-            CrackTimeSeconds::Float(f) => std::time::Duration::from_secs(f as u64),
-            CrackTimeSeconds::Integer(i) => std::time::Duration::from_secs(i),
+            CrackTimeSeconds::Float(f) => Duration::from_secs(f as u64),
+            CrackTimeSeconds::Integer(i) => Duration::from_secs(i),
         }
     }
 }
 
-pub(crate) fn estimate_attack_times(guesses: u64) -> (CrackTimes, Score) {
-    (CrackTimes::new(guesses), calculate_score(guesses))
+/// Get the crack time estimations and overall strength score for a number of guesses.
+///
+/// This allows rebuilding the user-facing parts of an `Entropy` from a stored
+/// `guesses` value without re-running the analysis.
+///
+/// # Example
+/// ```rust
+/// use zxcvbn::time_estimates::estimate_attack_times;
+/// use zxcvbn::{zxcvbn, Score};
+///
+/// let entropy = zxcvbn("password123", &[]);
+/// let (crack_times, score) = estimate_attack_times(entropy.guesses());
+/// assert_eq!(crack_times, entropy.crack_times());
+/// assert_eq!(score, Score::Zero);
+/// ```
+pub fn estimate_attack_times(guesses: u64) -> (CrackTimes, Score) {
+    (CrackTimes::new(guesses), guesses_to_score(guesses))
 }
 
-fn calculate_score(guesses: u64) -> Score {
+/// Get the overall strength score for a number of guesses, with the same thresholds as
+/// [`estimate_attack_times`], e.g. for guesses estimated by other means.
+///
+/// The score is:
+/// - 0 below 1 000 guesses,
+/// - 1 below 1 000 000 guesses,
+/// - 2 below 100 000 000 guesses,
+/// - 3 below 10 000 000 000 guesses,
+/// - 4 from 10 000 000 000 guesses.
+///
+/// Each threshold is offset by 5 guesses, like in the JS zxcvbn, so e.g. 1 004 guesses
+/// still score 0.
+///
+/// # Example
+/// ```rust
+/// use zxcvbn::time_estimates::guesses_to_score;
+/// use zxcvbn::Score;
+///
+/// assert_eq!(guesses_to_score(596), Score::Zero);
+/// assert_eq!(guesses_to_score(1_000_000_000), Score::Three);
+/// ```
+pub fn guesses_to_score(guesses: u64) -> Score {
     const DELTA: u64 = 5;
     if guesses < 1_000 + DELTA {
         Score::Zero
@@ -149,3 +706,346 @@ fn calculate_score(guesses: u64) -> Score {
         Score::Four
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_guesses_to_score() {
+        for (guesses, score) in [
+            (0, Score::Zero),
+            (1_004, Score::Zero),
+            (1_005, Score::One),
+            (1_000_004, Score::One),
+            (1_000_005, Score::Two),
+            (100_000_004, Score::Two),
+            (100_000_005, Score::Three),
+            (10_000_000_004, Score::Three),
+            (10_000_000_005, Score::Four),
+            (u64::MAX, Score::Four),
+        ] {
+            assert_eq!(guesses_to_score(guesses), score, "{}", guesses);
+            assert_eq!(estimate_attack_times(guesses).1, score, "{}", guesses);
+        }
+    }
+
+    #[test]
+    fn test_scenario_name_round_trip() {
+        for scenario in ScenarioName::ALL {
+            assert_eq!(scenario.to_string().parse(), Ok(scenario));
+            assert_eq!(scenario_from_str(scenario.as_ref()), Some(scenario));
+            assert_eq!(scenario_from_str(scenario.as_camel_case()), Some(scenario));
+        }
+        assert_eq!(
+            "online_no_throttling_10_per_second".parse(),
+            Ok(ScenarioName::OnlineNoThrottling10PerSecond)
+        );
+        assert_eq!(
+            scenario_from_str("offlineFastHashing1e10PerSecond"),
+            Some(ScenarioName::OfflineFastHashing1e10PerSecond)
+        );
+        for invalid in [
+            "",
+            "online",
+            "OnlineThrottling100PerHour",
+            "ONLINE_THROTTLING_100_PER_HOUR",
+            " online_throttling_100_per_hour",
+        ] {
+            assert_eq!(scenario_from_str(invalid), None);
+            assert!(invalid.parse::<ScenarioName>().is_err());
+        }
+    }
+
+    #[test]
+    fn test_crack_times_into_iter() {
+        let crack_times = CrackTimes::new(100_000);
+        let times: Vec<(ScenarioName, Duration)> = crack_times.into_iter().collect();
+        assert_eq!(
+            times,
+            vec![
+                (
+                    ScenarioName::OnlineThrottling100PerHour,
+                    Duration::from_secs(3_600_000)
+                ),
+                (
+                    ScenarioName::OnlineNoThrottling10PerSecond,
+                    Duration::from_secs(10_000)
+                ),
+                (
+                    ScenarioName::OfflineSlowHashing1e4PerSecond,
+                    Duration::from_secs(10)
+                ),
+                (
+                    ScenarioName::OfflineFastHashing1e10PerSecond,
+                    Duration::from_secs(0)
+                ),
+            ]
+        );
+        assert_eq!((&crack_times).into_iter().collect::<Vec<_>>(), times);
+    }
+
+    #[test]
+    fn test_crack_time_display() {
+        let test_data = [
+            (0., "less than a second"),
+            (1., "1 second"),
+            (1.5, "2 seconds"),
+            (59.6, "60 seconds"),
+            (90., "2 minutes"),
+            (3_600., "1 hour"),
+            (5.4 * 3_600., "5 hours"),
+            (3. * 86_400., "3 days"),
+            (45. * 86_400., "1 month"),
+            (2.5 * 372. * 86_400., "3 years"),
+            (100. * 372. * 86_400., "centuries"),
+        ];
+        for &(seconds, expected) in &test_data {
+            assert_eq!(CrackTimeSeconds::Float(seconds).to_string(), expected);
+        }
+        assert_eq!(CrackTimeSeconds::Integer(7_200).to_string(), "2 hours");
+    }
+
+    #[test]
+    fn test_crack_time_display_boundaries() {
+        let test_data = [
+            (0.999, "less than a second"),
+            (1., "1 second"),
+            (MINUTE - 0.6, "59 seconds"),
+            (MINUTE, "1 minute"),
+            (HOUR - 1., "60 minutes"),
+            (HOUR, "1 hour"),
+            (DAY - 1., "24 hours"),
+            (DAY, "1 day"),
+            (MONTH - 1., "31 days"),
+            (MONTH, "1 month"),
+            (YEAR - 1., "12 months"),
+            (YEAR, "1 year"),
+            (CENTURY - 1., "100 years"),
+            (CENTURY, "centuries"),
+        ];
+        for &(seconds, expected) in &test_data {
+            assert_eq!(
+                CrackTimeSeconds::Float(seconds).display(Locale::En),
+                expected,
+                "{} seconds",
+                seconds
+            );
+        }
+    }
+
+    #[test]
+    fn test_crack_times_display_per_scenario() {
+        let crack_times = CrackTimes::new(596);
+        assert_eq!(crack_times.display_online_throttled(), "6 hours");
+        assert_eq!(crack_times.display_online_no_throttling(), "60 seconds");
+        assert_eq!(
+            crack_times.display_offline_slow_hashing(),
+            "less than a second"
+        );
+        assert_eq!(
+            crack_times.display_offline_fast_hashing(),
+            "less than a second"
+        );
+        for scenario in ScenarioName::ALL {
+            assert_eq!(
+                crack_times.display_with_locale(scenario, Locale::default()),
+                crack_times.crack_time(scenario).to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn test_crack_time_for_scenario() {
+        let crack_times = CrackTimes::new(596);
+        let seconds = crack_times
+            .crack_time(ScenarioName::OnlineNoThrottling10PerSecond)
+            .as_secs_f64();
+        assert!((seconds - 59.6).abs() < 1e-9);
+        assert_eq!(
+            crack_times
+                .crack_time(ScenarioName::OnlineThrottling100PerHour)
+                .to_string(),
+            "6 hours"
+        );
+    }
+
+    #[cfg(feature = "ser")]
+    #[test]
+    fn test_deserialize_crack_times() {
+        // the output of the JavaScript zxcvbn for "password"
+        let js_result = serde_json::json!({
+            "password": "password",
+            "guesses": 3,
+            "guesses_log10": 0.47712125471966244,
+            "crack_times_seconds": {
+                "online_throttling_100_per_hour": 108,
+                "online_no_throttling_10_per_second": 0.3,
+                "offline_slow_hashing_1e4_per_second": 0.0003,
+                "offline_fast_hashing_1e10_per_second": 3e-10
+            },
+            "crack_times_display": {
+                "online_throttling_100_per_hour": "2 minutes",
+                "online_no_throttling_10_per_second": "less than a second",
+                "offline_slow_hashing_1e4_per_second": "less than a second",
+                "offline_fast_hashing_1e10_per_second": "less than a second"
+            },
+            "score": 0
+        });
+        let from_result: CrackTimes = serde_json::from_value(js_result.clone()).unwrap();
+        assert_eq!(from_result.guesses(), 3);
+        let from_seconds: CrackTimes =
+            serde_json::from_value(js_result["crack_times_seconds"].clone()).unwrap();
+        assert_eq!(from_seconds.guesses(), 3);
+        let from_display: CrackTimes =
+            serde_json::from_value(js_result["crack_times_display"].clone()).unwrap();
+        assert_eq!(from_display.guesses(), 3);
+
+        let display = serde_json::json!({
+            "online_throttling_100_per_hour": "4 months",
+            "online_no_throttling_10_per_second": "8 hours",
+            "offline_slow_hashing_1e4_per_second": "30 seconds",
+            "offline_fast_hashing_1e10_per_second": "less than a second"
+        });
+        let approximated: CrackTimes = serde_json::from_value(display).unwrap();
+        assert!((approximated.guesses() as f64 / 300_000. - 1.).abs() < 0.1);
+
+        let crack_times = CrackTimes::new(596);
+        let json = serde_json::to_string(&crack_times).unwrap();
+        assert_eq!(
+            serde_json::from_str::<CrackTimes>(&json).unwrap(),
+            crack_times
+        );
+        assert!(serde_json::from_str::<CrackTimes>(r#"{"unknown": 1}"#).is_err());
+    }
+
+    #[test]
+    fn test_meets_threshold() {
+        let crack_times = CrackTimes::new(596);
+        let scenario = ScenarioName::OnlineNoThrottling10PerSecond;
+        assert!(crack_times.meets_threshold(scenario, Duration::from_secs(59)));
+        assert!(crack_times.meets_threshold(scenario, Duration::from_millis(59_600)));
+        assert!(!crack_times.meets_threshold(scenario, Duration::from_secs(60)));
+        assert!(crack_times.meets_threshold(
+            ScenarioName::OnlineThrottling100PerHour,
+            Duration::from_secs(21_456)
+        ));
+        assert!(!crack_times.meets_threshold(
+            ScenarioName::OfflineFastHashing1e10PerSecond,
+            Duration::from_micros(1)
+        ));
+    }
+
+    #[test]
+    fn test_crack_times_array_round_trip() {
+        let crack_times = CrackTimes::new(100_000);
+        assert_eq!(
+            crack_times.as_array().to_vec(),
+            crack_times.into_iter().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            crack_times.as_seconds_array(),
+            [3_600_000., 10_000., 10., 1e-5]
+        );
+
+        for guesses in [0, 1, 10, 596, 100_000, 1 << 40, u64::MAX / 36, u64::MAX] {
+            let crack_times = CrackTimes::new(guesses);
+            assert_eq!(
+                CrackTimes::from_array(crack_times.as_array()),
+                crack_times,
+                "{}",
+                guesses
+            );
+            let mut reversed = crack_times.as_array();
+            reversed.reverse();
+            assert_eq!(CrackTimes::from_array(reversed), crack_times, "{}", guesses);
+        }
+        // beyond the precision of the throttled online attack, the other scenarios are used
+        let guesses = u64::MAX / 2;
+        let round_tripped = CrackTimes::from_array(CrackTimes::new(guesses).as_array()).guesses();
+        assert!((round_tripped as f64 / guesses as f64 - 1.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_crack_times_under_kdf() {
+        let crack_times = CrackTimes::new(1_000_000_000);
+        let seconds = |kdf: Kdf| crack_times.under_kdf(kdf).as_secs_f64();
+        assert!(seconds(Kdf::Ntlm) < seconds(Kdf::Md5));
+        assert!(seconds(Kdf::Md5) < seconds(Kdf::Sha1));
+        assert!(seconds(Kdf::Sha1) < seconds(Kdf::Sha256));
+        assert!(seconds(Kdf::Sha256) < seconds(Kdf::Argon2id));
+        // the time is proportional to the cost
+        assert_eq!(seconds(Kdf::Bcrypt { cost: 5 }), 1e9 / 184e3);
+        assert_eq!(
+            seconds(Kdf::Bcrypt { cost: 12 }),
+            4. * seconds(Kdf::Bcrypt { cost: 10 })
+        );
+        assert_eq!(
+            seconds(Kdf::Pbkdf2Sha256 {
+                iterations: 600_000
+            }),
+            600. * seconds(Kdf::Pbkdf2Sha256 { iterations: 1000 })
+        );
+        assert_eq!(
+            seconds(Kdf::Scrypt { log_n: 17 }),
+            8. * seconds(Kdf::Scrypt { log_n: 14 })
+        );
+        // degenerate costs don't divide by zero
+        assert!(seconds(Kdf::Pbkdf2Sha256 { iterations: 0 }).is_finite());
+        assert!(seconds(Kdf::Bcrypt { cost: u32::MAX }).is_finite());
+        assert_eq!(
+            CrackTimes::new(0).under_kdf(Kdf::Argon2id).as_secs_f64(),
+            0.
+        );
+    }
+
+    #[test]
+    fn test_crack_times_for_scenario() {
+        let crack_times = CrackTimes::new(100_000);
+        assert_eq!(
+            crack_times.for_scenario(ScenarioName::OnlineThrottling100PerHour),
+            Duration::from_secs(3_600_000)
+        );
+        assert_eq!(
+            crack_times.for_scenario(ScenarioName::OfflineSlowHashing1e4PerSecond),
+            Duration::from_secs(10)
+        );
+        assert_eq!(
+            crack_times.minimum(),
+            (
+                ScenarioName::OfflineFastHashing1e10PerSecond,
+                Duration::ZERO
+            )
+        );
+        assert_eq!(
+            crack_times.maximum(),
+            (
+                ScenarioName::OnlineThrottling100PerHour,
+                Duration::from_secs(3_600_000)
+            )
+        );
+        for (scenario, time) in crack_times.as_array() {
+            assert!(crack_times.minimum().1 <= time, "{}", scenario);
+            assert!(crack_times.maximum().1 >= time, "{}", scenario);
+        }
+    }
+
+    #[test]
+    fn test_crack_times_iter() {
+        let crack_times = CrackTimes::new(596);
+        let rows: Vec<(ScenarioName, f64, String)> = crack_times.iter().collect();
+        assert_eq!(
+            rows.iter().map(|row| row.0).collect::<Vec<_>>(),
+            ScenarioName::ALL
+        );
+        assert_eq!(
+            rows[1],
+            (
+                ScenarioName::OnlineNoThrottling10PerSecond,
+                59.6,
+                "60 seconds".to_string()
+            )
+        );
+        assert_eq!(rows[0].2, "6 hours");
+    }
+}
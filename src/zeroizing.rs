@@ -0,0 +1,127 @@
+//! Wiping of the intermediate copies of passwords, enabled with the `zeroize` feature.
+
+use std::ops::Deref;
+use std::ptr;
+use std::sync::atomic::{compiler_fence, Ordering};
+
+use crate::matching::patterns::MatchPattern;
+use crate::matching::{mask_token, Match};
+
+/// A `String` whose contents are overwritten with zeroes when it is dropped.
+///
+/// The string must not grow, as reallocating would leave a copy of its contents behind.
+pub(crate) struct ZeroizingString(pub(crate) String);
+
+impl Deref for ZeroizingString {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Drop for ZeroizingString {
+    fn drop(&mut self) {
+        let mut bytes = std::mem::take(&mut self.0).into_bytes();
+        // also wipe the spare capacity, which may hold bytes of a previous, longer content
+        let capacity = bytes.capacity();
+        bytes.resize(capacity, 0);
+        wipe(&mut bytes);
+    }
+}
+
+/// Replace the parts of the password kept by `m` with their masked version,
+/// see [`Match::display_token`], and wipe them.
+pub(crate) fn mask_match(m: &mut Match) {
+    let token = m.display_token();
+    replace(&mut m.token, token);
+    match m.pattern {
+        MatchPattern::Dictionary(ref mut p) => mask(&mut p.matched_word),
+        MatchPattern::Repeat(ref mut p) => {
+            mask(&mut p.base_token);
+            p.base_matches.iter_mut().for_each(mask_match);
+        }
+        MatchPattern::Regex(ref mut p) => p.regex_match.iter_mut().for_each(mask),
+        _ => {}
+    }
+}
+
+fn mask(text: &mut String) {
+    let masked = mask_token(text);
+    replace(text, masked);
+}
+
+fn replace(text: &mut String, masked: String) {
+    drop(ZeroizingString(std::mem::replace(text, masked)));
+}
+
+fn wipe(bytes: &mut [u8]) {
+    for byte in bytes.iter_mut() {
+        // volatile writes are not optimized away, even though the buffer is freed right after
+        unsafe { ptr::write_volatile(byte, 0) };
+    }
+    compiler_fence(Ordering::SeqCst);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matching::patterns::{DictionaryPattern, RepeatPattern};
+
+    #[test]
+    fn test_wipe() {
+        let mut bytes = b"correcthorsebatterystaple".to_vec();
+        wipe(&mut bytes);
+        assert!(bytes.iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_mask_match() {
+        let base = Match {
+            token: "password".to_string(),
+            pattern: MatchPattern::Dictionary(DictionaryPattern {
+                matched_word: "password".to_string(),
+                ..DictionaryPattern::default()
+            }),
+            ..Match::default()
+        };
+        let mut m = Match {
+            token: "passwordpassword".to_string(),
+            pattern: MatchPattern::Repeat(RepeatPattern {
+                base_token: "password".to_string(),
+                base_matches: vec![base],
+                ..RepeatPattern::default()
+            }),
+            ..Match::default()
+        };
+        mask_match(&mut m);
+        assert_eq!(m.token, "p**************d");
+        let p = match m.pattern {
+            MatchPattern::Repeat(ref p) => p,
+            _ => panic!("Wrong match pattern"),
+        };
+        assert_eq!(p.base_token, "p******d");
+        assert_eq!(p.base_matches[0].token, "p******d");
+        match p.base_matches[0].pattern {
+            MatchPattern::Dictionary(ref p) => assert_eq!(p.matched_word, "p******d"),
+            _ => panic!("Wrong match pattern"),
+        }
+    }
+
+    #[test]
+    fn test_sequence_is_masked() {
+        let entropy = crate::zxcvbn("p4ssw0rd", &[]);
+        assert_eq!(entropy.sequence()[0].token, "p******d");
+        match entropy.sequence()[0].pattern {
+            MatchPattern::Dictionary(ref p) => assert_eq!(p.matched_word, "p******d"),
+            _ => panic!("Wrong match pattern"),
+        }
+        assert!(entropy.feedback().is_some());
+    }
+
+    #[test]
+    fn test_zeroizing_string_deref() {
+        let password = ZeroizingString("p@ssw0rd".to_string());
+        assert_eq!(&*password, "p@ssw0rd");
+    }
+}
@@ -0,0 +1,150 @@
+#![cfg(feature = "ffi")]
+
+use std::ffi::CString;
+use std::os::raw::c_char;
+
+use zxcvbn::ffi::{zxcvbn_ffi, zxcvbn_free};
+use zxcvbn::zxcvbn;
+
+#[test]
+fn test_zxcvbn_ffi() {
+    let password = CString::new("jsmith8634!xQ").unwrap();
+    let input = CString::new("jsmith").unwrap();
+    let inputs = [input.as_ptr()];
+    let expected = zxcvbn("jsmith8634!xQ", &["jsmith"]);
+    unsafe {
+        let result = zxcvbn_ffi(password.as_ptr(), inputs.as_ptr(), inputs.len());
+        assert!(!result.is_null());
+        assert_eq!((*result).score, u8::from(expected.score()));
+        assert_eq!((*result).guesses, expected.guesses());
+        assert_eq!(
+            (*result).crack_time_online_seconds,
+            expected.guesses() as f64 / 10.
+        );
+        zxcvbn_free(result);
+    }
+}
+
+#[test]
+fn test_zxcvbn_ffi_invalid_arguments() {
+    let password = CString::new("password").unwrap();
+    let invalid_utf8 = [0xffu8, 0];
+    unsafe {
+        assert!(zxcvbn_ffi(std::ptr::null(), std::ptr::null(), 0).is_null());
+        assert!(zxcvbn_ffi(password.as_ptr(), std::ptr::null(), 1).is_null());
+        assert!(zxcvbn_ffi(invalid_utf8.as_ptr() as *const c_char, std::ptr::null(), 0).is_null());
+        let inputs = [std::ptr::null()];
+        assert!(zxcvbn_ffi(password.as_ptr(), inputs.as_ptr(), 1).is_null());
+
+        let result = zxcvbn_ffi(password.as_ptr(), std::ptr::null(), 0);
+        assert_eq!((*result).score, 0);
+        zxcvbn_free(result);
+        zxcvbn_free(std::ptr::null_mut());
+    }
+}
+
+/// The C type of a Rust type of the `extern "C"` interface.
+fn c_type(rust_type: &str) -> String {
+    match rust_type {
+        "u8" => "uint8_t".to_string(),
+        "u64" => "uint64_t".to_string(),
+        "f64" => "double".to_string(),
+        "usize" => "size_t".to_string(),
+        "c_char" => "char".to_string(),
+        "ZxcvbnResult" => "ZxcvbnResult".to_string(),
+        _ => {
+            if let Some(pointee) = rust_type.strip_prefix("*const ") {
+                let pointee = c_type(pointee);
+                // the const of a pointer to a pointer qualifies the inner pointer
+                match pointee.strip_suffix(" *") {
+                    Some(inner) => format!("{} *const *", inner),
+                    None => format!("const {} *", pointee),
+                }
+            } else if let Some(pointee) = rust_type.strip_prefix("*mut ") {
+                format!("{} *", c_type(pointee))
+            } else {
+                panic!("no C type for {}", rust_type)
+            }
+        }
+    }
+}
+
+/// A C declaration of `name` with the C type of `rust_type`, e.g. `const char *password`.
+fn c_declaration(rust_type: &str, name: &str) -> String {
+    let c_type = c_type(rust_type);
+    if c_type.ends_with('*') {
+        format!("{}{}", c_type, name)
+    } else {
+        format!("{} {}", c_type, name)
+    }
+}
+
+/// The source without its comments, with its whitespace collapsed to single spaces.
+fn normalize(source: &str) -> String {
+    let mut code = String::new();
+    let mut rest = source;
+    while let Some(start) = rest.find("/*") {
+        code.push_str(&rest[..start]);
+        rest = &rest[start + rest[start..].find("*/").unwrap() + 2..];
+    }
+    code.push_str(rest);
+    code.lines()
+        .map(|line| line.split("//").next().unwrap())
+        .collect::<Vec<_>>()
+        .join(" ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[test]
+fn test_header_matches_ffi_module() {
+    let header = normalize(include_str!("../zxcvbn.h"));
+    let module = normalize(include_str!("../src/ffi.rs"));
+
+    // the functions, from `extern "C" fn name(params) -> ret {`
+    let mut functions = Vec::new();
+    for item in module.split("extern \"C\" fn ").skip(1) {
+        let signature = &item[..item.find(" {").unwrap()];
+        let (open, close) = (signature.find('(').unwrap(), signature.find(')').unwrap());
+        let name = &signature[..open];
+        let params: Vec<String> = signature[open + 1..close]
+            .split(',')
+            .map(str::trim)
+            .filter(|param| !param.is_empty())
+            .map(|param| {
+                let (param_name, rust_type) = param.split_at(param.find(':').unwrap());
+                c_declaration(rust_type[1..].trim(), param_name)
+            })
+            .collect();
+        let declaration = match signature[close + 1..].trim().strip_prefix("-> ") {
+            Some(ret) => c_declaration(ret, name),
+            None => format!("void {}", name),
+        };
+        functions.push(format!("{}({});", declaration, params.join(", ")));
+    }
+    assert!(!functions.is_empty());
+    for function in &functions {
+        assert!(header.contains(function.as_str()), "{}", function);
+    }
+    // the header declares no other functions
+    assert_eq!(header.matches(");").count(), functions.len(), "{}", header);
+
+    // the fields of the result, in order
+    let body = module.split("pub struct ZxcvbnResult {").nth(1).unwrap();
+    let fields: Vec<String> = body[..body.find('}').unwrap()]
+        .split(',')
+        .map(str::trim)
+        .filter(|field| !field.is_empty())
+        .map(|field| {
+            let field = field.trim_start_matches("pub ");
+            let (name, rust_type) = field.split_at(field.find(':').unwrap());
+            format!("{};", c_declaration(rust_type[1..].trim(), name))
+        })
+        .collect();
+    let expected = format!(
+        "typedef struct ZxcvbnResult {{ {} }} ZxcvbnResult;",
+        fields.join(" ")
+    );
+    assert!(header.contains(&expected), "{}", expected);
+}
@@ -0,0 +1,156 @@
+//! Procedural macros for embedding custom frequency lists for `zxcvbn`.
+//!
+//! # Example
+//! ```rust,ignore
+//! use zxcvbn::{zxcvbn_with_options, ZxcvbnOptions};
+//! use zxcvbn_macros::include_wordlist;
+//!
+//! // Defines `static MY_APP: &[&str]` from the words of `words.txt`, most common first.
+//! include_wordlist!(path = "words.txt", name = "my_app");
+//!
+//! let options = ZxcvbnOptions::new().with_extra_dictionary("my_app", MY_APP);
+//! let entropy = zxcvbn_with_options("correcthorse", &[], &options);
+//! ```
+
+#![warn(missing_docs)]
+#![forbid(unsafe_code)]
+
+use std::path::Path;
+use std::str::FromStr;
+
+use proc_macro::{Delimiter, Literal, TokenStream, TokenTree};
+
+/// Embeds a frequency list from a newline-separated word file at compile time.
+///
+/// `include_wordlist!(path = "words.txt", name = "my_app")` reads `words.txt`, relative to
+/// the `CARGO_MANIFEST_DIR` of the crate using the macro, and defines a
+/// `pub static MY_APP: &[&str]` containing its words. The words are lowercased
+/// and keep the order of the file, so the first line is ranked as the most common word.
+/// Blank lines are ignored.
+#[proc_macro]
+pub fn include_wordlist(input: TokenStream) -> TokenStream {
+    match expand_include_wordlist(input) {
+        Ok(output) => output,
+        Err(message) => compile_error(&message),
+    }
+}
+
+fn expand_include_wordlist(input: TokenStream) -> Result<TokenStream, String> {
+    let mut path = None;
+    let mut name = None;
+    for (key, value) in parse_arguments(input)? {
+        match key.as_str() {
+            "path" => path = Some(value),
+            "name" => name = Some(value),
+            _ => {
+                return Err(format!(
+                    "unknown argument `{}`, expected `path` or `name`",
+                    key
+                ))
+            }
+        }
+    }
+    let path = path.ok_or("missing argument `path`")?;
+    let name = name.ok_or("missing argument `name`")?;
+
+    let ident = name.to_uppercase();
+    let is_valid_ident = ident
+        .chars()
+        .enumerate()
+        .all(|(i, c)| c == '_' || c.is_ascii_alphabetic() || (i > 0 && c.is_ascii_digit()));
+    if ident.is_empty() || !is_valid_ident {
+        return Err(format!("`{}` is not a valid name for a static", name));
+    }
+
+    let manifest_dir =
+        std::env::var("CARGO_MANIFEST_DIR").map_err(|_| "CARGO_MANIFEST_DIR is not set")?;
+    let full_path = Path::new(&manifest_dir).join(&path);
+    let contents = std::fs::read_to_string(&full_path)
+        .map_err(|e| format!("failed to read `{}`: {}", full_path.display(), e))?;
+    let words: Vec<String> = contents
+        .lines()
+        .map(str::trim)
+        .filter(|word| !word.is_empty())
+        .map(|word| format!("{:?}", word.to_lowercase()))
+        .collect();
+
+    // `include_bytes!` makes the compiler rebuild the crate when the word file changes.
+    let output = format!(
+        "pub static {}: &[&str] = &[{}];\nconst _: &[u8] = include_bytes!({:?});",
+        ident,
+        words.join(", "),
+        full_path.display().to_string()
+    );
+    TokenStream::from_str(&output).map_err(|e| e.to_string())
+}
+
+/// Parses a comma-separated list of `key = "value"` arguments.
+fn parse_arguments(input: TokenStream) -> Result<Vec<(String, String)>, String> {
+    let mut arguments = Vec::new();
+    let mut tokens = input.into_iter().peekable();
+    while tokens.peek().is_some() {
+        let key = match tokens.next() {
+            Some(TokenTree::Ident(ident)) => ident.to_string(),
+            _ => return Err("expected an argument name".to_string()),
+        };
+        match tokens.next() {
+            Some(TokenTree::Punct(punct)) if punct.as_char() == '=' => {}
+            _ => return Err(format!("expected `=` after `{}`", key)),
+        }
+        let value = match tokens.next() {
+            Some(TokenTree::Literal(literal)) => parse_string_literal(&literal),
+            // macro_rules! forwarding wraps fragments in invisible groups
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::None => {
+                match group.stream().into_iter().next() {
+                    Some(TokenTree::Literal(literal)) => parse_string_literal(&literal),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+        .ok_or_else(|| format!("expected a string literal for `{}`", key))?;
+        arguments.push((key, value));
+        match tokens.next() {
+            None => break,
+            Some(TokenTree::Punct(punct)) if punct.as_char() == ',' => {}
+            _ => return Err("expected `,` between arguments".to_string()),
+        }
+    }
+    Ok(arguments)
+}
+
+/// Gets the value of a string literal, e.g. `"words.txt"` or `r"words.txt"`.
+fn parse_string_literal(literal: &Literal) -> Option<String> {
+    let literal = literal.to_string();
+    if let Some(raw) = literal.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        let content = raw
+            .get(hashes..raw.len().checked_sub(hashes)?)?
+            .strip_prefix('"')?
+            .strip_suffix('"')?;
+        return Some(content.to_string());
+    }
+    let content = literal.strip_prefix('"')?.strip_suffix('"')?;
+    let mut value = String::with_capacity(content.len());
+    let mut chars = content.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+        match chars.next()? {
+            'n' => value.push('\n'),
+            'r' => value.push('\r'),
+            't' => value.push('\t'),
+            '0' => value.push('\0'),
+            c @ ('\\' | '"' | '\'') => value.push(c),
+            // other escapes are not expected in a file path
+            _ => return None,
+        }
+    }
+    Some(value)
+}
+
+fn compile_error(message: &str) -> TokenStream {
+    TokenStream::from_str(&format!("compile_error!({:?});", message)).unwrap()
+}
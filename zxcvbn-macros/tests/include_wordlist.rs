@@ -0,0 +1,22 @@
+use zxcvbn::frequency_lists::DictionaryType;
+use zxcvbn::matching::patterns::MatchPattern;
+use zxcvbn::{zxcvbn_with_options, ZxcvbnOptions};
+use zxcvbn_macros::include_wordlist;
+
+include_wordlist!(path = "tests/words.txt", name = "marsupials");
+
+#[test]
+fn test_include_wordlist() {
+    assert_eq!(MARSUPIALS, &["quokka", "wallaby", "numbat"]);
+}
+
+#[test]
+fn test_include_wordlist_as_extra_dictionary() {
+    let options = ZxcvbnOptions::new().with_extra_dictionary("marsupials", MARSUPIALS);
+    let entropy = zxcvbn_with_options("numbat", &[], &options);
+    let pattern = &entropy.sequence()[0].pattern;
+    assert!(matches!(
+        pattern,
+        MatchPattern::Dictionary(p) if p.dictionary_name == DictionaryType::Custom("marsupials") && p.rank == 3
+    ));
+}
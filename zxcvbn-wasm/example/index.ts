@@ -0,0 +1,26 @@
+import { zxcvbn, Entropy, Score } from "zxcvbn-wasm";
+
+const password = process.argv[2] ?? "Tr0ub4dour&3";
+const result: Entropy = zxcvbn(password, ["jsmith", "jsmith@example.com"]);
+
+const labels: Record<Score, string> = {
+  0: "too guessable",
+  1: "very guessable",
+  2: "somewhat guessable",
+  3: "safely unguessable",
+  4: "very unguessable",
+};
+console.log(`score: ${result.score} (${labels[result.score]})`);
+console.log(`guesses: 10^${result.guesses_log10.toFixed(2)}`);
+for (const [scenario, display] of Object.entries(result.crack_times_display)) {
+  console.log(`${scenario}: ${display}`);
+}
+if (result.feedback.warning) {
+  console.log(`warning: ${result.feedback.warning}`);
+}
+for (const suggestion of result.feedback.suggestions) {
+  console.log(`suggestion: ${suggestion}`);
+}
+for (const m of result.sequence) {
+  console.log(`${m.pattern} '${m.token}' (${m.i}-${m.j})`);
+}
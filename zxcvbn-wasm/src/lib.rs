@@ -0,0 +1,241 @@
+//! WebAssembly bindings of `zxcvbn`, built into an npm package with `wasm-pack`.
+//!
+//! The result of [`zxcvbn`] has the same shape as the one of the JavaScript zxcvbn, with its
+//! snake_case field names, and the generated `.d.ts` file declares its TypeScript types.
+
+#![deny(missing_docs)]
+
+use js_sys::{Array, Object, Reflect};
+use wasm_bindgen::prelude::*;
+use zxcvbn::feedback::Feedback;
+use zxcvbn::frequency_lists::DictionaryType;
+use zxcvbn::matching::patterns::MatchPattern;
+use zxcvbn::time_estimates::{CrackTimes, ScenarioName};
+use zxcvbn::{Entropy, Match};
+
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_DECLARATIONS: &'static str = r#"
+/** Overall strength score, from 0 (too guessable) to 4 (very unguessable). */
+export type Score = 0 | 1 | 2 | 3 | 4;
+
+/** The number of seconds needed to crack the password in each attack scenario. */
+export interface CrackTimesSeconds {
+  online_throttling_100_per_hour: number;
+  online_no_throttling_10_per_second: number;
+  offline_slow_hashing_1e4_per_second: number;
+  offline_fast_hashing_1e10_per_second: number;
+}
+
+/** The time needed to crack the password in each attack scenario, e.g. "3 hours". */
+export interface CrackTimesDisplay {
+  online_throttling_100_per_hour: string;
+  online_no_throttling_10_per_second: string;
+  offline_slow_hashing_1e4_per_second: string;
+  offline_fast_hashing_1e10_per_second: string;
+}
+
+/** Verbal feedback to help choose better passwords. */
+export interface Feedback {
+  /** Explains what's wrong, or "" if there is nothing specific to warn about. */
+  warning: string;
+  suggestions: string[];
+}
+
+/** A predictable pattern found in the password. */
+export interface Match {
+  pattern: "dictionary" | "spatial" | "repeat" | "sequence" | "regex" | "date" | "bruteforce";
+  /** Index of the first character of the token in the password. */
+  i: number;
+  /** Index of the last character of the token in the password. */
+  j: number;
+  token: string;
+  guesses: number;
+  guesses_log10: number;
+  // dictionary
+  matched_word?: string;
+  rank?: number;
+  dictionary_name?: string;
+  reversed?: boolean;
+  l33t?: boolean;
+  sub_display?: string;
+  // spatial
+  graph?: string;
+  turns?: number;
+  shifted_count?: number;
+  // repeat
+  base_token?: string;
+  base_guesses?: number;
+  repeat_count?: number;
+  // sequence
+  sequence_name?: string;
+  sequence_space?: number;
+  ascending?: boolean;
+  // regex
+  regex_name?: string;
+  // date
+  separator?: string;
+  year?: number;
+  month?: number;
+  day?: number;
+}
+
+/** The strength estimate of a password, as returned by `zxcvbn`. */
+export interface Entropy {
+  password: string;
+  guesses: number;
+  guesses_log10: number;
+  crack_times_seconds: CrackTimesSeconds;
+  crack_times_display: CrackTimesDisplay;
+  score: Score;
+  feedback: Feedback;
+  sequence: Match[];
+  /** The time the evaluation took, in milliseconds. */
+  calc_time: number;
+}
+
+/**
+ * Estimates the strength of `password`. The `user_inputs`, e.g. the username or email address,
+ * are matched as a dictionary of the user's personal details.
+ */
+export function zxcvbn(password: string, user_inputs?: string[]): Entropy;
+"#;
+
+/// Estimates the strength of `password`, with `user_inputs` as user-supplied inputs.
+///
+/// Returns an object shaped like the result of the JavaScript zxcvbn, see the `Entropy`
+/// TypeScript interface. User inputs which are not strings are ignored.
+#[wasm_bindgen(js_name = zxcvbn, skip_typescript)]
+pub fn zxcvbn_js(password: &str, user_inputs: Option<Box<[JsValue]>>) -> Result<JsValue, JsValue> {
+    let user_inputs: Vec<String> = user_inputs
+        .unwrap_or_default()
+        .iter()
+        .filter_map(JsValue::as_string)
+        .collect();
+    let user_inputs: Vec<&str> = user_inputs.iter().map(String::as_str).collect();
+    let entropy = zxcvbn::zxcvbn(password, &user_inputs);
+    entropy_to_js(password, &entropy).map(JsValue::from)
+}
+
+fn entropy_to_js(password: &str, entropy: &Entropy) -> Result<Object, JsValue> {
+    let result = Object::new();
+    set(&result, "password", password)?;
+    set(&result, "guesses", entropy.guesses() as f64)?;
+    set(&result, "guesses_log10", entropy.guesses_log10())?;
+    let (seconds, display) = crack_times_to_js(entropy.crack_times())?;
+    set(&result, "crack_times_seconds", seconds)?;
+    set(&result, "crack_times_display", display)?;
+    set(&result, "score", u8::from(entropy.score()))?;
+    set(&result, "feedback", feedback_to_js(entropy.feedback())?)?;
+    let sequence = Array::new();
+    for m in entropy.sequence() {
+        sequence.push(match_to_js(m)?.as_ref());
+    }
+    set(&result, "sequence", sequence)?;
+    set(
+        &result,
+        "calc_time",
+        entropy.calculation_time().as_secs_f64() * 1000.,
+    )?;
+    Ok(result)
+}
+
+fn crack_times_to_js(crack_times: CrackTimes) -> Result<(Object, Object), JsValue> {
+    let seconds = Object::new();
+    let display = Object::new();
+    for scenario in ScenarioName::ALL {
+        let time = crack_times.crack_time(scenario);
+        set(&seconds, scenario.as_str(), time.as_secs_f64())?;
+        set(&display, scenario.as_str(), time.to_string())?;
+    }
+    Ok((seconds, display))
+}
+
+fn feedback_to_js(feedback: Option<&Feedback>) -> Result<Object, JsValue> {
+    let result = Object::new();
+    let warning = feedback
+        .and_then(Feedback::warning)
+        .map_or_else(String::new, |warning| warning.to_string());
+    set(&result, "warning", warning)?;
+    let suggestions = Array::new();
+    for suggestion in feedback.map_or(&[][..], Feedback::suggestions) {
+        suggestions.push(&JsValue::from(suggestion.to_string()));
+    }
+    set(&result, "suggestions", suggestions)?;
+    Ok(result)
+}
+
+fn match_to_js(m: &Match) -> Result<Object, JsValue> {
+    let result = Object::new();
+    let guesses = m.guesses.unwrap_or_default() as f64;
+    set(&result, "i", m.i as f64)?;
+    set(&result, "j", m.j as f64)?;
+    set(&result, "token", m.token.as_str())?;
+    set(&result, "guesses", guesses)?;
+    set(&result, "guesses_log10", guesses.log10())?;
+    match m.pattern {
+        MatchPattern::Dictionary(ref p) => {
+            set(&result, "pattern", "dictionary")?;
+            set(&result, "matched_word", p.matched_word.as_str())?;
+            set(&result, "rank", p.rank as f64)?;
+            set(
+                &result,
+                "dictionary_name",
+                dictionary_name(p.dictionary_name),
+            )?;
+            set(&result, "reversed", p.reversed)?;
+            set(&result, "l33t", p.l33t)?;
+            if let Some(ref sub_display) = p.sub_display {
+                set(&result, "sub_display", sub_display.as_str())?;
+            }
+        }
+        MatchPattern::Spatial(ref p) => {
+            set(&result, "pattern", "spatial")?;
+            set(&result, "graph", p.graph.as_str())?;
+            set(&result, "turns", p.turns as f64)?;
+            set(&result, "shifted_count", p.shifted_count as f64)?;
+        }
+        MatchPattern::Repeat(ref p) => {
+            set(&result, "pattern", "repeat")?;
+            set(&result, "base_token", p.base_token.as_str())?;
+            set(&result, "base_guesses", p.base_guesses as f64)?;
+            set(&result, "repeat_count", p.repeat_count as f64)?;
+        }
+        MatchPattern::Sequence(ref p) => {
+            set(&result, "pattern", "sequence")?;
+            set(&result, "sequence_name", p.sequence_name)?;
+            set(&result, "sequence_space", p.sequence_space)?;
+            set(&result, "ascending", p.ascending)?;
+        }
+        MatchPattern::Regex(ref p) => {
+            set(&result, "pattern", "regex")?;
+            set(&result, "regex_name", p.regex_name)?;
+        }
+        MatchPattern::Date(ref p) => {
+            set(&result, "pattern", "date")?;
+            set(&result, "separator", p.separator.as_str())?;
+            set(&result, "year", p.year)?;
+            set(&result, "month", p.month)?;
+            set(&result, "day", p.day)?;
+        }
+        MatchPattern::BruteForce => set(&result, "pattern", "bruteforce")?,
+        // the patterns of features the package doesn't enable, e.g. custom regular expressions
+        _ => set(&result, "pattern", "regex")?,
+    }
+    Ok(result)
+}
+
+/// The name of a dictionary in the JavaScript zxcvbn, e.g. `"english_wikipedia"`.
+fn dictionary_name(dictionary: DictionaryType) -> &'static str {
+    match dictionary {
+        DictionaryType::UserInputs => "user_inputs",
+        DictionaryType::Organization => "organization",
+        DictionaryType::Phrases => "phrases",
+        DictionaryType::Diceware => "diceware",
+        DictionaryType::Custom(name) => name,
+        builtin => builtin.list_name().unwrap_or_default(),
+    }
+}
+
+fn set(target: &Object, key: &str, value: impl Into<JsValue>) -> Result<(), JsValue> {
+    Reflect::set(target, &JsValue::from_str(key), &value.into()).map(|_| ())
+}
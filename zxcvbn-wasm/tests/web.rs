@@ -0,0 +1,47 @@
+#![cfg(target_arch = "wasm32")]
+
+use js_sys::{Array, Reflect};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::wasm_bindgen_test;
+use zxcvbn_wasm::zxcvbn_js;
+
+fn get(target: &JsValue, key: &str) -> JsValue {
+    Reflect::get(target, &JsValue::from_str(key)).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn test_entropy_shape() {
+    let user_inputs = vec![JsValue::from_str("jsmith")].into_boxed_slice();
+    let entropy = zxcvbn_js("jsmith1987qwerty", Some(user_inputs)).unwrap();
+    assert_eq!(
+        get(&entropy, "password").as_string().unwrap(),
+        "jsmith1987qwerty"
+    );
+    assert_eq!(get(&entropy, "score").as_f64(), Some(3.));
+    assert!(get(&entropy, "guesses").as_f64().unwrap() > 1.);
+    assert!(get(&entropy, "calc_time").as_f64().is_some());
+
+    let display = get(&entropy, "crack_times_display");
+    assert!(get(&display, "offline_fast_hashing_1e10_per_second")
+        .as_string()
+        .is_some());
+
+    let sequence = Array::from(&get(&entropy, "sequence"));
+    assert_eq!(sequence.length(), 3);
+    let first = sequence.get(0);
+    assert_eq!(get(&first, "pattern").as_string().unwrap(), "dictionary");
+    assert_eq!(
+        get(&first, "dictionary_name").as_string().unwrap(),
+        "user_inputs"
+    );
+    assert_eq!(get(&first, "i").as_f64(), Some(0.));
+    assert_eq!(get(&first, "j").as_f64(), Some(5.));
+}
+
+#[wasm_bindgen_test]
+fn test_feedback_of_strong_password() {
+    let entropy = zxcvbn_js("r0sebudmaelstrom11/20/91aaaa", None).unwrap();
+    let feedback = get(&entropy, "feedback");
+    assert_eq!(get(&feedback, "warning").as_string().unwrap(), "");
+    assert_eq!(Array::from(&get(&feedback, "suggestions")).length(), 0);
+}
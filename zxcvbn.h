@@ -0,0 +1,51 @@
+/* C interface to zxcvbn, available with the "ffi" feature. See src/ffi.rs. */
+
+#ifndef ZXCVBN_H
+#define ZXCVBN_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/**
+ * The result of evaluating a password through `zxcvbn_ffi`.
+ */
+typedef struct ZxcvbnResult {
+  /**
+   * Overall strength score from 0-4.
+   */
+  uint8_t score;
+  /**
+   * Estimated guesses needed to crack the password.
+   */
+  uint64_t guesses;
+  /**
+   * Seconds needed to crack the password in an online attack on a service
+   * that doesn't rate-limit, at 10 guesses per second.
+   */
+  double crack_time_online_seconds;
+} ZxcvbnResult;
+
+/**
+ * Evaluates the NUL-terminated UTF-8 `password`, with the `n_inputs` NUL-terminated UTF-8
+ * strings of `user_inputs` as user-supplied inputs.
+ *
+ * Returns a result which must be freed with `zxcvbn_free`, or NULL if `password`,
+ * `user_inputs` or one of the inputs is NULL or not valid UTF-8.
+ * `user_inputs` may be NULL if `n_inputs` is 0.
+ */
+ZxcvbnResult *zxcvbn_ffi(const char *password, const char *const *user_inputs, size_t n_inputs);
+
+/**
+ * Frees a result returned by `zxcvbn_ffi`. Does nothing if `result` is NULL.
+ */
+void zxcvbn_free(ZxcvbnResult *result);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif
+
+#endif  /* ZXCVBN_H */